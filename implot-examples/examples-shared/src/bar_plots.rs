@@ -2,7 +2,7 @@
 //! features of the libray, see the line_plots example.
 
 use imgui::{CollapsingHeader, Ui};
-use implot::{Plot, PlotBars, PlotUi};
+use implot::{set_next_fill_style, set_next_line_style, Plot, PlotBars, PlotUi, YAxisChoice};
 
 pub fn show_basic_vertical_plot(ui: &Ui, plot_ui: &PlotUi) {
     ui.text("This header shows a simple vertical bar plot.");
@@ -32,13 +32,58 @@ pub fn show_basic_horizontal_plot(ui: &Ui, plot_ui: &PlotUi) {
             // If this is called outside a plot build callback, the program will panic.
             let axis_positions = vec![0.2, 0.4, 0.6, 0.8];
             let values = vec![0.1, 0.2, 0.3, 0.4];
+            // For horizontal bars, the bar size is along the Y axis, so it is their height
             PlotBars::new("legend label")
-                .with_bar_width(0.05)
+                .with_bar_height(0.05)
                 .with_horizontal_bars()
                 .plot(&axis_positions, &values);
         });
 }
 
+pub fn show_ranking_plot(ui: &Ui, plot_ui: &PlotUi) {
+    ui.text("This header shows a ranking with horizontal bars and categories on the Y axis.");
+    let content_width = ui.window_content_region_width();
+    // Without explicit positions, the bars are at 0, 1, 2 and so on, which is where the
+    // category labels go
+    let categories = ["Rust", "C++", "Python", "Go"];
+    let scores = [0.9, 0.75, 0.6, 0.4];
+    let tick_labels: Vec<(f64, String)> = categories
+        .iter()
+        .enumerate()
+        .map(|(index, category)| (index as f64, category.to_string()))
+        .collect();
+    Plot::new("Ranking bar plot")
+        .size([content_width, 300.0])
+        .y_ticks_with_labels(YAxisChoice::First, &tick_labels, false)
+        .build(plot_ui, || {
+            PlotBars::new("score")
+                .with_horizontal_bars()
+                .with_bar_height(0.5)
+                .plot_values(&scores);
+        });
+}
+
+pub fn show_fill_styled_plot(ui: &Ui, plot_ui: &PlotUi) {
+    ui.text("This header shows bar plots with fill colors that differ from the line colors.");
+    let content_width = ui.window_content_region_width();
+    Plot::new("Fill styled bar plot")
+        .size([content_width, 300.0])
+        .build(plot_ui, || {
+            let values = vec![0.1, 0.2, 0.3, 0.4];
+            // The fill and line styles only apply to the next item, so there is nothing to pop
+            // afterwards. None keeps the automatic value.
+            set_next_fill_style(Some([0.2, 0.6, 0.2, 1.0].into()), Some(0.5));
+            set_next_line_style(Some([0.1, 0.3, 0.1, 1.0].into()), None);
+            PlotBars::new("first series")
+                .with_bar_width(0.05)
+                .plot(&[0.2, 0.4, 0.6, 0.8], &values);
+            set_next_fill_style(Some([0.8, 0.4, 0.1, 1.0].into()), None);
+            PlotBars::new("second series")
+                .with_bar_width(0.05)
+                .plot(&[0.25, 0.45, 0.65, 0.85], &values);
+        });
+}
+
 pub fn show_demo_headers(ui: &Ui, plot_ui: &PlotUi) {
     if CollapsingHeader::new("Bar plots: Basic vertical").build(ui) {
         show_basic_vertical_plot(ui, plot_ui);
@@ -46,4 +91,10 @@ pub fn show_demo_headers(ui: &Ui, plot_ui: &PlotUi) {
     if CollapsingHeader::new("Bar plots: Basic horizontal").build(ui) {
         show_basic_horizontal_plot(ui, plot_ui);
     }
+    if CollapsingHeader::new("Bar plots: Ranking").build(ui) {
+        show_ranking_plot(ui, plot_ui);
+    }
+    if CollapsingHeader::new("Bar plots: Fill styles").build(ui) {
+        show_fill_styled_plot(ui, plot_ui);
+    }
 }
@@ -0,0 +1,36 @@
+//! This example demonstrates how digital signal plots are to be used. For more general
+//! features of the libray, see the line_plots example.
+
+use imgui::{CollapsingHeader, Ui};
+use implot::{with_style_var, Plot, PlotDigital, PlotLine, PlotUi, StyleVarValue};
+
+pub fn show_logic_analyzer_plot(ui: &Ui, plot_ui: &PlotUi) {
+    ui.text("This header shows digital channels together with an analog trace.");
+    ui.text("The digital channels stack at the bottom, in the order they are plotted in.");
+    let content_width = ui.window_content_region_width();
+    let times: Vec<f64> = (0..200).map(|k| k as f64 * 0.05).collect();
+    let analog: Vec<f64> = times.iter().map(|t| 0.5 + 0.4 * t.sin()).collect();
+    // Nonzero values are high
+    let clock: Vec<f64> = (0..times.len()).map(|k| ((k / 5) % 2) as f64).collect();
+    let comparator: Vec<f64> = analog
+        .iter()
+        .map(|value| if *value > 0.5 { 1.0 } else { 0.0 })
+        .collect();
+
+    Plot::new("Digital plot")
+        .size([content_width, 300.0])
+        .build(plot_ui, || {
+            PlotLine::new("analog").plot(&times, &analog);
+            // The height of the channels and the gap between them are style variables
+            with_style_var(plot_ui, StyleVarValue::DigitalBitHeight(12.0), || {
+                PlotDigital::new("clock").plot(&times, &clock);
+                PlotDigital::new("analog > 0.5").plot(&times, &comparator);
+            });
+        });
+}
+
+pub fn show_demo_headers(ui: &Ui, plot_ui: &PlotUi) {
+    if CollapsingHeader::new("Digital plots").build(ui) {
+        show_logic_analyzer_plot(ui, plot_ui);
+    }
+}
@@ -2,30 +2,71 @@
 //! features of the libray, see the line_plots example.
 
 use imgui::{CollapsingHeader, Ui};
-use implot::{ImPlotPoint, Plot, PlotHeatmap, PlotUi};
+use implot::{
+    lerp_colormap, push_colormap, Colormap, HeatmapWithScale, Plot, PlotHeatmap, PlotText, PlotUi,
+};
 
 pub fn show_basic_heatmap(ui: &Ui, plot_ui: &PlotUi) {
-    ui.text("This header shows a simple heatmap");
-    let content_width = ui.window_content_region_width();
-    Plot::new("Heatmap plot")
-        // The size call could also be omitted, though the defaults don't consider window
-        // width, which is why we're not doing so here.
-        .size([content_width, 300.0])
+    ui.text("This header shows a simple heatmap, with a color scale next to it");
+    let values = (0..100).map(|x| 0.1 * x as f64).collect::<Vec<_>>();
+    // HeatmapWithScale gives the plot the available width minus the room for the color scale,
+    // and draws the scale next to it with the same height and value range.
+    HeatmapWithScale::new(
+        // Only the height of the plot is used, the width comes from the window
+        Plot::new("Heatmap plot").size([0.0, 300.0]),
+        PlotHeatmap::new("my favourite heatmap")
+            // If you omit the with_scale call, the range will be computed based on the values
+            .with_scale(0.0, 10.0)
+            .with_drawing_area([-1.0, -1.0], [1.0, 1.0]),
+    )
+    .build(plot_ui, &values, 10, 10, |heatmap| {
+        // Show the row, column and value of the hovered cell
+        heatmap.show_hovered_cell_tooltip(&values, 10, 10);
+    });
+}
+
+pub fn show_colormap_heatmaps(ui: &Ui, plot_ui: &PlotUi) {
+    ui.text("This header shows two heatmaps next to each other with different colormaps.");
+    let plot_width = ui.window_content_region_width() / 2.0 - 10.0;
+    let values = (0..100).map(|x| 0.1 * x as f64).collect::<Vec<_>>();
+
+    // The colormap is pushed only for the duration of one plot, after popping it, the
+    // colormap that was current before is used again.
+    let pushed_colormap = push_colormap(plot_ui, Colormap::Viridis);
+    Plot::new("Viridis heatmap")
+        .size([plot_width, 300.0])
         .build(plot_ui, || {
-            let values = (0..100).map(|x| 0.1 * x as f64).collect::<Vec<_>>();
-            PlotHeatmap::new("my favourite heatmap")
-                // If you omit the with_scale call, the range will be computed based on the values
+            PlotHeatmap::new("viridis")
                 .with_scale(0.0, 10.0)
-                .with_drawing_area(
-                    ImPlotPoint { x: -1.0, y: -1.0 },
-                    ImPlotPoint { x: 1.0, y: 1.0 },
-                )
                 .plot(&values, 10, 10);
+            // Label the first cell of each row with its value. The text color is picked to be
+            // readable on the color of the cell, which is looked up in the pushed colormap.
+            for row in 0..10 {
+                let value = values[row * 10];
+                let cell_color = lerp_colormap((value / 10.0) as f32);
+                // Row 0 is at the top of the heatmap, which covers [0, 1] in both directions
+                PlotText::new(&format!("{:.1}", value))
+                    .with_auto_text_color(cell_color)
+                    .plot(0.05, 0.95 - 0.1 * row as f64, false);
+            }
         });
+    pushed_colormap.pop();
+
+    ui.same_line();
+    let pushed_colormap = push_colormap(plot_ui, Colormap::Hot);
+    Plot::new("Hot heatmap")
+        .size([plot_width, 300.0])
+        .build(plot_ui, || {
+            PlotHeatmap::new("hot").plot(&values, 10, 10);
+        });
+    pushed_colormap.pop();
 }
 
 pub fn show_demo_headers(ui: &Ui, plot_ui: &PlotUi) {
     if CollapsingHeader::new("Heatmap: Basic").build(ui) {
         show_basic_heatmap(ui, plot_ui);
     }
+    if CollapsingHeader::new("Heatmap: Pushed colormaps").build(ui) {
+        show_colormap_heatmaps(ui, plot_ui);
+    }
 }
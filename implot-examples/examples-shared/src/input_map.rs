@@ -0,0 +1,89 @@
+//! This example demonstrates how to change the mouse buttons and modifier keys ImPlot uses for
+//! interacting with plots.
+
+use imgui::{MouseButton, Ui};
+use implot::{Context, InputMap, KeyModifiers};
+
+/// State of the input map demo. The input map of a context can't be changed while plots are
+/// built with it, so the controls are shown during the frame, and the changes are applied with
+/// [`InputMapDemoState::apply`] before the next one.
+pub struct InputMapDemoState {
+    /// Input map of the context before any changes, used to undo them again
+    default_input_map: Option<InputMap>,
+    swap_select_and_pan: bool,
+    right_click_cancels_selection: bool,
+    /// Options that are currently applied to the context
+    applied: (bool, bool),
+}
+
+impl InputMapDemoState {
+    /// Create a new input map demo state object with default values in it.
+    pub fn new() -> Self {
+        Self {
+            default_input_map: None,
+            swap_select_and_pan: false,
+            right_click_cancels_selection: false,
+            applied: (false, false),
+        }
+    }
+
+    /// Swap the gestures for panning and box selection, so plain drags with the left mouse
+    /// button select and drags with the right mouse button pan.
+    pub fn swap_select_and_pan(input_map: &mut InputMap) {
+        let pan = (input_map.pan_button, input_map.pan_modifiers);
+        input_map.pan_button = input_map.select_button;
+        input_map.pan_modifiers = input_map.select_modifiers;
+        input_map.select_button = pan.0;
+        input_map.select_modifiers = pan.1;
+    }
+
+    /// Box select with the left mouse button while holding shift, and cancel the selection in
+    /// progress with a right click. Queries need ctrl and shift held, so they don't get in the
+    /// way of an application's own right click menus.
+    pub fn cancel_selection_with_right_click(input_map: &mut InputMap) {
+        input_map.select_button = MouseButton::Left;
+        input_map.select_modifiers = KeyModifiers::SHIFT;
+        input_map.select_cancel_button = MouseButton::Right;
+        input_map.query_button = MouseButton::Left;
+        input_map.query_modifiers = KeyModifiers::CTRL | KeyModifiers::SHIFT;
+    }
+
+    /// Show the controls for the input map options.
+    pub fn show_controls(&mut self, ui: &Ui) {
+        ui.checkbox(
+            "Swap panning and box selection",
+            &mut self.swap_select_and_pan,
+        );
+        ui.checkbox(
+            "Shift + left drag selects, right click cancels",
+            &mut self.right_click_cancels_selection,
+        );
+    }
+
+    /// Apply the options chosen with the controls to the given context. Call this before
+    /// getting the plot ui of a frame.
+    pub fn apply(&mut self, context: &mut Context) {
+        let options = (self.swap_select_and_pan, self.right_click_cancels_selection);
+        if options == self.applied {
+            return;
+        }
+        let default_input_map = *self
+            .default_input_map
+            .get_or_insert_with(|| context.input_map());
+        let mut input_map = context.input_map_mut();
+        *input_map = default_input_map;
+        if self.right_click_cancels_selection {
+            Self::cancel_selection_with_right_click(&mut input_map);
+        }
+        if self.swap_select_and_pan {
+            Self::swap_select_and_pan(&mut input_map);
+        }
+        self.applied = options;
+    }
+}
+
+impl Default for InputMapDemoState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,9 +1,14 @@
 pub mod bar_plots;
+mod digital_plots;
 pub mod heatmaps;
+pub mod input_map;
 pub mod line_plots;
+pub mod pie_charts;
 pub mod scatter_plots;
+mod shaded_plots;
 pub mod stairs_plots;
 mod stem_plots;
+pub mod style;
 pub mod text_plots;
 
 use imgui::{Condition, Ui, Window};
@@ -56,6 +61,10 @@ impl DemoState {
                 ui.text("Text plots:");
                 text_plots::show_demo_headers(ui, plot_ui);
 
+                ui.separator();
+                ui.text("Shaded plots:");
+                shaded_plots::show_demo_headers(ui, plot_ui);
+
                 ui.separator();
                 ui.text("Stairs plots:");
                 stairs_plots::show_demo_headers(ui, plot_ui);
@@ -67,6 +76,18 @@ impl DemoState {
                 ui.separator();
                 ui.text("Stem plots:");
                 stem_plots::show_demo_headers(ui, plot_ui);
+
+                ui.separator();
+                ui.text("Pie charts:");
+                pie_charts::show_demo_headers(ui, plot_ui);
+
+                ui.separator();
+                ui.text("Digital plots:");
+                digital_plots::show_demo_headers(ui, plot_ui);
+
+                ui.separator();
+                ui.text("Style:");
+                style::show_demo_headers(ui);
             });
     }
 }
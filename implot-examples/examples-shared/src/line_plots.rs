@@ -3,12 +3,13 @@
 
 use imgui::{CollapsingHeader, Condition, Ui};
 use implot::{
-    get_plot_limits, get_plot_mouse_position, get_plot_query, is_legend_entry_hovered,
-    is_plot_hovered, is_plot_queried, pixels_to_plot_vec2, plot_to_pixels_vec2, push_style_color,
-    push_style_var_f32, push_style_var_i32, set_colormap_from_preset, set_colormap_from_vec,
-    set_plot_y_axis, AxisFlags, Colormap, ImPlotLimits, ImPlotPoint, ImPlotRange, ImVec2, ImVec4,
-    Marker, Plot, PlotColorElement, PlotFlags, PlotLine, PlotLocation, PlotOrientation, PlotUi,
-    StyleVar, YAxisChoice,
+    get_colormap_color, get_plot_limits, get_plot_mouse_position, get_plot_query,
+    is_legend_entry_hovered, is_plot_hovered, is_plot_queried, next_colormap_color,
+    pixels_to_plot_vec2, plot_overlay_text, plot_to_pixels_vec2, push_style, push_style_var_marker,
+    set_colormap_from_preset, set_colormap_from_vec, set_next_line_style, set_plot_y_axis,
+    with_style_color, with_style_var, AxisFlags, Colormap, ImPlotRange, ImVec2, Marker,
+    MeasureTool, Plot, PlotColorElement, PlotFlags, PlotHLines, PlotLimits, PlotLine, PlotLocation,
+    PlotOrientation, PlotPoint, PlotUi, PlotVLines, StyleVarValue, YAxisChoice,
 };
 
 use std::{cell::RefCell, rc::Rc};
@@ -16,6 +17,7 @@ use std::{cell::RefCell, rc::Rc};
 /// State of the line plots demo.
 pub struct LinePlotDemoState {
     linked_limits: Rc<RefCell<ImPlotRange>>,
+    measure_tool: MeasureTool,
 }
 
 impl LinePlotDemoState {
@@ -23,6 +25,7 @@ impl LinePlotDemoState {
     pub fn new() -> Self {
         Self {
             linked_limits: Rc::new(RefCell::new(ImPlotRange { Min: 0.0, Max: 1.0 })),
+            measure_tool: MeasureTool::new(),
         }
     }
 
@@ -159,11 +162,11 @@ impl LinePlotDemoState {
         let content_width = ui.window_content_region_width();
 
         // Create some containers for exfiltrating data from the closure below
-        let mut hover_pos_plot: Option<ImPlotPoint> = None;
+        let mut hover_pos_plot: Option<PlotPoint> = None;
         let mut hover_pos_pixels: Option<ImVec2> = None;
-        let mut hover_pos_from_pixels: Option<ImPlotPoint> = None;
-        let mut plot_limits: Option<ImPlotLimits> = None;
-        let mut query_limits: Option<ImPlotLimits> = None;
+        let mut hover_pos_from_pixels: Option<PlotPoint> = None;
+        let mut plot_limits: Option<PlotLimits> = None;
+        let mut query_limits: Option<PlotLimits> = None;
         let mut legend1_hovered = false;
         let mut legend2_hovered = false;
 
@@ -180,18 +183,12 @@ impl LinePlotDemoState {
             .build(plot_ui, || {
                 if is_plot_hovered() {
                     hover_pos_plot = Some(get_plot_mouse_position(None));
-                    hover_pos_pixels = Some(plot_to_pixels_vec2(&(hover_pos_plot.unwrap()), None));
+                    hover_pos_pixels = Some(plot_to_pixels_vec2(hover_pos_plot.unwrap(), None));
                 }
 
                 // Getting the plot position from pixels also works when the plot is not hovered,
                 // the coordinates are then simply outside the visible range.
-                hover_pos_from_pixels = Some(pixels_to_plot_vec2(
-                    &ImVec2 {
-                        x: ui.io().mouse_pos[0],
-                        y: ui.io().mouse_pos[1],
-                    },
-                    None,
-                ));
+                hover_pos_from_pixels = Some(pixels_to_plot_vec2(ui.io().mouse_pos, None));
 
                 // Plot a line so we have a legend entry
                 PlotLine::new("Legend1").plot(&[2.0, 2.0], &[2.0, 1.0]);
@@ -245,43 +242,76 @@ impl LinePlotDemoState {
         let content_width = ui.window_content_region_width();
 
         // The style stack works the same as for other imgui things - we can push
-        // things to have them apply, then pop again to undo the change. In implot-rs,
-        // pushing returns a value on which we have to call .pop() later. Pushing
-        // variables can be done outside of plot calls as well.
-        let style = push_style_color(&PlotColorElement::PlotBg, 1.0, 1.0, 1.0, 0.2);
-        Plot::new("Style demo plot")
-            .size([content_width, 300.0])
-            .x_limits(ImPlotRange { Min: 0.0, Max: 6.0 }, Condition::Always)
-            .y_limits(
-                ImPlotRange {
-                    Min: -1.0,
-                    Max: 3.0,
-                },
-                YAxisChoice::First,
-                Condition::Always,
-            )
-            .with_plot_flags(&(PlotFlags::NONE))
-            .with_y_axis_flags(YAxisChoice::First, &(AxisFlags::NONE))
+        // things to have them apply, then pop again to undo the change. The recommended way
+        // of doing this in implot-rs is the with_style_* functions, which push, run the given
+        // closure and pop again afterwards. Pushing variables can be done outside of plot
+        // calls as well.
+        with_style_color(
+            plot_ui,
+            PlotColorElement::PlotBg,
+            [1.0, 1.0, 1.0, 0.2],
+            || {
+                Plot::new("Style demo plot")
+                    .size([content_width, 300.0])
+                    .x_limits(ImPlotRange { Min: 0.0, Max: 6.0 }, Condition::Always)
+                    .y_limits(
+                        ImPlotRange {
+                            Min: -1.0,
+                            Max: 3.0,
+                        },
+                        YAxisChoice::First,
+                        Condition::Always,
+                    )
+                    .with_plot_flags(&(PlotFlags::NONE))
+                    .with_y_axis_flags(YAxisChoice::First, &(AxisFlags::NONE))
+                    .build(plot_ui, || {
+                        // Pushing returns a token as well, on which we have to call .pop() later.
+                        // Markers can be selected as shown here.
+                        let markerchoice = push_style_var_marker(plot_ui, Marker::Cross);
+                        PlotLine::new("Left eye").plot(&[2.0, 2.0], &[2.0, 1.0]);
+                        // Calling pop() on the return value of the push above will undo the marker
+                        // choice.
+                        markerchoice.pop();
+
+                        // Line weights can be set in the scoped form as well, along with some other
+                        // things - the value passed determines both the style variable and its new
+                        // value, see the docs of StyleVarValue for more info.
+                        with_style_var(plot_ui, StyleVarValue::LineWeight(5.0), || {
+                            PlotLine::new("Right eye").plot(&[4.0, 4.0], &[2.0, 1.0]);
+                        });
+
+                        // Several colors and variables can be pushed together, and popped with a
+                        // single token.
+                        let nose_style = push_style(
+                            plot_ui,
+                            &[(PlotColorElement::Line, [0.8, 0.2, 0.2, 1.0].into())],
+                            &[
+                                StyleVarValue::LineWeight(2.0),
+                                StyleVarValue::Marker(Marker::Circle),
+                            ],
+                        );
+                        PlotLine::new("Nose").plot(&[3.0, 3.0], &[1.5, 0.8]);
+                        nose_style.pop();
+
+                        let x_values = vec![1.0, 2.0, 4.0, 5.0];
+                        let y_values = vec![1.0, 0.0, 0.0, 1.0];
+                        // For styling a single item, setting the style of the next item is shorter
+                        // than pushing and popping. None keeps the automatic value.
+                        set_next_line_style(Some([1.0, 0.5, 0.0, 1.0].into()), Some(3.0));
+                        PlotLine::new("Mouth").plot(&x_values, &y_values);
+                    });
+            },
+        );
+
+        // Style changes that apply to a single plot can also be set on the plot itself, which
+        // pushes them before the plot begins and pops them after it ends.
+        Plot::new("Style override demo plot")
+            .size([content_width, 150.0])
+            .with_color_override(PlotColorElement::PlotBg, [0.0, 0.0, 0.0, 0.0])
+            .with_style_override(StyleVarValue::PlotBorderSize(0.0))
             .build(plot_ui, || {
-                // Markers can be selected as shown here. The markers are internally represented
-                // as an u32, hence this calling style.
-                let markerchoice = push_style_var_i32(&StyleVar::Marker, Marker::Cross as i32);
-                PlotLine::new("Left eye").plot(&[2.0, 2.0], &[2.0, 1.0]);
-                // Calling pop() on the return value of the push above will undo the marker choice.
-                markerchoice.pop();
-
-                // Line weights can be set the same way, along with some other things - see
-                // the docs of StyleVar for more info.
-                let lineweight = push_style_var_f32(&StyleVar::LineWeight, 5.0);
-                PlotLine::new("Right eye").plot(&[4.0, 4.0], &[2.0, 1.0]);
-                lineweight.pop();
-
-                let x_values = vec![1.0, 2.0, 4.0, 5.0];
-                let y_values = vec![1.0, 0.0, 0.0, 1.0];
-                PlotLine::new("Mouth").plot(&x_values, &y_values);
+                PlotLine::new("Borderless").plot(&[0.0, 1.0, 2.0], &[0.0, 1.0, 0.0]);
             });
-
-        style.pop();
     }
 
     pub fn show_colormaps_plot(ui: &Ui, plot_ui: &PlotUi) {
@@ -307,18 +337,8 @@ impl LinePlotDemoState {
         // so we follow suit. Make sure to set the last number (w in ImVec4) to 1.0 to see anything -
         // it's the alpha channel.
         set_colormap_from_vec(vec![
-            ImVec4 {
-                x: 0.9,
-                y: 0.9,
-                z: 0.0,
-                w: 1.0,
-            },
-            ImVec4 {
-                x: 0.0,
-                y: 0.9,
-                z: 0.9,
-                w: 1.0,
-            },
+            [0.9, 0.9, 0.0, 1.0].into(),
+            [0.0, 0.9, 0.9, 1.0].into(),
         ]);
 
         Plot::new("Colormap demo plot #2")
@@ -371,6 +391,132 @@ impl LinePlotDemoState {
             });
     }
 
+    pub fn show_colormap_overlay_plot(ui: &Ui, plot_ui: &PlotUi) {
+        ui.text("This header shows custom drawing colored consistently with the colormap.");
+        ui.text("The circles are drawn manually, but get the color the next line would get.");
+        let content_width = ui.window_content_region_width();
+        let x_positions = (0..20).map(|k| k as f64 * 0.5).collect::<Vec<_>>();
+        Plot::new("Colormap overlay plot")
+            .size([content_width, 300.0])
+            .build(plot_ui, || {
+                // These two lines get the first two colors of the colormap
+                let sine = x_positions.iter().map(|x| x.sin()).collect::<Vec<_>>();
+                let cosine = x_positions.iter().map(|x| x.cos()).collect::<Vec<_>>();
+                PlotLine::new("sine").plot(&x_positions, &sine);
+                PlotLine::new("cosine").plot(&x_positions, &cosine);
+
+                // The custom circles get the third color. Any line plotted after this would get
+                // the fourth color, since next_colormap_color() advances the color rotation.
+                let color = next_colormap_color();
+                let draw_list = ui.get_window_draw_list();
+                for x in x_positions.iter() {
+                    let center = plot_to_pixels_vec2([*x, 0.5], None);
+                    draw_list
+                        .add_circle(
+                            [center.x, center.y],
+                            4.0,
+                            [color.x, color.y, color.z, color.w],
+                        )
+                        .filled(true)
+                        .build();
+                }
+            });
+    }
+
+    pub fn show_stable_colors_plot(ui: &Ui, plot_ui: &PlotUi) {
+        ui.text("This header shows how to give series stable colors from the colormap.");
+        ui.text("Series 2 is left out, but the other series keep their colors regardless.");
+        let content_width = ui.window_content_region_width();
+        Plot::new("Stable colors plot")
+            .size([content_width, 300.0])
+            .build(plot_ui, || {
+                for series_id in (0..6).filter(|series_id| *series_id != 2) {
+                    // Series i always gets color i of the colormap, wrapping around if there are
+                    // more series than colors
+                    set_next_line_style(Some(get_colormap_color(series_id)), None);
+                    let y = series_id as f64 * 0.1;
+                    PlotLine::new(&format!("series {}", series_id)).plot(&[0.1, 0.9], &[y, y]);
+                }
+            });
+    }
+
+    pub fn show_overlay_text_plot(ui: &Ui, plot_ui: &PlotUi) {
+        ui.text("This header shows how to draw custom text overlays inside a plot.");
+        let content_width = ui.window_content_region_width();
+        Plot::new("Overlay text plot")
+            .size([content_width, 300.0])
+            // The built-in mouse position text is replaced by our own readout below
+            .with_plot_flags(&(PlotFlags::NONE | PlotFlags::NO_MOUSE_POSITION))
+            .build(plot_ui, || {
+                PlotLine::new("legend label").plot(&[0.1, 0.9], &[0.1, 0.9]);
+
+                // Overlay text can be placed at all the locations that legends can be placed at
+                let locations = [
+                    (PlotLocation::North, "North"),
+                    (PlotLocation::South, "South"),
+                    (PlotLocation::West, "West"),
+                    (PlotLocation::East, "East"),
+                    (PlotLocation::NorthWest, "NorthWest"),
+                    (PlotLocation::SouthWest, "SouthWest"),
+                    (PlotLocation::SouthEast, "SouthEast"),
+                ];
+                for (location, name) in locations.iter() {
+                    plot_overlay_text(*location, name);
+                }
+
+                // Multi-line text works too, which is handy for custom mouse position readouts
+                if is_plot_hovered() {
+                    let position = get_plot_mouse_position(None);
+                    plot_overlay_text(
+                        PlotLocation::NorthEast,
+                        &format!("t = {:.2} s\nv = {:.2}", position.x, position.y),
+                    );
+                }
+                plot_overlay_text(PlotLocation::Center, "Center\n(multi-line)");
+            });
+    }
+
+    pub fn show_measurement_plot(&mut self, ui: &Ui, plot_ui: &PlotUi) {
+        ui.text("This header shows how to measure distances in a plot.");
+        ui.text("Hold shift and drag with the left mouse button to measure,");
+        ui.text("hold shift and click the right mouse button to remove the measurement.");
+        let content_width = ui.window_content_region_width();
+        let x_positions = (0..100).map(|k| k as f64 * 0.1).collect::<Vec<_>>();
+        let y_positions = x_positions.iter().map(|x| x.sin()).collect::<Vec<_>>();
+        let measure_tool = &mut self.measure_tool;
+        Plot::new("Measurement plot")
+            .size([content_width, 300.0])
+            .build(plot_ui, || {
+                PlotLine::new("sine").plot(&x_positions, &y_positions);
+                measure_tool.show();
+            });
+
+        // The measured values can also be used outside of the plot
+        if let Some(measurement) = self.measure_tool.measurement("Measurement plot") {
+            ui.text(format!(
+                "Measured dx = {:.3}, dy = {:.3}",
+                measurement.delta_x, measurement.delta_y
+            ));
+        }
+    }
+
+    pub fn show_reference_lines_plot(ui: &Ui, plot_ui: &PlotUi) {
+        ui.text("This header shows lines across the whole plot, for thresholds and events.");
+        ui.text("They stay in place when panning and zooming.");
+        let content_width = ui.window_content_region_width();
+        let x_positions = (0..100).map(|k| k as f64 * 0.1).collect::<Vec<_>>();
+        let y_positions = x_positions.iter().map(|x| x.sin()).collect::<Vec<_>>();
+        Plot::new("Reference lines plot")
+            .size([content_width, 300.0])
+            .build(plot_ui, || {
+                PlotLine::new("sine").plot(&x_positions, &y_positions);
+                PlotHLines::new("thresholds").plot(&[-0.8, 0.8]);
+                with_style_var(plot_ui, StyleVarValue::LineWeight(2.0), || {
+                    PlotVLines::new("events").plot(&[2.5, 4.0, 7.5]);
+                });
+            });
+    }
+
     pub fn show_demo_headers(&mut self, ui: &Ui, plot_ui: &PlotUi) {
         if CollapsingHeader::new("Line plot: Basic").build(ui) {
             Self::show_basic_plot(ui, plot_ui);
@@ -387,6 +533,18 @@ impl LinePlotDemoState {
         if CollapsingHeader::new("Line plot: Colormaps").build(ui) {
             Self::show_colormaps_plot(ui, plot_ui);
         }
+        if CollapsingHeader::new("Line plot: Colormap-colored overlay").build(ui) {
+            Self::show_colormap_overlay_plot(ui, plot_ui);
+        }
+        if CollapsingHeader::new("Line plot: Stable colors").build(ui) {
+            Self::show_stable_colors_plot(ui, plot_ui);
+        }
+        if CollapsingHeader::new("Line plot: Overlay text").build(ui) {
+            Self::show_overlay_text_plot(ui, plot_ui);
+        }
+        if CollapsingHeader::new("Line plot: Measurements").build(ui) {
+            self.show_measurement_plot(ui, plot_ui);
+        }
         if CollapsingHeader::new("Line plot: Multiple Y Axes").build(ui) {
             Self::show_two_yaxis_plot(ui, plot_ui);
         }
@@ -399,6 +557,9 @@ impl LinePlotDemoState {
         if CollapsingHeader::new("Line plot: Linked plots").build(ui) {
             self.show_linked_x_axis_plots(ui, plot_ui);
         }
+        if CollapsingHeader::new("Line plot: Reference lines").build(ui) {
+            Self::show_reference_lines_plot(ui, plot_ui);
+        }
     }
 }
 
@@ -0,0 +1,55 @@
+//! This example demonstrates how pie charts are to be used. For more general
+//! features of the libray, see the line_plots example.
+
+use imgui::{CollapsingHeader, Condition, Ui};
+use implot::{AxisFlags, Plot, PlotFlags, PlotPieChart, PlotUi, YAxisChoice};
+
+/// Set up a plot for showing a pie chart in. Pies are only round with an equal axis ratio,
+/// and the axes don't mean anything for them, so they are hidden.
+fn pie_chart_plot(title: &str, content_width: f32) -> Plot {
+    let axis_flags =
+        AxisFlags::NO_GRID_LINES | AxisFlags::NO_TICK_MARKS | AxisFlags::NO_TICK_LABELS;
+    Plot::new(title)
+        .size([content_width, 300.0])
+        .with_plot_flags(&(PlotFlags::NONE | PlotFlags::AXIS_EQUAL))
+        .with_x_axis_flags(&axis_flags)
+        .with_y_axis_flags(YAxisChoice::First, &axis_flags)
+        .x_limits([0.0, 1.0], Condition::Always)
+        .y1_limits([0.0, 1.0], Condition::Always)
+}
+
+pub fn show_basic_plot(ui: &Ui, plot_ui: &PlotUi) {
+    ui.text("This header shows a simple pie chart.");
+    let content_width = ui.window_content_region_width();
+    pie_chart_plot("Pie chart", content_width).build(plot_ui, || {
+        // Values that add up to one or less are fractions of the whole pie
+        let labels = ["Apples", "Pears", "Plums"];
+        let values = [0.4, 0.35, 0.25];
+        PlotPieChart::new([0.5, 0.5], 0.4)
+            .with_label_format(Some("%.2f"))
+            .plot(&labels, &values);
+    });
+}
+
+pub fn show_normalized_plot(ui: &Ui, plot_ui: &PlotUi) {
+    ui.text("This header shows a pie chart with values that are scaled to fill the pie.");
+    let content_width = ui.window_content_region_width();
+    pie_chart_plot("Normalized pie chart", content_width).build(plot_ui, || {
+        // The labels can be owned strings as well
+        let labels: Vec<String> = (1..=4).map(|i| format!("Slice {}", i)).collect();
+        let values = [0.1, 0.2, 0.05, 0.15];
+        PlotPieChart::new([0.5, 0.5], 0.4)
+            .with_normalize(true)
+            .with_label_format(None)
+            .plot(&labels, &values);
+    });
+}
+
+pub fn show_demo_headers(ui: &Ui, plot_ui: &PlotUi) {
+    if CollapsingHeader::new("Pie chart: Basic").build(ui) {
+        show_basic_plot(ui, plot_ui);
+    }
+    if CollapsingHeader::new("Pie chart: Normalized").build(ui) {
+        show_normalized_plot(ui, plot_ui);
+    }
+}
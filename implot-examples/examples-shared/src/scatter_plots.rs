@@ -2,7 +2,10 @@
 //! features of the libray, see the line_plots example.
 
 use imgui::{CollapsingHeader, Ui};
-use implot::{push_style_var_f32, push_style_var_i32, Marker, Plot, PlotScatter, PlotUi, StyleVar};
+use implot::{
+    get_queried_point_indices, highlight_nearest_point, set_next_marker_style, HighlightStyle,
+    Marker, Plot, PlotFlags, PlotScatter, PlotUi,
+};
 
 pub fn show_basic_plot(ui: &Ui, plot_ui: &PlotUi) {
     ui.text("This header just draws a scatter plot with as little code as possible.");
@@ -27,25 +30,93 @@ pub fn show_custom_markers_plot(ui: &Ui, plot_ui: &PlotUi) {
         // width, which is why we're not doing so here.
         .size([content_width, 300.0])
         .build(plot_ui, || {
-            // Change to cross marker for one scatter plot call
+            // Change to cross marker for one scatter plot call. The marker style only applies
+            // to the next item, and None keeps the automatic value for a setting.
             let x_positions = vec![0.1, 0.2, 0.1, 0.5, 0.9];
             let y_positions = vec![0.1, 0.1, 0.3, 0.3, 0.9];
-            let markerchoice = push_style_var_i32(&StyleVar::Marker, Marker::Cross as i32);
+            set_next_marker_style(Some(Marker::Cross), None, None, None, None);
             PlotScatter::new("legend label 1").plot(&x_positions, &y_positions);
-            markerchoice.pop();
 
-            // One can combine things like marker size and markor choice
+            // One can combine things like marker size, marker choice and colors
             let x_positions = vec![0.4, 0.1];
             let y_positions = vec![0.5, 0.3];
-            let marker_choice = push_style_var_i32(&StyleVar::Marker, Marker::Diamond as i32);
-            let marker_size = push_style_var_f32(&StyleVar::MarkerSize, 12.0);
+            set_next_marker_style(
+                Some(Marker::Diamond),
+                Some(12.0),
+                Some([1.0, 0.8, 0.0, 1.0].into()),
+                None,
+                None,
+            );
             PlotScatter::new("legend label 2").plot(&x_positions, &y_positions);
+        });
+}
+
+pub fn show_query_selection_plot(ui: &Ui, plot_ui: &PlotUi) {
+    ui.text("This header shows how to select points with a query rectangle.");
+    ui.text("Drag with the middle mouse button to query a region of the plot.");
+    let content_width = ui.window_content_region_width();
+
+    // Some scattered data, generated deterministically so it looks the same every frame
+    let x_positions = (0..100)
+        .map(|k| (k as f64 * 0.61803).fract())
+        .collect::<Vec<_>>();
+    let y_positions = (0..100)
+        .map(|k| (k as f64 * 0.41421).fract())
+        .collect::<Vec<_>>();
+    let mut selected_indices = Vec::new();
+
+    Plot::new("Query selection scatter plot")
+        .size([content_width, 300.0])
+        .with_plot_flags(&(PlotFlags::NONE | PlotFlags::QUERY))
+        .build(plot_ui, || {
+            PlotScatter::new("all points").plot(&x_positions, &y_positions);
+
+            // The indices of the points within the query rectangle can then be used to work on
+            // the selected data. Here, we simply plot the selected points again as a separate,
+            // differently-styled series so they stand out.
+            if get_queried_point_indices(&x_positions, &y_positions, None, &mut selected_indices) {
+                let selected_x = selected_indices
+                    .iter()
+                    .map(|&k| x_positions[k])
+                    .collect::<Vec<_>>();
+                let selected_y = selected_indices
+                    .iter()
+                    .map(|&k| y_positions[k])
+                    .collect::<Vec<_>>();
+                set_next_marker_style(Some(Marker::Square), Some(6.0), None, None, None);
+                PlotScatter::new("selected points").plot(&selected_x, &selected_y);
+            }
+        });
+
+    ui.text(format!(
+        "Number of selected points: {}",
+        selected_indices.len()
+    ));
+}
+
+pub fn show_hover_highlight_plot(ui: &Ui, plot_ui: &PlotUi) {
+    ui.text("This header shows how to highlight the point closest to the mouse.");
+    let content_width = ui.window_content_region_width();
+    let x_positions = (0..50).map(|k| k as f64 * 0.02).collect::<Vec<_>>();
+    let y_positions = x_positions
+        .iter()
+        .map(|x| (x * 10.0).sin())
+        .collect::<Vec<_>>();
+    let mut highlighted_index = None;
 
-            // TODO(4bb4) check if these have to be in reverse push order. Does not
-            // seem to be the case.
-            marker_size.pop();
-            marker_choice.pop();
+    Plot::new("Hover highlight scatter plot")
+        .size([content_width, 300.0])
+        .build(plot_ui, || {
+            PlotScatter::new("data").plot(&x_positions, &y_positions);
+            // The highlight is only drawn while the plot is hovered, and does not show up
+            // in the legend.
+            highlighted_index =
+                highlight_nearest_point(&x_positions, &y_positions, &HighlightStyle::new());
         });
+
+    if let Some(index) = highlighted_index {
+        ui.text(format!("Highlighted point index: {}", index));
+    }
 }
 
 pub fn show_demo_headers(ui: &Ui, plot_ui: &PlotUi) {
@@ -56,4 +127,12 @@ pub fn show_demo_headers(ui: &Ui, plot_ui: &PlotUi) {
     if CollapsingHeader::new("Custom markers").build(ui) {
         show_custom_markers_plot(ui, plot_ui);
     }
+
+    if CollapsingHeader::new("Query selection").build(ui) {
+        show_query_selection_plot(ui, plot_ui);
+    }
+
+    if CollapsingHeader::new("Hovered point highlight").build(ui) {
+        show_hover_highlight_plot(ui, plot_ui);
+    }
 }
@@ -0,0 +1,64 @@
+//! This example demonstrates how shaded areas are to be used. For more general
+//! features of the libray, see the line_plots example.
+
+use imgui::{CollapsingHeader, Ui};
+use implot::{with_style_var, Plot, PlotLine, PlotShaded, PlotUi, StyleVarValue};
+
+pub fn show_uncertainty_band_plot(ui: &Ui, plot_ui: &PlotUi) {
+    ui.text("This header shows a line with a shaded uncertainty band around it.");
+    let content_width = ui.window_content_region_width();
+    Plot::new("Shaded plot")
+        .size([content_width, 300.0])
+        .build(plot_ui, || {
+            let x_positions: Vec<f64> = (0..50).map(|i| i as f64 * 0.1).collect();
+            let y_values: Vec<f64> = x_positions.iter().map(|x| x.sin()).collect();
+            // The band gets wider towards the right
+            let y_lower: Vec<f64> = x_positions
+                .iter()
+                .zip(&y_values)
+                .map(|(x, y)| y - 0.1 - 0.05 * x)
+                .collect();
+            let y_upper: Vec<f64> = x_positions
+                .iter()
+                .zip(&y_values)
+                .map(|(x, y)| y + 0.1 + 0.05 * x)
+                .collect();
+
+            // The fill alpha style variable controls how opaque the shaded area is
+            with_style_var(plot_ui, StyleVarValue::FillAlpha(0.25), || {
+                PlotShaded::new("uncertainty").plot_between(&x_positions, &y_lower, &y_upper);
+            });
+            PlotLine::new("estimate").plot(&x_positions, &y_values);
+        });
+}
+
+pub fn show_fill_to_reference_plot(ui: &Ui, plot_ui: &PlotUi) {
+    ui.text("This header shows curves filled down to a reference line.");
+    let content_width = ui.window_content_region_width();
+    Plot::new("Shaded plot to reference")
+        .size([content_width, 300.0])
+        .build(plot_ui, || {
+            let x_positions: Vec<f64> = (0..100).map(|i| i as f64 * 0.05).collect();
+            let envelope: Vec<f64> = x_positions.iter().map(|x| (-x).exp()).collect();
+            let offset_curve: Vec<f64> = envelope.iter().map(|y| y - 0.5).collect();
+
+            with_style_var(plot_ui, StyleVarValue::FillAlpha(0.25), || {
+                PlotShaded::new("envelope").plot_to_ref(&x_positions, &envelope, 0.0);
+                // An infinite reference fills to the bottom (or top) of the plot area
+                PlotShaded::new("to bottom").plot_to_ref(
+                    &x_positions,
+                    &offset_curve,
+                    f64::NEG_INFINITY,
+                );
+            });
+        });
+}
+
+pub fn show_demo_headers(ui: &Ui, plot_ui: &PlotUi) {
+    if CollapsingHeader::new("Shaded plots").build(ui) {
+        show_uncertainty_band_plot(ui, plot_ui);
+    }
+    if CollapsingHeader::new("Shaded plots: Fill to reference").build(ui) {
+        show_fill_to_reference_plot(ui, plot_ui);
+    }
+}
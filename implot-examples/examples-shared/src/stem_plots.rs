@@ -21,8 +21,23 @@ pub fn show_basic_plot(ui: &Ui, plot_ui: &PlotUi) {
         });
 }
 
+pub fn show_signal_plot(ui: &Ui, plot_ui: &PlotUi) {
+    ui.text("This header shows the samples of a discrete-time signal as stems.");
+    let content_width = ui.window_content_region_width();
+    Plot::new("Stem plot of a signal")
+        .size([content_width, 300.0])
+        .build(plot_ui, || {
+            // Without axis positions, the stems are drawn at X positions 0, 1, 2 and so on
+            let samples: Vec<f64> = (0..20).map(|n| (n as f64 * 0.4).sin()).collect();
+            PlotStems::new("samples").plot_values(&samples);
+        });
+}
+
 pub fn show_demo_headers(ui: &Ui, plot_ui: &PlotUi) {
     if CollapsingHeader::new("Stem plots").build(ui) {
         show_basic_plot(ui, plot_ui);
     }
+    if CollapsingHeader::new("Stem plots: Discrete-time signal").build(ui) {
+        show_signal_plot(ui, plot_ui);
+    }
 }
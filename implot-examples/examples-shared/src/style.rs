@@ -0,0 +1,40 @@
+//! This example demonstrates how to tweak the style of plots interactively with ImPlot's style
+//! editor, and how to bring the result into your own code.
+
+use imgui::{CollapsingHeader, Slider, Ui};
+use implot::{get_style, set_anti_aliased_lines, set_fill_alpha, show_style_editor};
+
+pub fn show_style_editor_demo(ui: &Ui) {
+    ui.text_wrapped(
+        "This header shows ImPlot's style editor. Changes made here apply to all the plots \
+         in this demo. Once the plots look the way you like, the button below prints the \
+         style as Rust code that can be applied with Context::set_style.",
+    );
+    if ui.button("Print style as Rust code") {
+        // The printed code assigns to a variable named "style", which can be obtained with
+        // Context::style() and applied with Context::set_style() again afterwards.
+        println!("{}", get_style().to_rust_code());
+    }
+    ui.separator();
+
+    // The two settings below are changed often enough to have setters of their own, which
+    // don't require going through the full style.
+    let style = get_style();
+    let mut anti_aliased_lines = style.anti_aliased_lines;
+    if ui.checkbox("Anti-aliased lines", &mut anti_aliased_lines) {
+        set_anti_aliased_lines(anti_aliased_lines);
+    }
+    let mut fill_alpha = style.fill_alpha;
+    if Slider::new("Fill alpha", 0.0, 1.0).build(ui, &mut fill_alpha) {
+        set_fill_alpha(fill_alpha);
+    }
+    ui.separator();
+    // Passing None lets ImPlot keep the reference style used by the "Revert" button itself.
+    show_style_editor(None);
+}
+
+pub fn show_demo_headers(ui: &Ui) {
+    if CollapsingHeader::new("Style editor").build(ui) {
+        show_style_editor_demo(ui);
+    }
+}
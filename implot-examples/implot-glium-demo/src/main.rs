@@ -1,4 +1,4 @@
-use imgui::{Condition, Window};
+use imgui::{CollapsingHeader, Condition, Window};
 use implot::Context;
 
 // The actual backend-specific code is in this.
@@ -7,26 +7,48 @@ mod support;
 fn main() {
     let system = support::init(file!());
     let mut showing_demo = false;
+    let mut showing_metrics = false;
     let mut showing_rust_demo = true;
     let mut demo_state = examples_shared::DemoState::new();
-    let plotcontext = Context::create();
+    let mut showing_builtin_demo = false;
+    let mut builtin_demo_state = implot::DemoState::new();
+    let mut input_map_state = examples_shared::input_map::InputMapDemoState::new();
+    let mut plotcontext = Context::create();
     system.main_loop(move |_, ui| {
+        // The input map can't be changed while plots are built, so the controls below take
+        // effect at the start of the next frame.
+        input_map_state.apply(&mut plotcontext);
+
         // The context is moved into the closure after creation so plot_ui is valid.
-        let plot_ui = plotcontext.get_plot_ui();
+        let plot_ui = plotcontext.get_plot_ui(ui);
 
         if showing_demo {
             implot::show_demo_window(&mut showing_demo);
         }
 
+        if showing_metrics {
+            implot::show_metrics_window(&mut showing_metrics);
+        }
+
         if showing_rust_demo {
             demo_state.show_demos(ui, &plot_ui);
         }
 
+        if showing_builtin_demo {
+            implot::show_rust_demo_window(ui, &plot_ui, &mut builtin_demo_state);
+        }
+
         Window::new("Welcome to the ImPlot-rs demo!")
             .size([430.0, 450.0], Condition::FirstUseEver)
             .build(ui, || {
                 ui.checkbox("Show C++ ImPlot demo window", &mut showing_demo);
                 ui.checkbox("Show Rust ImPlot demo windows", &mut showing_rust_demo);
+                ui.checkbox(
+                    "Show implot-rs built-in demo window",
+                    &mut showing_builtin_demo,
+                );
+                ui.checkbox("Show ImPlot metrics window", &mut showing_metrics);
+                input_map_state.show_controls(ui);
                 // TODO(4bb4) ... move windows by default so this is less confusing
                 ui.text_wrapped(
                     "Note that the windows are stacked, so move this one out of the way to see\
@@ -36,6 +58,13 @@ fn main() {
                      in particular.
                     ",
                 );
+                if CollapsingHeader::new("Plot controls").build(ui) {
+                    implot::show_user_guide();
+                }
             });
+
+        // In debug builds, this panics if any style or colormap push made this frame has not
+        // been popped again, listing where the pushes were made.
+        implot::debug_check_style_stacks();
     });
 }
@@ -17,6 +17,22 @@ fn main() {
         env::var_os("DEP_IMGUI_THIRD_PARTY").expect("DEP_IMGUI_THIRD_PARTY not defined"),
     );
 
+    // Record the ImPlot version the bindings are generated from, so the implot crate can check
+    // that it matches the ImPlot that implot-sys bundles.
+    let implot_header_path = sys_crate_path
+        .join("third-party")
+        .join("cimplot")
+        .join("implot")
+        .join("implot.h");
+    let implot_header =
+        std::fs::read_to_string(&implot_header_path).expect("Could not read implot.h");
+    let implot_version = implot_header
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("#define IMPLOT_VERSION"))
+        .find(|value| value.starts_with(char::is_whitespace))
+        .map(|value| value.trim().trim_matches('"'))
+        .expect("No IMPLOT_VERSION define found in implot.h");
+
     let bindings = Builder::default()
         .header(
             cimgui_include_path
@@ -39,6 +55,12 @@ fn main() {
         .raw_line("pub use imgui_sys::{ImVec2, ImVec4, ImGuiCond, ImTextureID};")
         .raw_line("pub use imgui_sys::{ImGuiContext, ImGuiKeyModFlags, ImDrawList};")
         .raw_line("pub use imgui_sys::{ImGuiMouseButton, ImGuiDragDropFlags};")
+        .raw_line(format!(
+            "/// Version of ImPlot these bindings were generated from, as given by \
+             `IMPLOT_VERSION` in implot.h.\n\
+             pub const BINDINGS_IMPLOT_VERSION: &str = {:?};",
+            implot_version
+        ))
         .whitelist_recursively(false)
         .whitelist_function("ImPlot.*")
         .whitelist_type("ImPlot.*")
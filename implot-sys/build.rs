@@ -9,11 +9,18 @@ const CPP_FILES: &[&str] = &[
     "third-party/cimplot/cimplot.cpp",
     "third-party/cimplot/implot/implot.cpp",
     "third-party/cimplot/implot/implot_items.cpp",
-    "third-party/cimplot/implot/implot_demo.cpp", // Could remove this if demo not used
 ];
 
+// The demo is only built with the "demo" feature. Without it, a stub provides the
+// ShowDemoWindow symbol that cimplot.cpp refers to.
+const DEMO_CPP_FILE: &str = "third-party/cimplot/implot/implot_demo.cpp";
+const DEMO_STUB_CPP_FILE: &str = "src/implot_demo_stub.cpp";
+
 const IMPLOT_INCLUDE_DIRECTORIES: &[&str] = &["third-party/cimplot/implot/"];
 
+// The header that defines IMPLOT_VERSION, which is exposed as a Rust constant.
+const IMPLOT_HEADER_FILE: &str = "third-party/cimplot/implot/implot.h";
+
 fn assert_file_exists(path: &str) -> io::Result<()> {
     match fs::metadata(path) {
         Ok(_) => Ok(()),
@@ -27,7 +34,56 @@ fn assert_file_exists(path: &str) -> io::Result<()> {
     }
 }
 
+// Write the IMPLOT_VERSION define of the ImPlot header to version.rs in OUT_DIR, which the
+// crate includes.
+fn write_version_file() -> io::Result<()> {
+    assert_file_exists(IMPLOT_HEADER_FILE)?;
+    let header = fs::read_to_string(IMPLOT_HEADER_FILE)?;
+    let version = header
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("#define IMPLOT_VERSION"))
+        // Skip other defines that start with the same name
+        .find(|value| value.starts_with(char::is_whitespace))
+        .map(|value| value.trim().trim_matches('"'))
+        .unwrap_or_else(|| panic!("No IMPLOT_VERSION define found in {}", IMPLOT_HEADER_FILE));
+    let out_dir = env::var_os("OUT_DIR").expect("OUT_DIR not defined");
+    fs::write(
+        Path::new(&out_dir).join("version.rs"),
+        format!(
+            "/// Version of the bundled ImPlot, as given by `IMPLOT_VERSION` in implot.h.\n\
+             pub const IMPLOT_VERSION: &str = {:?};\n",
+            version
+        ),
+    )?;
+    Ok(())
+}
+
+// Write a version.rs that fails the build of the crate with a compile_error!, so the reason
+// shows up as a regular compiler error instead of a build script panic.
+fn write_unsupported_target_file(target: &str) -> io::Result<()> {
+    let out_dir = env::var_os("OUT_DIR").expect("OUT_DIR not defined");
+    let message = format!(
+        "implot-sys can't be built for {}, since there is no C++ standard library for it. \
+         Use wasm32-unknown-emscripten for the web.",
+        target
+    );
+    fs::write(
+        Path::new(&out_dir).join("version.rs"),
+        format!(
+            "compile_error!({:?});\npub const IMPLOT_VERSION: &str = \"\";\n",
+            message
+        ),
+    )
+}
+
 fn main() -> io::Result<()> {
+    let target_arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_default();
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+    if target_arch == "wasm32" && target_os != "emscripten" {
+        // Skip compiling ImPlot, which would fail with less helpful errors
+        return write_unsupported_target_file(&env::var("TARGET").unwrap_or_default());
+    }
+
     // --- Compile cimgui
     let mut build = cc::Build::new();
     build.cpp(true);
@@ -57,10 +113,20 @@ fn main() -> io::Result<()> {
     build.flag_if_supported("-Wno-return-type-c-linkage");
     build.flag_if_supported("-Wno-unused-parameter");
     build.flag_if_supported("-std=c++11");
-    for path in CPP_FILES {
+    if target_os == "emscripten" {
+        // cc picks emcc for this target, which brings its own sysroot. ImPlot does not use
+        // exceptions, and leaving them out matches the default of emscripten's linker.
+        build.flag("-fno-exceptions");
+    }
+    let demo_file = if env::var_os("CARGO_FEATURE_DEMO").is_some() {
+        DEMO_CPP_FILE
+    } else {
+        DEMO_STUB_CPP_FILE
+    };
+    for path in CPP_FILES.iter().chain(std::iter::once(&demo_file)) {
         assert_file_exists(path)?;
         build.file(path);
     }
     build.compile("cimplot");
-    Ok(())
+    write_version_file()
 }
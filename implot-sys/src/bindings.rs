@@ -1,8 +1,10 @@
 /* automatically generated by rust-bindgen 0.57.0 */
 
-pub use imgui_sys::{ImVec2, ImVec4, ImGuiCond, ImTextureID};
-pub use imgui_sys::{ImGuiContext, ImGuiKeyModFlags, ImDrawList};
-pub use imgui_sys::{ImGuiMouseButton, ImGuiDragDropFlags};
+pub use imgui_sys::{ImDrawList, ImGuiContext, ImGuiKeyModFlags};
+pub use imgui_sys::{ImGuiCond, ImTextureID, ImVec2, ImVec4};
+pub use imgui_sys::{ImGuiDragDropFlags, ImGuiMouseButton};
+/// Version of ImPlot these bindings were generated from, as given by `IMPLOT_VERSION` in implot.h.
+pub const BINDINGS_IMPLOT_VERSION: &str = "0.9 WIP";
 
 pub type ImS8 = ::std::os::raw::c_schar;
 pub type ImU8 = ::std::os::raw::c_uchar;
@@ -0,0 +1,5 @@
+// Stands in for implot_demo.cpp when the "demo" feature of implot-sys is off, so the demo isn't
+// compiled into the library while cimplot.cpp still finds the function it wraps.
+#include "implot.h"
+
+void ImPlot::ShowDemoWindow(bool*) {}
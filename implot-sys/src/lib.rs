@@ -6,8 +6,13 @@
 #[cfg(test)]
 use imgui_sys;
 
-use std::ops::Range;
+use std::ops::{Range, RangeInclusive};
 include!("bindings.rs");
+include!(concat!(env!("OUT_DIR"), "/version.rs"));
+
+/// Version of this crate. The implot crate compares it with its own version when creating a
+/// context, since mismatched pairs of the two crates can disagree about the bindings.
+pub const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 impl From<Range<f64>> for ImPlotRange {
     fn from(from: Range<f64>) -> Self {
@@ -18,6 +23,27 @@ impl From<Range<f64>> for ImPlotRange {
     }
 }
 
+impl From<RangeInclusive<f64>> for ImPlotRange {
+    fn from(from: RangeInclusive<f64>) -> Self {
+        ImPlotRange {
+            Min: *from.start(),
+            Max: *from.end(),
+        }
+    }
+}
+
+impl From<ImPlotRange> for Range<f64> {
+    fn from(from: ImPlotRange) -> Self {
+        from.Min..from.Max
+    }
+}
+
+impl From<ImPlotRange> for RangeInclusive<f64> {
+    fn from(from: ImPlotRange) -> Self {
+        from.Min..=from.Max
+    }
+}
+
 impl From<[f64; 2]> for ImPlotRange {
     fn from(from: [f64; 2]) -> Self {
         ImPlotRange {
@@ -45,6 +71,179 @@ impl From<ImVec2> for ImPlotRange {
     }
 }
 
+impl ImPlotRange {
+    /// Returns `count` evenly spaced values from `Min` to `Max`, both included, for example to
+    /// generate x values that cover the current view. Inverted ranges give decreasing values, a
+    /// count of one gives just `Min`.
+    pub fn linspace(&self, count: usize) -> impl ExactSizeIterator<Item = f64> {
+        let (min, max) = (self.Min, self.Max);
+        let step = if count > 1 {
+            (max - min) / (count - 1) as f64
+        } else {
+            0.0
+        };
+        (0..count).map(move |i| {
+            // The last value is Max itself, so rounding in the steps does not show up there
+            if i + 1 == count && count > 1 {
+                max
+            } else {
+                min + step * i as f64
+            }
+        })
+    }
+
+    /// Returns the range with `Min` and `Max` swapped if it is inverted, so that `Min <= Max`.
+    /// The geometry methods below work on normalized ranges, and always return normalized
+    /// ranges, so inverted inputs give the same results as their normalized counterparts.
+    pub fn normalized(&self) -> Self {
+        ImPlotRange {
+            Min: self.Min.min(self.Max),
+            Max: self.Min.max(self.Max),
+        }
+    }
+
+    /// Returns the size of the range, which is never negative.
+    pub fn size(&self) -> f64 {
+        (self.Max - self.Min).abs()
+    }
+
+    /// Returns whether the value lies within the range, bounds included.
+    pub fn contains(&self, value: f64) -> bool {
+        let range = self.normalized();
+        range.Min <= value && value <= range.Max
+    }
+
+    /// Returns whether the other range lies completely within this one, bounds included.
+    pub fn contains_range(&self, other: &ImPlotRange) -> bool {
+        let other = other.normalized();
+        self.contains(other.Min) && self.contains(other.Max)
+    }
+
+    /// Returns the overlap of the two ranges, or `None` if they don't overlap. Ranges that only
+    /// touch give an empty range at the touching point.
+    pub fn intersect(&self, other: &ImPlotRange) -> Option<ImPlotRange> {
+        let (a, b) = (self.normalized(), other.normalized());
+        let range = ImPlotRange {
+            Min: a.Min.max(b.Min),
+            Max: a.Max.min(b.Max),
+        };
+        if range.Min <= range.Max {
+            Some(range)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the smallest range that contains both ranges.
+    pub fn union(&self, other: &ImPlotRange) -> ImPlotRange {
+        let (a, b) = (self.normalized(), other.normalized());
+        ImPlotRange {
+            Min: a.Min.min(b.Min),
+            Max: a.Max.max(b.Max),
+        }
+    }
+
+    /// Returns the range grown by `fraction` of its size on both sides, so `0.1` adds a 10%
+    /// margin at either end. Negative fractions shrink the range, down to an empty range at
+    /// its center.
+    pub fn inflate(&self, fraction: f64) -> ImPlotRange {
+        self.inflate_absolute(self.size() * fraction)
+    }
+
+    /// Returns the range grown by `amount` on both sides. Negative amounts shrink the range,
+    /// down to an empty range at its center.
+    pub fn inflate_absolute(&self, amount: f64) -> ImPlotRange {
+        let range = self.normalized();
+        let amount = amount.max(-range.size() / 2.0);
+        ImPlotRange {
+            Min: range.Min - amount,
+            Max: range.Max + amount,
+        }
+    }
+}
+
+impl ImPlotLimits {
+    /// Create limits from the X and Y ranges.
+    pub fn from_ranges(x: Range<f64>, y: Range<f64>) -> Self {
+        ImPlotLimits {
+            X: x.into(),
+            Y: y.into(),
+        }
+    }
+
+    /// Returns the X and Y ranges of the limits.
+    pub fn to_ranges(&self) -> (Range<f64>, Range<f64>) {
+        (self.X.into(), self.Y.into())
+    }
+
+    /// Returns the limits with both ranges normalized, see [`ImPlotRange::normalized`]. Like
+    /// for ranges, the geometry methods below treat inverted limits like normalized ones.
+    pub fn normalized(&self) -> Self {
+        ImPlotLimits {
+            X: self.X.normalized(),
+            Y: self.Y.normalized(),
+        }
+    }
+
+    /// Returns whether the point lies within the limits, edges included. Useful for checking
+    /// whether an annotation is visible, for example.
+    pub fn contains_point(&self, point: &ImPlotPoint) -> bool {
+        self.X.contains(point.x) && self.Y.contains(point.y)
+    }
+
+    /// Returns whether the other limits lie completely within these, edges included.
+    pub fn contains_range(&self, other: &ImPlotLimits) -> bool {
+        self.X.contains_range(&other.X) && self.Y.contains_range(&other.Y)
+    }
+
+    /// Returns the overlap of the two limits, or `None` if they don't overlap on either axis.
+    pub fn intersect(&self, other: &ImPlotLimits) -> Option<ImPlotLimits> {
+        Some(ImPlotLimits {
+            X: self.X.intersect(&other.X)?,
+            Y: self.Y.intersect(&other.Y)?,
+        })
+    }
+
+    /// Returns the smallest limits that contain both limits, for example to merge queries.
+    pub fn union(&self, other: &ImPlotLimits) -> ImPlotLimits {
+        ImPlotLimits {
+            X: self.X.union(&other.X),
+            Y: self.Y.union(&other.Y),
+        }
+    }
+
+    /// Returns the limits grown by `fraction` of their size on every side, see
+    /// [`ImPlotRange::inflate`]. Use this to fit data with a margin, by inflating its bounds
+    /// before setting them as the plot limits.
+    pub fn inflate(&self, fraction: f64) -> ImPlotLimits {
+        ImPlotLimits {
+            X: self.X.inflate(fraction),
+            Y: self.Y.inflate(fraction),
+        }
+    }
+
+    /// Returns the limits grown by `dx` on the left and right, and by `dy` at the top and
+    /// bottom, see [`ImPlotRange::inflate_absolute`].
+    pub fn inflate_absolute(&self, dx: f64, dy: f64) -> ImPlotLimits {
+        ImPlotLimits {
+            X: self.X.inflate_absolute(dx),
+            Y: self.Y.inflate_absolute(dy),
+        }
+    }
+}
+
+impl From<(Range<f64>, Range<f64>)> for ImPlotLimits {
+    fn from(from: (Range<f64>, Range<f64>)) -> Self {
+        ImPlotLimits::from_ranges(from.0, from.1)
+    }
+}
+
+impl From<ImPlotLimits> for (Range<f64>, Range<f64>) {
+    fn from(from: ImPlotLimits) -> Self {
+        from.to_ranges()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -71,4 +270,123 @@ mod tests {
         assert_eq!(im_range.Min, imvec.x as f64);
         assert_eq!(im_range.Max, imvec.y as f64);
     }
+    #[test]
+    fn test_plot_range_to_std_ranges() {
+        let im_range = ImPlotRange { Min: 2.0, Max: 3.0 };
+        assert_eq!(Range::from(im_range), 2.0..3.0);
+        assert_eq!(RangeInclusive::from(im_range), 2.0..=3.0);
+        let back: ImPlotRange = (2.0..=3.0).into();
+        assert_eq!((back.Min, back.Max), (2.0, 3.0));
+
+        // Empty and inverted ranges are kept as they are
+        let empty = ImPlotRange { Min: 1.0, Max: 1.0 };
+        assert!(Range::from(empty).is_empty());
+        assert!(!RangeInclusive::from(empty).is_empty());
+        let inverted = ImPlotRange {
+            Min: 4.0,
+            Max: -4.0,
+        };
+        assert_eq!(Range::from(inverted), 4.0..-4.0);
+        let back: ImPlotRange = (4.0..=-4.0).into();
+        assert_eq!((back.Min, back.Max), (4.0, -4.0));
+    }
+
+    #[test]
+    fn test_plot_limits_ranges() {
+        let limits = ImPlotLimits::from_ranges(0.0..1.0, -2.0..2.0);
+        assert_eq!((limits.X.Min, limits.X.Max), (0.0, 1.0));
+        assert_eq!((limits.Y.Min, limits.Y.Max), (-2.0, 2.0));
+        assert_eq!(limits.to_ranges(), (0.0..1.0, -2.0..2.0));
+
+        let inverted: ImPlotLimits = (5.0..5.0, 3.0..-3.0).into();
+        let ranges: (Range<f64>, Range<f64>) = inverted.into();
+        assert_eq!(ranges, (5.0..5.0, 3.0..-3.0));
+    }
+
+    #[test]
+    fn test_linspace() {
+        let range = ImPlotRange { Min: 0.0, Max: 1.0 };
+        assert_eq!(
+            range.linspace(5).collect::<Vec<_>>(),
+            vec![0.0, 0.25, 0.5, 0.75, 1.0]
+        );
+        assert_eq!(range.linspace(5).len(), 5);
+        assert_eq!(range.linspace(1).collect::<Vec<_>>(), vec![0.0]);
+        assert_eq!(range.linspace(0).count(), 0);
+
+        let inverted = ImPlotRange {
+            Min: 1.0,
+            Max: -1.0,
+        };
+        assert_eq!(
+            inverted.linspace(3).collect::<Vec<_>>(),
+            vec![1.0, 0.0, -1.0]
+        );
+        let empty = ImPlotRange { Min: 2.0, Max: 2.0 };
+        assert_eq!(empty.linspace(3).collect::<Vec<_>>(), vec![2.0, 2.0, 2.0]);
+
+        // The last value is exactly the maximum, despite rounding in the steps
+        let range = ImPlotRange { Min: 0.1, Max: 0.7 };
+        assert_eq!(range.linspace(7).last(), Some(0.7));
+    }
+    fn bounds(range: ImPlotRange) -> (f64, f64) {
+        (range.Min, range.Max)
+    }
+
+    #[test]
+    fn test_range_geometry() {
+        let range = ImPlotRange { Min: 0.0, Max: 4.0 };
+        let inverted = ImPlotRange { Min: 4.0, Max: 0.0 };
+        for range in &[range, inverted] {
+            assert!(range.contains(0.0) && range.contains(2.0) && range.contains(4.0));
+            assert!(!range.contains(-0.1) && !range.contains(4.1));
+            assert!(range.contains_range(&(3.0..1.0).into()));
+            assert!(!range.contains_range(&(3.0..5.0).into()));
+            assert_eq!(bounds(range.normalized()), (0.0, 4.0));
+            assert_eq!(range.size(), 4.0);
+        }
+
+        let other = ImPlotRange { Min: 6.0, Max: 2.0 };
+        assert_eq!(bounds(inverted.intersect(&other).unwrap()), (2.0, 4.0));
+        assert_eq!(bounds(inverted.union(&other)), (0.0, 6.0));
+        let touching = ImPlotRange { Min: 4.0, Max: 5.0 };
+        assert_eq!(bounds(range.intersect(&touching).unwrap()), (4.0, 4.0));
+        assert!(range.intersect(&(5.0..6.0).into()).is_none());
+
+        assert_eq!(bounds(inverted.inflate(0.25)), (-1.0, 5.0));
+        assert_eq!(bounds(range.inflate_absolute(0.5)), (-0.5, 4.5));
+        assert_eq!(bounds(range.inflate(-0.25)), (1.0, 3.0));
+        // Shrinking stops at the center
+        assert_eq!(bounds(range.inflate(-2.0)), (2.0, 2.0));
+        assert_eq!(bounds(range.inflate_absolute(-10.0)), (2.0, 2.0));
+        // Empty ranges stay empty when inflated by a fraction
+        let empty = ImPlotRange { Min: 1.0, Max: 1.0 };
+        assert_eq!(bounds(empty.inflate(0.5)), (1.0, 1.0));
+        assert_eq!(bounds(empty.inflate_absolute(1.0)), (0.0, 2.0));
+    }
+
+    #[test]
+    fn test_limits_geometry() {
+        let limits = ImPlotLimits::from_ranges(0.0..2.0, 10.0..0.0);
+        assert!(limits.contains_point(&ImPlotPoint { x: 1.0, y: 5.0 }));
+        assert!(limits.contains_point(&ImPlotPoint { x: 2.0, y: 0.0 }));
+        assert!(!limits.contains_point(&ImPlotPoint { x: 1.0, y: 11.0 }));
+        assert!(limits.contains_range(&ImPlotLimits::from_ranges(0.5..1.5, 2.0..8.0)));
+        assert!(!limits.contains_range(&ImPlotLimits::from_ranges(0.5..2.5, 2.0..8.0)));
+
+        let other = ImPlotLimits::from_ranges(1.0..3.0, 5.0..15.0);
+        let intersection = limits.intersect(&other).unwrap();
+        assert_eq!(intersection.to_ranges(), (1.0..2.0, 5.0..10.0));
+        assert_eq!(limits.union(&other).to_ranges(), (0.0..3.0, 0.0..15.0));
+        // Overlapping on one axis only is no overlap
+        let beside = ImPlotLimits::from_ranges(3.0..4.0, 0.0..10.0);
+        assert!(limits.intersect(&beside).is_none());
+
+        assert_eq!(limits.inflate(0.1).to_ranges(), (-0.2..2.2, -1.0..11.0));
+        assert_eq!(
+            limits.inflate_absolute(1.0, 2.0).to_ranges(),
+            (-1.0..3.0, -2.0..12.0)
+        );
+        assert_eq!(limits.normalized().to_ranges(), (0.0..2.0, 0.0..10.0));
+    }
 }
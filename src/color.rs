@@ -0,0 +1,264 @@
+//! # Color module
+//!
+//! This module contains helpers for creating colors from the formats palettes are commonly
+//! given in, such as hex strings. The resulting colors can be used with the colormap and style
+//! functions of this crate.
+use crate::ImVec4;
+use std::fmt;
+
+/// Errors that can occur when parsing hex color strings.
+#[derive(Clone, Debug, PartialEq)]
+pub enum HexColorError {
+    /// The string does not have 3, 6 or 8 hex digits (after the optional leading `#`)
+    InvalidLength(String),
+    /// The string contains characters that are not hex digits
+    InvalidDigit(String),
+}
+
+impl fmt::Display for HexColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HexColorError::InvalidLength(hex) => write!(
+                f,
+                "\"{}\" is not a color of the form #RGB, #RRGGBB or #RRGGBBAA",
+                hex
+            ),
+            HexColorError::InvalidDigit(hex) => {
+                write!(f, "\"{}\" contains characters that are not hex digits", hex)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HexColorError {}
+
+/// Extension trait for creating `ImVec4` colors from other color formats. `ImVec4` is defined in
+/// imgui-sys, so these can't be inherent methods - import this trait to use them, for example
+/// as `ImVec4::from_hex("#1f77b4")`.
+pub trait ImVec4Ext: Sized {
+    /// Create a color from a packed `0xAABBGGRR` value, which is the layout imgui uses for
+    /// packed colors (red in the lowest byte).
+    fn from_rgba_u32(packed: u32) -> Self;
+
+    /// Create a color from a hex string of the form `#RGB`, `#RRGGBB` or `#RRGGBBAA`. The
+    /// leading `#` is optional, and colors without alpha are fully opaque.
+    fn from_hex(hex: &str) -> Result<Self, HexColorError>;
+}
+
+impl ImVec4Ext for ImVec4 {
+    fn from_rgba_u32(packed: u32) -> Self {
+        let channel = |shift: u32| ((packed >> shift) & 0xFF) as f32 / 255.0;
+        ImVec4 {
+            x: channel(0),
+            y: channel(8),
+            z: channel(16),
+            w: channel(24),
+        }
+    }
+
+    fn from_hex(hex: &str) -> Result<Self, HexColorError> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(HexColorError::InvalidDigit(hex.to_owned()));
+        }
+        // All characters are ASCII at this point, so slicing by byte index is fine
+        let channel = |index: usize, width: usize| {
+            let value = u8::from_str_radix(&digits[index * width..(index + 1) * width], 16)
+                .expect("Hex digits were checked before");
+            // Single digits stand for both digits of the channel, so "f" is "ff"
+            let value = if width == 1 { value * 0x11 } else { value };
+            value as f32 / 255.0
+        };
+        match digits.len() {
+            3 => Ok(ImVec4 {
+                x: channel(0, 1),
+                y: channel(1, 1),
+                z: channel(2, 1),
+                w: 1.0,
+            }),
+            6 | 8 => Ok(ImVec4 {
+                x: channel(0, 2),
+                y: channel(1, 2),
+                z: channel(2, 2),
+                w: if digits.len() == 8 {
+                    channel(3, 2)
+                } else {
+                    1.0
+                },
+            }),
+            _ => Err(HexColorError::InvalidLength(hex.to_owned())),
+        }
+    }
+}
+
+/// Convert a list of hex color strings into colors, see [`ImVec4Ext::from_hex`] for the accepted
+/// formats. The result can be passed on to
+/// [`set_colormap_from_slice`](fn.set_colormap_from_slice.html) or
+/// [`register_colormap`](fn.register_colormap.html):
+/// ```no_run
+/// # use implot::{colormap_from_hex, set_colormap_from_slice};
+/// let colors = colormap_from_hex(&["#1f77b4", "#ff7f0e", "#2ca02c"]).unwrap();
+/// set_colormap_from_slice(&colors).unwrap();
+/// ```
+pub fn colormap_from_hex(hex_colors: &[&str]) -> Result<Vec<ImVec4>, HexColorError> {
+    hex_colors.iter().map(|hex| ImVec4::from_hex(hex)).collect()
+}
+
+/// Returns the relative luminance of an sRGB color as defined by WCAG 2, from 0 for black to 1
+/// for white. The alpha channel is ignored.
+pub fn relative_luminance<C: Into<ImVec4>>(color: C) -> f32 {
+    let color = color.into();
+    let linear = |channel: f32| {
+        if channel <= 0.040_45 {
+            channel / 12.92
+        } else {
+            ((channel + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(color.x) + 0.7152 * linear(color.y) + 0.0722 * linear(color.z)
+}
+
+/// Returns the WCAG 2 contrast ratio between two colors, from 1 for equal luminance to 21 for
+/// black on white. The order of the colors does not matter, and the alpha channels are ignored.
+/// WCAG asks for a ratio of at least 4.5 for regular text.
+pub fn contrast_ratio<C: Into<ImVec4>, D: Into<ImVec4>>(a: C, b: D) -> f32 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Returns opaque black or white, whichever has the higher contrast ratio (see
+/// [`contrast_ratio`]) with the given background color. This keeps text readable on top of
+/// colors that aren't known in advance, such as heatmap cells colored by a colormap. The alpha
+/// channel of the background is ignored.
+pub fn contrasting_text_color<C: Into<ImVec4>>(background: C) -> ImVec4 {
+    // The contrast ratios with black and white are equal at this luminance
+    const THRESHOLD: f32 = 0.179;
+    let value = if relative_luminance(background) > THRESHOLD {
+        0.0
+    } else {
+        1.0
+    };
+    ImVec4 {
+        x: value,
+        y: value,
+        z: value,
+        w: 1.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn components(color: ImVec4) -> [f32; 4] {
+        [color.x, color.y, color.z, color.w]
+    }
+
+    #[test]
+    fn test_from_hex_formats() {
+        let expected = [1.0, 0x22 as f32 / 255.0, 0.0, 1.0];
+        for hex in &["#f20", "f20", "#ff2200", "ff2200", "#FF2200ff", "FF2200FF"] {
+            assert_eq!(components(ImVec4::from_hex(hex).unwrap()), expected);
+        }
+        assert_eq!(
+            components(ImVec4::from_hex("#00000080").unwrap()),
+            [0.0, 0.0, 0.0, 128.0 / 255.0]
+        );
+    }
+
+    #[test]
+    fn test_from_hex_errors() {
+        for hex in &["", "#", "#ff", "#ff00", "#ff00000", "#ff0000000"] {
+            assert_eq!(
+                ImVec4::from_hex(hex),
+                Err(HexColorError::InvalidLength(hex.to_string()))
+            );
+        }
+        for hex in &["#ff00zz", "##ff0000", "+f00", "#ff 000", "#ffé00"] {
+            assert_eq!(
+                ImVec4::from_hex(hex),
+                Err(HexColorError::InvalidDigit(hex.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_rgba_u32() {
+        assert_eq!(
+            components(ImVec4::from_rgba_u32(0x80FF0000)),
+            [0.0, 0.0, 1.0, 128.0 / 255.0]
+        );
+        assert_eq!(
+            components(ImVec4::from_rgba_u32(0xFF0000FF)),
+            [1.0, 0.0, 0.0, 1.0]
+        );
+    }
+
+    #[test]
+    fn test_colormap_from_hex() {
+        assert_eq!(colormap_from_hex(&["#000", "#fff"]).unwrap().len(), 2);
+        assert_eq!(
+            colormap_from_hex(&["#000", "nope"]),
+            Err(HexColorError::InvalidDigit("nope".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_contrasting_text_color_flips_at_threshold() {
+        let black = [0.0, 0.0, 0.0, 1.0];
+        let white = [1.0, 1.0, 1.0, 1.0];
+        let gray = |value: f32| ImVec4 {
+            x: value,
+            y: value,
+            z: value,
+            w: 1.0,
+        };
+        // Gray levels get brighter monotonically, so the text color flips exactly once
+        let mut flips = 0;
+        let mut previous = white;
+        for level in 0..=255 {
+            let background = gray(level as f32 / 255.0);
+            let text = components(contrasting_text_color(background));
+            if text != previous {
+                flips += 1;
+                previous = text;
+            }
+            let expected = if relative_luminance(background) > 0.179 {
+                black
+            } else {
+                white
+            };
+            assert_eq!(text, expected);
+            // The chosen color is never the worse choice
+            let other = if text == black { white } else { black };
+            assert!(
+                contrast_ratio(background, contrasting_text_color(background))
+                    >= contrast_ratio(background, other)
+            );
+        }
+        assert_eq!(flips, 1);
+
+        // Saturated colors with the same channel values differ in luminance
+        assert_eq!(
+            components(contrasting_text_color([0.0, 0.0, 1.0, 1.0])),
+            white
+        );
+        assert_eq!(
+            components(contrasting_text_color([0.0, 1.0, 0.0, 1.0])),
+            black
+        );
+        assert_eq!(
+            components(contrasting_text_color((1.0, 0.0, 0.0, 1.0))),
+            black
+        );
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        let black: ImVec4 = [0.0, 0.0, 0.0, 1.0].into();
+        let white: ImVec4 = [1.0, 1.0, 1.0, 1.0].into();
+        assert!((contrast_ratio(black, white) - 21.0).abs() < 1e-4);
+        assert!((contrast_ratio(white, black) - 21.0).abs() < 1e-4);
+        assert!((contrast_ratio(white, white) - 1.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,670 @@
+//! # Colormap module
+//!
+//! This module contains helpers for referring to colormaps by name, for example when the choice
+//! of colormap comes from a settings UI or a configuration file, and for registering custom named
+//! colormaps. The basic colormap functions, such as [`push_colormap`](fn.push_colormap.html), are
+//! in the crate root.
+use crate::context::debug_check_thread;
+#[cfg(feature = "imgui-rs")]
+use crate::imgui_compat::Ui;
+use crate::stack_check::{has_outstanding_pushes, StackKind};
+use crate::{
+    get_colormap_color, lerp_colormap_samples, push_colormap, push_colormap_choice,
+    set_next_fill_style, set_next_line_style, sys, Colormap, ColormapToken, ImVec4, PlotUi,
+    UnknownNameError,
+};
+use parking_lot::Mutex;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+/// All custom colormaps registered so far. These are never removed, since ImPlot keeps pointers
+/// to the colors of pushed colormaps.
+static CUSTOM_COLORMAPS: Mutex<Vec<CustomColormap>> = parking_lot::const_mutex(Vec::new());
+
+/// Colors of the colormaps set with `set_colormap_from_slice`, see [`StoredColormaps`].
+static SET_COLORMAPS: Mutex<StoredColormaps> = parking_lot::const_mutex(StoredColormaps::new());
+
+/// A copy of the colors of a colormap set with `set_colormap_from_slice`.
+struct StoredColormap {
+    /// Hash of the bit patterns of the colors, for finding colormaps without comparing all the
+    /// colors
+    hash: u64,
+    /// The colors, which stay at the same address as long as they are stored
+    colors: Box<[ImVec4]>,
+    /// Raw pointers of the contexts that have these colors as their current colormap
+    current_in: Vec<usize>,
+    /// Raw pointers of the contexts where these colors may have been saved on the colormap
+    /// stack, by pushing another colormap while they were the current one
+    on_stack_in: Vec<usize>,
+}
+
+/// Copies of the colors of the colormaps set with `set_colormap_from_slice`. ImPlot keeps the
+/// pointer to the colors instead of copying them, so a copy is kept as long as a context can
+/// still refer to it: while it is the current colormap of a context, and while it may be on the
+/// colormap stack of a context. Colormaps that are set repeatedly share the same copy.
+struct StoredColormaps {
+    colormaps: Vec<StoredColormap>,
+}
+
+impl StoredColormaps {
+    const fn new() -> Self {
+        Self {
+            colormaps: Vec::new(),
+        }
+    }
+
+    /// Store the colors as the current colormap of the given context, and return the stored
+    /// copy. `stack_is_empty` tells whether the colormap stack of the context is empty, in which
+    /// case none of the colors it used before can be on it anymore.
+    fn set(&mut self, context: usize, colors: &[ImVec4], stack_is_empty: bool) -> *const ImVec4 {
+        self.remove_context(context, stack_is_empty);
+        let hash = hash_colors(colors);
+        let index = match self
+            .colormaps
+            .iter()
+            .position(|stored| stored.hash == hash && *stored.colors == *colors)
+        {
+            Some(index) => index,
+            None => {
+                self.colormaps.push(StoredColormap {
+                    hash,
+                    colors: colors.into(),
+                    current_in: Vec::new(),
+                    on_stack_in: Vec::new(),
+                });
+                self.colormaps.len() - 1
+            }
+        };
+        let stored = &mut self.colormaps[index];
+        stored.current_in.push(context);
+        let colors_ptr = stored.colors.as_ptr();
+        self.free_unused();
+        colors_ptr
+    }
+
+    /// Note that the current colormap of the given context is pushed onto its colormap stack.
+    fn push(&mut self, context: usize) {
+        for stored in &mut self.colormaps {
+            if stored.current_in.contains(&context) && !stored.on_stack_in.contains(&context) {
+                stored.on_stack_in.push(context);
+            }
+        }
+    }
+
+    /// Note that the given context no longer uses its current colormap, and no longer uses any
+    /// colormap on its stack if `stack_is_empty` is true. Colormaps that no context uses anymore
+    /// are freed.
+    fn release(&mut self, context: usize, stack_is_empty: bool) {
+        self.remove_context(context, stack_is_empty);
+        self.free_unused();
+    }
+
+    /// Internal helper that removes the given context from the users of the colormaps, like
+    /// [`StoredColormaps::release`], without freeing any colormaps yet.
+    fn remove_context(&mut self, context: usize, stack_is_empty: bool) {
+        for stored in &mut self.colormaps {
+            stored.current_in.retain(|&other| other != context);
+            if stack_is_empty {
+                stored.on_stack_in.retain(|&other| other != context);
+            }
+        }
+    }
+
+    /// Internal helper that frees the colormaps that no context uses anymore.
+    fn free_unused(&mut self) {
+        self.colormaps
+            .retain(|stored| !stored.current_in.is_empty() || !stored.on_stack_in.is_empty());
+    }
+}
+
+/// Internal helper that hashes the bit patterns of the colors.
+fn hash_colors(colors: &[ImVec4]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for color in colors {
+        [color.x, color.y, color.z, color.w]
+            .iter()
+            .for_each(|component| component.to_bits().hash(&mut hasher));
+    }
+    hasher.finish()
+}
+
+/// Returns the current context as a key for `StoredColormaps`.
+fn current_context_key() -> usize {
+    unsafe { sys::ImPlot_GetCurrentContext() as usize }
+}
+
+/// Internal helper that sets a copy of the colors as the colormap of the current context, see
+/// [`StoredColormaps`].
+pub(crate) fn set_stored_colormap(colors: &[ImVec4]) {
+    let mut set_colormaps = SET_COLORMAPS.lock();
+    let stack_is_empty = !has_outstanding_pushes(StackKind::Colormap);
+    let colors_ptr = set_colormaps.set(current_context_key(), colors, stack_is_empty);
+    // Still locked, so the colors can't be freed before ImPlot refers to them
+    unsafe {
+        sys::ImPlot_SetColormapVec4Ptr(colors_ptr, colors.len() as i32);
+    }
+}
+
+/// Internal helper to call after a preset colormap was set on the current context, which then no
+/// longer refers to the colors of a colormap set before.
+pub(crate) fn release_stored_colormap() {
+    let stack_is_empty = !has_outstanding_pushes(StackKind::Colormap);
+    SET_COLORMAPS
+        .lock()
+        .release(current_context_key(), stack_is_empty);
+}
+
+/// Internal helper to call before a colormap is pushed onto the stack of the current context.
+pub(crate) fn push_stored_colormap() {
+    SET_COLORMAPS.lock().push(current_context_key());
+}
+
+/// Internal helper to call when a context is destroyed, after which it refers to no colors.
+pub(crate) fn release_stored_colormaps_of(context: *mut sys::ImPlotContext) {
+    SET_COLORMAPS.lock().release(context as usize, true);
+}
+
+/// Errors that can occur when creating colormaps.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ColormapError {
+    /// A colormap with the given name already exists
+    DuplicateName(String),
+    /// The colormap has fewer than the two colors ImPlot needs
+    TooFewColors(usize),
+}
+
+impl fmt::Display for ColormapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColormapError::DuplicateName(name) => {
+                write!(f, "a colormap named \"{}\" already exists", name)
+            }
+            ColormapError::TooFewColors(count) => write!(
+                f,
+                "a colormap needs at least 2 colors, but {} were given",
+                count
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ColormapError {}
+
+/// Handle to a custom colormap created with [`register_colormap`]. This can be used in the same
+/// places as the preset [`Colormap`](enum.Colormap.html)s, see [`ColormapChoice`].
+#[derive(Copy, Clone, Debug)]
+pub struct CustomColormap {
+    /// Name the colormap was registered with
+    name: &'static str,
+    /// Colors of the colormap
+    colors: &'static [ImVec4],
+}
+
+impl CustomColormap {
+    /// Returns the name the colormap was registered with.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the colors of the colormap.
+    pub fn colors(&self) -> &'static [ImVec4] {
+        self.colors
+    }
+}
+
+/// Choice of colormap, which is either one of the built-in presets or a registered custom
+/// colormap. Functions taking this accept both a [`Colormap`](enum.Colormap.html) and a
+/// [`CustomColormap`] directly.
+#[derive(Copy, Clone, Debug)]
+pub enum ColormapChoice {
+    /// One of the built-in preset colormaps
+    Preset(Colormap),
+    /// A colormap registered with [`register_colormap`]
+    Custom(CustomColormap),
+}
+
+impl ColormapChoice {
+    /// Returns the name of the colormap.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColormapChoice::Preset(preset) => preset.name(),
+            ColormapChoice::Custom(custom) => custom.name(),
+        }
+    }
+
+    /// Returns all the preset colormaps, followed by all the registered custom colormaps.
+    pub fn all() -> Vec<ColormapChoice> {
+        Colormap::ALL
+            .iter()
+            .map(|preset| ColormapChoice::Preset(*preset))
+            .chain(
+                CUSTOM_COLORMAPS
+                    .lock()
+                    .iter()
+                    .map(|custom| ColormapChoice::Custom(*custom)),
+            )
+            .collect()
+    }
+
+    /// Returns the preset or custom colormap with the given name, or `None` if there is none.
+    /// The comparison is case-sensitive.
+    pub fn from_name(name: &str) -> Option<ColormapChoice> {
+        Colormap::from_name(name)
+            .map(ColormapChoice::Preset)
+            .or_else(|| {
+                CUSTOM_COLORMAPS
+                    .lock()
+                    .iter()
+                    .find(|custom| custom.name == name)
+                    .map(|custom| ColormapChoice::Custom(*custom))
+            })
+    }
+}
+
+impl From<Colormap> for ColormapChoice {
+    fn from(preset: Colormap) -> Self {
+        ColormapChoice::Preset(preset)
+    }
+}
+
+impl From<CustomColormap> for ColormapChoice {
+    fn from(custom: CustomColormap) -> Self {
+        ColormapChoice::Custom(custom)
+    }
+}
+
+/// Register a custom colormap under the given name, so it can be used like the built-in presets
+/// and can be found by [`push_colormap_by_name`]. ImPlot needs at least two colors per colormap.
+/// Registering a name that is already in use, including the names of the presets, fails with
+/// [`ColormapError::DuplicateName`].
+///
+/// Registered colormaps are kept for the rest of the program, so this is meant to be called once
+/// per colormap, for example at startup:
+/// ```no_run
+/// # use implot::{push_colormap, register_colormap, ImVec4, PlotUi};
+/// # fn example(plot_ui: &PlotUi) {
+/// let palette = register_colormap(
+///     "Company palette",
+///     &[
+///         ImVec4 { x: 0.0, y: 0.2, z: 0.4, w: 1.0 },
+///         ImVec4 { x: 1.0, y: 0.6, z: 0.0, w: 1.0 },
+///     ],
+/// )
+/// .unwrap();
+/// // Later on, while plotting
+/// let pushed_colormap = push_colormap(plot_ui, palette);
+/// // Plot some things
+/// pushed_colormap.pop();
+/// # }
+/// ```
+pub fn register_colormap(name: &str, colors: &[ImVec4]) -> Result<CustomColormap, ColormapError> {
+    if colors.len() < 2 {
+        return Err(ColormapError::TooFewColors(colors.len()));
+    }
+
+    let mut custom_colormaps = CUSTOM_COLORMAPS.lock();
+    if Colormap::from_name(name).is_some() || custom_colormaps.iter().any(|c| c.name == name) {
+        return Err(ColormapError::DuplicateName(name.to_owned()));
+    }
+    let custom = CustomColormap {
+        name: Box::leak(name.to_owned().into_boxed_str()),
+        colors: Box::leak(colors.to_vec().into_boxed_slice()),
+    };
+    custom_colormaps.push(custom);
+    Ok(custom)
+}
+
+impl Colormap {
+    /// All the built-in preset colormaps, in the order ImPlot defines them in.
+    pub const ALL: [Colormap; 11] = [
+        Colormap::Standard,
+        Colormap::Deep,
+        Colormap::Dark,
+        Colormap::Pastel,
+        Colormap::Paired,
+        Colormap::Viridis,
+        Colormap::Plasma,
+        Colormap::Hot,
+        Colormap::Cool,
+        Colormap::Pink,
+        Colormap::Jet,
+    ];
+
+    /// Returns all the built-in preset colormaps, for example to offer them in a settings UI.
+    /// This is [`Colormap::ALL`] as a slice.
+    pub fn all() -> &'static [Colormap] {
+        &Colormap::ALL
+    }
+
+    /// Returns the name ImPlot uses for this colormap, such as `"Viridis"`. Note that the name
+    /// of [`Colormap::Standard`] is `"Default"`.
+    #[rustversion::attr(since(1.48), doc(alias = "GetColormapName"))]
+    pub fn name(self) -> &'static str {
+        // Same as ImPlot's table of names, which can't be read without a context
+        match self {
+            Colormap::Standard => "Default",
+            Colormap::Deep => "Deep",
+            Colormap::Dark => "Dark",
+            Colormap::Pastel => "Pastel",
+            Colormap::Paired => "Paired",
+            Colormap::Viridis => "Viridis",
+            Colormap::Plasma => "Plasma",
+            Colormap::Hot => "Hot",
+            Colormap::Cool => "Cool",
+            Colormap::Pink => "Pink",
+            Colormap::Jet => "Jet",
+        }
+    }
+
+    /// Returns the preset colormap with the given name, or `None` if there is none. The
+    /// comparison is case-sensitive.
+    pub fn from_name(name: &str) -> Option<Colormap> {
+        Colormap::ALL
+            .iter()
+            .copied()
+            .find(|colormap| colormap.name() == name)
+    }
+}
+
+impl FromStr for Colormap {
+    type Err = UnknownNameError;
+
+    /// Parses the name of a preset colormap, see [`Colormap::name`]. Use
+    /// [`ColormapChoice::from_name`] to include registered custom colormaps.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Colormap::from_name(name).ok_or_else(|| UnknownNameError {
+            kind: "colormap",
+            name: name.to_owned(),
+        })
+    }
+}
+
+/// Returns the names of all the colormaps that can be used with [`push_colormap_by_name`], for
+/// example to offer them in a dropdown. These are the names of the presets, followed by the
+/// names of the registered custom colormaps.
+pub fn colormap_names() -> Vec<&'static str> {
+    ColormapChoice::all()
+        .iter()
+        .map(|colormap| colormap.name())
+        .collect()
+}
+
+/// Push the colormap with the given name to the colormap stack, see
+/// [`push_colormap`](fn.push_colormap.html). Returns `None` and leaves the stack unchanged if
+/// there is no colormap with the given name. The known names can be listed with
+/// [`colormap_names`].
+#[rustversion::attr(since(1.48), doc(alias = "PushColormap"))]
+#[track_caller]
+pub fn push_colormap_by_name<'ui>(plot_ui: &'ui PlotUi, name: &str) -> Option<ColormapToken<'ui>> {
+    // Not using Option::map here, so the push is attributed to the caller in debug checks
+    let colormap = ColormapChoice::from_name(name)?;
+    Some(push_colormap(plot_ui, colormap))
+}
+
+/// Fills `out` with `samples` evenly spaced colors of the given colormap, or of the current
+/// colormap if `colormap` is `None`. The output vector is cleared first, which allows reusing its
+/// allocation. The first and last sample are exactly the first and last color of the colormap,
+/// the samples in between are interpolated with [`lerp_colormap`](fn.lerp_colormap.html).
+/// ```no_run
+/// # use implot::{resample_colormap, Colormap};
+/// let mut gradient = Vec::new();
+/// resample_colormap(Some(Colormap::Viridis.into()), 32, &mut gradient);
+/// ```
+pub fn resample_colormap(colormap: Option<ColormapChoice>, samples: usize, out: &mut Vec<ImVec4>) {
+    debug_check_thread();
+    out.clear();
+    out.resize(
+        samples,
+        ImVec4 {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: 0.0,
+        },
+    );
+    // Pushed and popped right here, so this doesn't need a PlotUi for tying a token to a frame
+    if let Some(colormap) = colormap {
+        push_colormap_choice(colormap);
+    }
+    lerp_colormap_samples(out);
+    if colormap.is_some() {
+        unsafe {
+            sys::ImPlot_PopColormap(1);
+        }
+    }
+}
+
+/// Show a combo box for picking a colormap out of the presets and the registered custom
+/// colormaps, with a preview of the colors of each of them. Returns true if the user picked a
+/// different colormap, in which case `current` has been updated. This is an imgui widget to be
+/// used outside of plots, for example in a settings window. Only available with the `imgui-rs`
+/// feature, which is on by default.
+/// ```no_run
+/// # use implot::{colormap_selector, push_colormap, Colormap, ColormapChoice, PlotUi};
+/// # fn example(ui: &imgui::Ui, plot_ui: &PlotUi, current: &mut ColormapChoice) {
+/// // current is stored somewhere that persists across frames, and initialized with for example
+/// // ColormapChoice::from(Colormap::Viridis)
+/// colormap_selector(ui, "Colormap", current);
+/// let pushed_colormap = push_colormap(plot_ui, *current);
+/// // Plot some things
+/// pushed_colormap.pop();
+/// # }
+/// ```
+#[cfg(feature = "imgui-rs")]
+pub fn colormap_selector(ui: &Ui, label: &str, current: &mut ColormapChoice) -> bool {
+    const NAME_WIDTH: f32 = 120.0;
+    const PREVIEW_WIDTH: f32 = 100.0;
+    const PREVIEW_SAMPLES: usize = 16;
+
+    let combo = match ui.begin_combo(label, current.name()) {
+        Some(combo) => combo,
+        None => return false,
+    };
+    let mut changed = false;
+    let mut samples = Vec::with_capacity(PREVIEW_SAMPLES);
+    for colormap in ColormapChoice::all() {
+        let is_current = colormap.name() == current.name();
+        if ui
+            .selectable_config(colormap.name())
+            .selected(is_current)
+            .size([NAME_WIDTH, 0.0])
+            .build()
+            && !is_current
+        {
+            *current = colormap;
+            changed = true;
+        }
+        if is_current {
+            ui.set_item_default_focus();
+        }
+
+        // Draw the preview as a row of horizontal gradients between neighboring samples
+        ui.same_line();
+        let top_left = ui.cursor_screen_pos();
+        let height = ui.text_line_height();
+        resample_colormap(Some(colormap), PREVIEW_SAMPLES, &mut samples);
+        let segment_width = PREVIEW_WIDTH / (PREVIEW_SAMPLES - 1) as f32;
+        let draw_list = ui.get_window_draw_list();
+        for (index, pair) in samples.windows(2).enumerate() {
+            let left = [pair[0].x, pair[0].y, pair[0].z, pair[0].w];
+            let right = [pair[1].x, pair[1].y, pair[1].z, pair[1].w];
+            let x = top_left[0] + index as f32 * segment_width;
+            draw_list.add_rect_filled_multicolor(
+                [x, top_left[1]],
+                [x + segment_width, top_left[1] + height],
+                left,
+                right,
+                right,
+                left,
+            );
+        }
+        ui.dummy([PREVIEW_WIDTH, height]);
+    }
+    combo.end();
+    changed
+}
+
+/// Assigns colormap colors to series by a stable key, so each series keeps its color when other
+/// series appear, disappear or change order. Without this, ImPlot gives each item the next color
+/// of the colormap in plotting order, so the colors change along with the order.
+///
+/// Keys get colormap indices in the order they are first seen, starting at 0. If there are more
+/// keys than colors in the colormap, the indices wrap around, so key number `n` gets the color
+/// `n % colormap size` and shares it with earlier keys. The keys can be anything hashable, such
+/// as series names or IDs:
+/// ```no_run
+/// # use implot::{ColorAssigner, Plot, PlotLine, PlotUi};
+/// # fn example(plot_ui: &PlotUi, colors: &mut ColorAssigner<String>, names: &[String]) {
+/// # let (x, y) = ([0.0, 1.0], [0.0, 1.0]);
+/// // Created once, for example in the application state:
+/// // let colors = ColorAssigner::new();
+/// Plot::new("Dashboard").build(plot_ui, || {
+///     for name in names {
+///         colors.apply(name);
+///         PlotLine::new(name).plot(&x, &y);
+///     }
+/// });
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ColorAssigner<K: Eq + Hash + Clone> {
+    /// Colormap index assigned to each key seen so far
+    indices: HashMap<K, usize>,
+    /// Index that the next new key gets
+    next_index: usize,
+}
+
+impl<K: Eq + Hash + Clone> Default for ColorAssigner<K> {
+    fn default() -> Self {
+        Self {
+            indices: HashMap::new(),
+            next_index: 0,
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone> ColorAssigner<K> {
+    /// Create a new color assigner without any keys assigned yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the colormap index assigned to the given key, assigning the next index if the key
+    /// is new. The index can be larger than the colormap, see [`ColorAssigner`] for how this
+    /// wraps around.
+    pub fn index(&mut self, key: &K) -> usize {
+        if let Some(index) = self.indices.get(key) {
+            return *index;
+        }
+        let index = self.next_index;
+        self.indices.insert(key.clone(), index);
+        self.next_index += 1;
+        index
+    }
+
+    /// Returns the color of the current colormap assigned to the given key, assigning one if the
+    /// key is new.
+    pub fn color(&mut self, key: &K) -> ImVec4 {
+        let index = self.index(key);
+        get_colormap_color(index)
+    }
+
+    /// Set the line and fill color of the next item to the color assigned to the given key,
+    /// assigning one if the key is new. Call this right before plotting the item for the key.
+    pub fn apply(&mut self, key: &K) {
+        let color = self.color(key);
+        set_next_line_style(Some(color), None);
+        set_next_fill_style(Some(color), None);
+    }
+
+    /// Forget the index assigned to the given key. The index is not given to other keys, so the
+    /// colors of the remaining keys stay the same, and the key gets a new index if it is seen
+    /// again.
+    pub fn remove(&mut self, key: &K) {
+        self.indices.remove(key);
+    }
+
+    /// Forget all assigned indices, so the next key seen gets index 0 again.
+    pub fn clear(&mut self) {
+        self.indices.clear();
+        self.next_index = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_colormap_name_round_trip() {
+        for colormap in Colormap::all() {
+            let parsed: Colormap = colormap.name().parse().unwrap();
+            assert_eq!(parsed.name(), colormap.name());
+        }
+        assert_eq!(
+            Colormap::from_name("Default").map(Colormap::name),
+            Some("Default")
+        );
+        assert!("Standard".parse::<Colormap>().is_err());
+    }
+
+    #[test]
+    fn test_color_assigner_indices_are_stable() {
+        let mut assigner = ColorAssigner::new();
+        assert_eq!(assigner.index(&"a"), 0);
+        assert_eq!(assigner.index(&"b"), 1);
+        // Seeing keys again, in any order, keeps their indices
+        assert_eq!(assigner.index(&"b"), 1);
+        assert_eq!(assigner.index(&"a"), 0);
+
+        // Removed keys don't free up their index for other keys
+        assigner.remove(&"a");
+        assert_eq!(assigner.index(&"c"), 2);
+        assert_eq!(assigner.index(&"a"), 3);
+
+        assigner.clear();
+        assert_eq!(assigner.index(&"c"), 0);
+    }
+
+    #[test]
+    fn test_stored_colormaps_are_kept_while_used() {
+        let gray = |value| ImVec4 {
+            x: value,
+            y: value,
+            z: value,
+            w: 1.0,
+        };
+        let first = [gray(0.0), gray(1.0)];
+        let second = [gray(0.5), gray(1.0)];
+        let mut stored = StoredColormaps::new();
+
+        // Setting the same colors again reuses the copy, also for other contexts
+        let first_ptr = stored.set(1, &first, true);
+        assert_eq!(stored.set(1, &first, true), first_ptr);
+        assert_eq!(stored.set(2, &first, true), first_ptr);
+        assert_eq!(stored.colormaps.len(), 1);
+
+        // The copy is freed once no context has the colors as its current colormap
+        stored.set(1, &second, true);
+        assert_eq!(stored.colormaps.len(), 2);
+        stored.release(2, true);
+        assert_eq!(stored.colormaps.len(), 1);
+
+        // Colors pushed over stay until the stack of the context is empty again
+        stored.push(1);
+        stored.set(1, &first, false);
+        assert_eq!(stored.colormaps.len(), 2);
+        stored.set(1, &first, false);
+        assert_eq!(stored.colormaps.len(), 2);
+        stored.set(1, &first, true);
+        assert_eq!(stored.colormaps.len(), 1);
+        assert_eq!(*stored.colormaps[0].colors, first);
+
+        // Preset colormaps and destroyed contexts use no stored colors
+        stored.release(1, true);
+        assert!(stored.colormaps.is_empty());
+    }
+}
@@ -3,94 +3,751 @@
 //! also helps readability if one is already familiar with the imgui code.
 
 use parking_lot::ReentrantMutex;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::thread::{self, ThreadId};
 
+use crate::imgui_compat::imgui_context_exists;
+#[cfg(feature = "imgui-rs")]
+use crate::imgui_compat::{ImguiContext, Ui};
+use crate::style::COLOR_ELEMENTS;
 use crate::sys;
-use crate::PlotUi;
+use crate::{
+    set_colormap_from_preset, set_colormap_from_slice, ColormapChoice, ImVec2, ImVec4,
+    InputMapGuard, PlotColorElement, PlotUi, Style,
+};
 /// An implot context.
 ///
 /// A context is required to do most of the things this library provides. While this was created
-/// implicitly in earlier versions of the library, it is now created explicitly. These contexts
-/// cannot currently be disabled through the high level API. This could be implemented though,
-/// if you need multiple contexts that you can switch around between, file an issue.
+/// implicitly in earlier versions of the library, it is now created explicitly.
+///
+/// Several contexts can exist at the same time, for example one per imgui context. ImPlot draws
+/// with the current context, which is the one created last until another one is made current
+/// with [`Context::set_as_current`]. [`Context::get_plot_ui`] does that as well, so plots built
+/// with a [`PlotUi`] always use the context it was obtained from.
+///
+/// On shutdown, drop the implot context before the imgui context, the reverse of the creation
+/// order. Destroying an implot context needs a current imgui context, so if there is none when
+/// it is dropped, the implot context is leaked instead of touching freed imgui state:
+/// ```no_run
+/// let imgui = imgui::Context::create();
+/// let plot_context = implot::Context::create_for(&imgui);
+/// // ... render frames ...
+/// drop(plot_context);
+/// drop(imgui);
+/// ```
 #[rustversion::attr(since(1.48), doc(alias = "ImPlotContext"))]
 pub struct Context {
+    /// The ImPlot context. Being a raw pointer, this also keeps the context from being `Send`
+    /// or `Sync`, since ImPlot can only be used from one thread.
     raw: *mut sys::ImPlotContext,
+    /// Whether the context is destroyed when this is dropped, which is false for contexts
+    /// wrapped with [`Context::from_raw`]
+    owned: bool,
+    /// The thread the context was created on, which is the only one it may be used from
+    thread: ThreadId,
+    /// Style colors set explicitly with [`Context::set_style_color`], indexed by element. These
+    /// are kept when the automatic colors are derived again with
+    /// [`sync_style_with_imgui`](fn.sync_style_with_imgui.html).
+    pub(crate) color_overrides: [Option<ImVec4>; COLOR_ELEMENTS.len()],
+    /// The colormap set with [`Context::set_default_colormap`], if any
+    default_colormap: Option<ColormapChoice>,
 }
 
 // This mutex is used to guard any accesses to the context
 static CTX_MUTEX: ReentrantMutex<()> = parking_lot::const_reentrant_mutex(());
 
-/// Check if there is no current context defined by calling into the C++ API
-fn no_current_context() -> bool {
-    let ctx = unsafe { sys::ImPlot_GetCurrentContext() };
-    ctx.is_null()
+// The contexts created by this crate along with the threads they were created on, so the free
+// functions can check in debug builds that they are called on the thread of the current context.
+// The pointers are stored as usize because raw pointers are not Send.
+#[cfg(debug_assertions)]
+static CONTEXT_THREADS: parking_lot::Mutex<Vec<(usize, ThreadId)>> =
+    parking_lot::const_mutex(Vec::new());
+
+/// Panic if the given context was created on another thread than the current one. Context and
+/// PlotUi can't be sent to other threads, but the free functions of this crate can be called on
+/// any thread, and ImPlot and imgui are not thread-safe.
+fn check_thread(thread: ThreadId) {
+    assert!(
+        thread == thread::current().id(),
+        "implot was used on another thread than the one its context was created on, \
+         but ImPlot and imgui can only be used from a single thread"
+    );
+}
+
+/// Internal helper for functions that work with the current context: in debug builds, panic if
+/// the current context was created on another thread than the calling one. Contexts that were
+/// not created by this crate (see [`Context::from_raw`]) are not checked.
+pub(crate) fn debug_check_thread() {
+    #[cfg(debug_assertions)]
+    {
+        let current = current_raw_context() as usize;
+        let thread = CONTEXT_THREADS
+            .lock()
+            .iter()
+            .find(|(raw, _)| *raw == current)
+            .map(|(_, thread)| *thread);
+        if let Some(thread) = thread {
+            check_thread(thread);
+        }
+    }
+}
+
+/// Panic if the implot-sys crate is not compatible with this one, which happens with
+/// mismatched versions of the two, for example through patch overrides, or if the ImPlot that
+/// implot-sys bundles is not the one its bindings were generated from, for example after
+/// updating the submodule without regenerating the bindings. Such mismatches can disagree about
+/// the layout of the types shared with ImPlot, which leads to undefined behavior instead of an
+/// error.
+fn check_sys_version() {
+    let version = env!("CARGO_PKG_VERSION");
+    assert!(
+        versions_compatible(version, sys::CRATE_VERSION),
+        "implot {} can't be used with implot-sys {} (bundling ImPlot {}), use the implot-sys \
+         version that matches implot",
+        version,
+        sys::CRATE_VERSION,
+        sys::IMPLOT_VERSION
+    );
+    assert_eq!(
+        sys::IMPLOT_VERSION,
+        sys::BINDINGS_IMPLOT_VERSION,
+        "implot-sys {} bundles ImPlot {}, but its bindings were generated for ImPlot {}, \
+         regenerate them with implot-sys-bindgen",
+        sys::CRATE_VERSION,
+        sys::IMPLOT_VERSION,
+        sys::BINDINGS_IMPLOT_VERSION
+    );
+}
+
+/// Returns true if the two crate versions are semver compatible: the same major version, and
+/// also the same minor version before 1.0.
+fn versions_compatible(a: &str, b: &str) -> bool {
+    let significant = |version: &str| {
+        let mut parts = version.split('.');
+        let major = parts.next().unwrap_or_default().to_owned();
+        if major == "0" {
+            format!("0.{}", parts.next().unwrap_or_default())
+        } else {
+            major
+        }
+    };
+    significant(a) == significant(b)
+}
+
+/// Returns the current context as known to the C++ API, which is null if there is none.
+pub(crate) fn current_raw_context() -> *mut sys::ImPlotContext {
+    unsafe { sys::ImPlot_GetCurrentContext() }
 }
 
 impl Context {
-    /// Create a context. This will also activate the context in ImPlot, and hence creating
-    /// a second context when one already exists is an error and will panic.
+    /// Create a context. This will also make the context the current one in ImPlot, see
+    /// [`Context::set_as_current`].
+    ///
+    /// The implot context has to be created after the imgui context, so this panics if there
+    /// is no current imgui context. [`Context::create_for`] makes this ordering explicit.
     pub fn create() -> Self {
-        let _guard = CTX_MUTEX.lock();
         assert!(
-            no_current_context(),
-            "A new active context cannot be created, because another one already exists"
+            imgui_context_exists(),
+            "The implot context has to be created after the imgui context, see Context::create_for"
+        );
+        Self::create_unchecked()
+    }
+
+    /// Create a context for use with the given imgui context, which guarantees that the imgui
+    /// context has been created first. Otherwise, this is the same as [`Context::create`].
+    ///
+    /// ImPlot has no fonts of its own, it draws with the fonts of the imgui context that is
+    /// current while plotting. This makes it work with imgui contexts that share a font atlas
+    /// (see `imgui::Context::create_with_shared_font_atlas`), either with one implot context for
+    /// each imgui context, or with a single one that is used while any of them is current.
+    /// The given imgui context doesn't have to be the current one while this is called, which
+    /// allows creating the implot context while the imgui context is suspended.
+    ///
+    /// Only available with the `imgui-rs` feature, which is on by default. Without imgui-rs, use
+    /// [`Context::create_for_raw`].
+    #[cfg(feature = "imgui-rs")]
+    pub fn create_for(imgui: &ImguiContext) -> Self {
+        // Only borrowed to prove that an imgui context exists
+        let _ = imgui;
+        Self::create_unchecked()
+    }
+
+    /// Create a context for use with the given raw imgui context, for applications that use
+    /// imgui through imgui-sys or their own bindings to it instead of imgui-rs. Otherwise, this
+    /// is the same as [`Context::create_for`]. See [`Context::get_plot_ui_raw`] for driving
+    /// frames without imgui-rs.
+    ///
+    /// # Safety
+    /// `imgui` has to point to a valid imgui context of the imgui library that imgui-sys links,
+    /// which ImPlot is compiled against. The imgui context has to stay alive until this context
+    /// is dropped.
+    ///
+    /// # Panics
+    /// Will panic if `imgui` is null.
+    pub unsafe fn create_for_raw(imgui: *mut imgui_sys::ImGuiContext) -> Self {
+        assert!(
+            !imgui.is_null(),
+            "Context::create_for_raw needs an imgui context, got null"
         );
+        Self::create_unchecked()
+    }
 
+    /// Internal helper that creates the context, without checking for an imgui context.
+    fn create_unchecked() -> Self {
+        check_sys_version();
+        let _guard = CTX_MUTEX.lock();
         let ctx = unsafe { sys::ImPlot_CreateContext() };
         unsafe {
             sys::ImPlot_SetCurrentContext(ctx);
         }
-        Self { raw: ctx }
+        let thread = thread::current().id();
+        #[cfg(debug_assertions)]
+        CONTEXT_THREADS.lock().push((ctx as usize, thread));
+        Self {
+            raw: ctx,
+            owned: true,
+            thread,
+            color_overrides: [None; COLOR_ELEMENTS.len()],
+            default_colormap: None,
+        }
     }
 
-    /// Get a "plot ui" struct, this will be used to build actual plots and is quite
-    /// analogous to imgui-rs' "Ui" struct.
-    pub fn get_plot_ui(&self) -> PlotUi {
-        PlotUi { context: self }
+    /// Wrap a context that was created outside of this crate, for example by C++ code that
+    /// also uses ImPlot. The returned [`BorrowedContext`] can be used like a `Context`, but
+    /// does not destroy the context when it is dropped, that stays the job of whoever created
+    /// it. This does not make the context current.
+    ///
+    /// # Safety
+    /// `raw` has to point to a valid ImPlot context, which has to stay valid for the lifetime
+    /// `'a` and must not be destroyed while the returned value exists.
+    #[rustversion::attr(since(1.48), doc(alias = "ImPlotContext"))]
+    pub unsafe fn from_raw<'a>(raw: *mut sys::ImPlotContext) -> BorrowedContext<'a> {
+        assert!(
+            !raw.is_null(),
+            "Context::from_raw needs a context, got null"
+        );
+        BorrowedContext {
+            context: Self {
+                raw,
+                owned: false,
+                thread: thread::current().id(),
+                color_overrides: [None; COLOR_ELEMENTS.len()],
+                default_colormap: None,
+            },
+            lifetime: PhantomData,
+        }
     }
 
-    /// Use light colors for the implot style.
+    /// Returns the raw pointer to the ImPlot context, for use with the low-level bindings or
+    /// C++ code that uses ImPlot. The pointer stays valid for as long as this context exists.
+    pub fn raw(&self) -> *mut sys::ImPlotContext {
+        self.raw
+    }
+
+    /// Create a context like [`Context::create`], and set it up with the given settings:
+    /// ```no_run
+    /// # use implot::{Colormap, Context, ContextSettings};
+    /// let context = Context::create_with(ContextSettings::new().with_colormap(Colormap::Viridis));
+    /// ```
     ///
-    /// This will eventually be exposed more thoroughly in the form of ImPlotStyle,
-    /// but for now this allows one to at least easily set the color preset.
-    pub fn use_light_colors(&self) {
+    /// # Panics
+    /// Will panic if there is no current imgui context, see [`Context::create`].
+    pub fn create_with(settings: ContextSettings) -> Self {
+        let mut context = Self::create();
+        if let Some(style) = &settings.style {
+            context.set_style(style);
+        }
+        if let Some(colormap) = settings.colormap {
+            context.set_default_colormap(colormap);
+        }
+        context
+    }
+
+    /// Set the colormap of this context, which is used by all plots until it is changed again.
+    /// Pushed colormaps take precedence over this until they are popped. Unlike
+    /// [`set_colormap_from_preset`](fn.set_colormap_from_preset.html), this also takes the
+    /// custom colormaps created with [`register_colormap`](fn.register_colormap.html).
+    #[rustversion::attr(since(1.48), doc(alias = "SetColormap"))]
+    pub fn set_default_colormap<C: Into<ColormapChoice>>(&mut self, colormap: C) {
+        let colormap = colormap.into();
+        self.with_current(|| match colormap {
+            ColormapChoice::Preset(preset) => set_colormap_from_preset(preset, 0),
+            ColormapChoice::Custom(custom) => set_colormap_from_slice(custom.colors())
+                .expect("Registered colormaps have enough colors"),
+        });
+        self.default_colormap = Some(colormap);
+    }
+
+    /// Returns the colormap set with [`Context::set_default_colormap`], or `None` if it was
+    /// never called and ImPlot's default colormap is used. Colormaps set in other ways, such as
+    /// with [`set_colormap_from_preset`](fn.set_colormap_from_preset.html), are not tracked.
+    pub fn default_colormap(&self) -> Option<ColormapChoice> {
+        self.default_colormap
+    }
+
+    /// Make this the current context, which is the one ImPlot draws with. This is only needed
+    /// when there are several contexts, and then only for the free functions of this crate
+    /// that work with the current context, such as [`get_style`](fn.get_style.html) -
+    /// [`Context::get_plot_ui`] makes its context current by itself.
+    #[rustversion::attr(since(1.48), doc(alias = "SetCurrentContext"))]
+    pub fn set_as_current(&self) {
+        let _guard = CTX_MUTEX.lock();
         unsafe {
-            let style = sys::ImPlot_GetStyle();
-            assert_ne!(style, std::ptr::null_mut());
-            sys::ImPlot_StyleColorsLight(style);
+            sys::ImPlot_SetCurrentContext(self.raw);
         }
     }
 
-    /// Use dark colors for the implot style.
-    ///
-    /// This will eventually be exposed more thoroughly in the form of ImPlotStyle,
-    /// but for now this allows one to at least easily set the color preset.
-    pub fn use_dark_colors(&self) {
+    /// Returns whether this is the current context, see [`Context::set_as_current`].
+    #[rustversion::attr(since(1.48), doc(alias = "GetCurrentContext"))]
+    pub fn is_current(&self) -> bool {
+        current_raw_context() == self.raw
+    }
+
+    /// Internal helper that runs `f` with this context as the current context, and restores the
+    /// context that was current before afterwards. This is for methods that change this context
+    /// through ImPlot functions that work with the current context.
+    pub(crate) fn with_current<R, F: FnOnce() -> R>(&self, f: F) -> R {
+        let _guard = CTX_MUTEX.lock();
+        let previous = current_raw_context();
         unsafe {
-            let style = sys::ImPlot_GetStyle();
-            assert_ne!(style, std::ptr::null_mut());
-            sys::ImPlot_StyleColorsDark(style);
+            sys::ImPlot_SetCurrentContext(self.raw);
         }
+        let result = f();
+        unsafe {
+            sys::ImPlot_SetCurrentContext(previous);
+        }
+        result
     }
 
-    /// Use classic colors for the implot style.
+    /// Get a "plot ui" struct, this will be used to build actual plots and is quite
+    /// analogous to imgui-rs' "Ui" struct. This makes this context the current one, so the
+    /// plots are drawn with it.
     ///
-    /// This will eventually be exposed more thoroughly in the form of ImPlotStyle,
-    /// but for now this allows one to at least easily set the color preset.
-    pub fn use_classic_colors(&self) {
-        unsafe {
-            let style = sys::ImPlot_GetStyle();
-            assert_ne!(style, std::ptr::null_mut());
-            sys::ImPlot_StyleColorsClassic(style);
+    /// The imgui `Ui` of the current frame is borrowed for as long as the `PlotUi` is used, so
+    /// plots can only be built during a frame, after `NewFrame` and before rendering:
+    /// ```compile_fail
+    /// # fn example(context: &implot::Context, imgui: &mut imgui::Context) {
+    /// let ui = imgui.new_frame();
+    /// let plot_ui = context.get_plot_ui(ui);
+    /// imgui.render();
+    /// implot::Plot::new("too late").build(&plot_ui, || {});
+    /// # }
+    /// ```
+    ///
+    /// Only available with the `imgui-rs` feature, which is on by default. Without imgui-rs, use
+    /// [`Context::get_plot_ui_raw`].
+    #[cfg(feature = "imgui-rs")]
+    pub fn get_plot_ui<'ui>(&'ui self, ui: &'ui Ui) -> PlotUi<'ui> {
+        // Only borrowed to prove that a frame is active
+        let _ = ui;
+        if cfg!(debug_assertions) {
+            check_thread(self.thread);
         }
+        self.set_as_current();
+        PlotUi { context: self }
+    }
+
+    /// Get a "plot ui" struct for applications that drive imgui frames themselves, without
+    /// imgui-rs. Otherwise, this is the same as [`Context::get_plot_ui`]:
+    /// ```no_run
+    /// # unsafe fn example(imgui: *mut imgui_sys::ImGuiContext) {
+    /// let context = implot::Context::create_for_raw(imgui);
+    /// loop {
+    ///     // Set up the display size, the time step and the input in imgui's IO first
+    ///     imgui_sys::igNewFrame();
+    ///     let plot_ui = context.get_plot_ui_raw();
+    ///     implot::Plot::new("Raw frame").build(&plot_ui, || {
+    ///         implot::PlotLine::new("line").plot(&[0.0, 1.0], &[0.0, 1.0]);
+    ///     });
+    ///     drop(plot_ui);
+    ///     imgui_sys::igRender();
+    ///     // Hand igGetDrawData() to the renderer
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// # Safety
+    /// An imgui frame of the imgui context this context was created for has to be active, after
+    /// `igNewFrame` and before `igRender`, for as long as the returned value is used. That imgui
+    /// context has to be the current one.
+    pub unsafe fn get_plot_ui_raw(&self) -> PlotUi<'_> {
+        if cfg!(debug_assertions) {
+            check_thread(self.thread);
+        }
+        self.set_as_current();
+        PlotUi { context: self }
+    }
+
+    /// Get a "plot ui" struct without proof that an imgui frame is active. Building plots with
+    /// it outside of a frame crashes inside ImPlot, use [`Context::get_plot_ui`] instead.
+    #[deprecated(
+        since = "0.7.0",
+        note = "use get_plot_ui, which borrows the imgui Ui of the current frame"
+    )]
+    pub fn get_plot_ui_unchecked(&self) -> PlotUi<'_> {
+        if cfg!(debug_assertions) {
+            check_thread(self.thread);
+        }
+        self.set_as_current();
+        PlotUi { context: self }
+    }
+
+    /// Use light colors for the implot style. This is the same as
+    /// [`style_colors_light`](fn.style_colors_light.html).
+    ///
+    /// These color presets only change the colors; [`Context::style`] gives access to the full
+    /// style.
+    pub fn use_light_colors(&self) {
+        self.with_current(crate::style_colors_light);
+    }
+
+    /// Use dark colors for the implot style. This is the same as
+    /// [`style_colors_dark`](fn.style_colors_dark.html).
+    pub fn use_dark_colors(&self) {
+        self.with_current(crate::style_colors_dark);
+    }
+
+    /// Use classic colors for the implot style. This is the same as
+    /// [`style_colors_classic`](fn.style_colors_classic.html).
+    pub fn use_classic_colors(&self) {
+        self.with_current(crate::style_colors_classic);
+    }
+}
+
+/// Settings for setting up a context with [`Context::create_with`]. Settings that are not
+/// configured keep ImPlot's defaults.
+#[derive(Clone, Debug, Default)]
+pub struct ContextSettings {
+    /// Colormap to use by default, see [`Context::set_default_colormap`]
+    colormap: Option<ColormapChoice>,
+    /// Style to use, see [`Context::set_style`]
+    style: Option<Style>,
+}
+
+impl ContextSettings {
+    /// Create new settings, with nothing configured yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the colormap to use by default, which is either one of the built-in presets or a
+    /// colormap registered with [`register_colormap`](fn.register_colormap.html).
+    pub fn with_colormap<C: Into<ColormapChoice>>(mut self, colormap: C) -> Self {
+        self.colormap = Some(colormap.into());
+        self
+    }
+
+    /// Set the style to use. See [`StyleBuilder`](struct.StyleBuilder.html) and
+    /// [`Style::default`](struct.Style.html#impl-Default) for creating a style from scratch.
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
     }
 }
 
 impl Drop for Context {
     fn drop(&mut self) {
+        if !self.owned {
+            return;
+        }
         let _guard = CTX_MUTEX.lock();
+        #[cfg(debug_assertions)]
+        CONTEXT_THREADS
+            .lock()
+            .retain(|(raw, _)| *raw != self.raw as usize);
+        crate::colormap::release_stored_colormaps_of(self.raw);
+        crate::frame_stats::forget_frame_stats_of(self.raw);
+        let current = current_raw_context();
+        // Destroying the context frees its memory through ImGui, which touches the current imgui
+        // context. If the imgui context was dropped first there is none, and the implot context
+        // is leaked instead of crashing on shutdown.
+        if !imgui_context_exists() {
+            if current == self.raw {
+                unsafe {
+                    sys::ImPlot_SetCurrentContext(std::ptr::null_mut());
+                }
+            }
+            return;
+        }
         unsafe {
             sys::ImPlot_DestroyContext(self.raw);
         }
+        // ImPlot leaves no context current if this was the current one. If another context was
+        // current, make sure that it still is.
+        if current != self.raw {
+            unsafe {
+                sys::ImPlot_SetCurrentContext(current);
+            }
+        }
+    }
+}
+
+/// An ImPlot context that was created outside of this crate and is not owned by it, see
+/// [`Context::from_raw`]. This dereferences to a [`Context`], so it can be used like one, but the
+/// context is not destroyed when this is dropped.
+///
+/// It only dereferences to a shared `Context`, since a mutable one could be swapped out of it
+/// and used after the context it borrows has been destroyed. The methods of `Context` that
+/// take `&mut self` are forwarded by methods of the same name instead, and the functions that
+/// change a context, such as [`sync_style_with_imgui`](fn.sync_style_with_imgui.html), take
+/// either of them through [`ContextMut`]:
+/// ```compile_fail
+/// # fn example(mut borrowed: implot::BorrowedContext, other: &mut implot::Context) {
+/// std::mem::swap(&mut *borrowed, other);
+/// # }
+/// ```
+///
+/// Like a [`Context`], this can't be sent to other threads:
+/// ```compile_fail
+/// fn check<T: Send>() {}
+/// check::<implot::BorrowedContext>();
+/// ```
+pub struct BorrowedContext<'a> {
+    context: Context,
+    /// Ties this to the lifetime the creator of the context guarantees it to be valid for
+    lifetime: PhantomData<&'a mut sys::ImPlotContext>,
+}
+
+impl Deref for BorrowedContext<'_> {
+    type Target = Context;
+
+    fn deref(&self) -> &Context {
+        &self.context
+    }
+}
+
+impl BorrowedContext<'_> {
+    /// Set the colormap of this context, see [`Context::set_default_colormap`].
+    #[rustversion::attr(since(1.48), doc(alias = "SetColormap"))]
+    pub fn set_default_colormap<C: Into<ColormapChoice>>(&mut self, colormap: C) {
+        self.context.set_default_colormap(colormap);
+    }
+
+    /// Returns mutable access to the input map of this context, see
+    /// [`Context::input_map_mut`].
+    #[rustversion::attr(since(1.48), doc(alias = "GetInputMap"))]
+    pub fn input_map_mut(&mut self) -> InputMapGuard<'_> {
+        self.context.input_map_mut()
+    }
+
+    /// Replace the style of this context, see [`Context::set_style`].
+    #[rustversion::attr(since(1.48), doc(alias = "GetStyle"))]
+    pub fn set_style(&mut self, style: &Style) {
+        self.context.set_style(style);
+    }
+
+    /// Set a style color of this context explicitly, see [`Context::set_style_color`].
+    pub fn set_style_color<C: Into<ImVec4>>(&mut self, element: PlotColorElement, color: C) {
+        self.context.set_style_color(element, color);
+    }
+
+    /// Forget the override of the given style color, see
+    /// [`Context::clear_style_color_override`].
+    pub fn clear_style_color_override(&mut self, element: PlotColorElement) {
+        self.context.clear_style_color_override(element);
+    }
+
+    /// Set the size of plots that are built without an explicit size, see
+    /// [`Context::set_default_plot_size`].
+    #[rustversion::attr(since(1.48), doc(alias = "PlotDefaultSize"))]
+    pub fn set_default_plot_size<S: Into<ImVec2>>(&mut self, size: S) {
+        self.context.set_default_plot_size(size);
+    }
+
+    /// Set the minimum size plots are shrunk to, see [`Context::set_min_plot_size`].
+    #[rustversion::attr(since(1.48), doc(alias = "PlotMinSize"))]
+    pub fn set_min_plot_size<S: Into<ImVec2>>(&mut self, size: S) {
+        self.context.set_min_plot_size(size);
+    }
+}
+
+/// A context that can be changed: either a [`Context`] or a [`BorrowedContext`]. The functions
+/// of this crate that change a context, such as
+/// [`StyleBuilder::apply`](struct.StyleBuilder.html#method.apply), take either of them through
+/// this trait. It can't be implemented outside of this crate.
+pub trait ContextMut: sealed::Sealed {}
+
+impl ContextMut for Context {}
+
+impl ContextMut for BorrowedContext<'_> {}
+
+mod sealed {
+    /// Gives the functions of this crate mutable access to the `Context` of a `ContextMut`. The
+    /// token can only be created in this crate, so nothing else can get that access, which
+    /// would allow swapping the `Context` out of a `BorrowedContext`.
+    pub trait Sealed {
+        fn context_mut(&mut self, token: Token) -> &mut super::Context;
+    }
+
+    /// Proof that the caller of `Sealed::context_mut` is this crate.
+    pub struct Token(pub(crate) ());
+
+    impl Sealed for super::Context {
+        fn context_mut(&mut self, _: Token) -> &mut super::Context {
+            self
+        }
+    }
+
+    impl Sealed for super::BorrowedContext<'_> {
+        fn context_mut(&mut self, _: Token) -> &mut super::Context {
+            &mut self.context
+        }
+    }
+}
+
+/// Internal helper that returns the `Context` of a [`ContextMut`].
+pub(crate) fn context_mut<C: ContextMut + ?Sized>(context: &mut C) -> &mut Context {
+    context.context_mut(sealed::Token(()))
+}
+
+#[cfg(all(test, feature = "imgui-rs"))]
+mod tests {
+    use super::*;
+    use crate::test_support::TEST_MUTEX;
+
+    #[test]
+    fn test_versions_compatible() {
+        assert!(versions_compatible("0.6.0", "0.6.3"));
+        assert!(!versions_compatible("0.6.0", "0.7.0"));
+        assert!(versions_compatible("1.2.0", "1.4.1"));
+        assert!(!versions_compatible("1.2.0", "2.0.0"));
+        assert!(versions_compatible(
+            env!("CARGO_PKG_VERSION"),
+            sys::CRATE_VERSION
+        ));
+    }
+
+    #[test]
+    fn test_bundled_implot_matches_the_bindings() {
+        assert_eq!(sys::IMPLOT_VERSION, sys::BINDINGS_IMPLOT_VERSION);
+    }
+
+    #[test]
+    fn test_create_and_drop_orderings() {
+        let _guard = TEST_MUTEX.lock();
+        let imgui = imgui::Context::create();
+
+        // Dropping a context that is not current keeps the current one
+        let first = Context::create_for(&imgui);
+        assert!(first.is_current());
+        let second = Context::create_for(&imgui);
+        assert!(second.is_current());
+        assert!(!first.is_current());
+        first.set_as_current();
+        drop(second);
+        assert!(first.is_current());
+        drop(first);
+        assert!(current_raw_context().is_null());
+
+        // Dropping the current context leaves no context current
+        let first = Context::create_for(&imgui);
+        let second = Context::create_for(&imgui);
+        drop(second);
+        assert!(current_raw_context().is_null());
+        first.set_as_current();
+        assert!(first.is_current());
+        drop(first);
+        assert!(current_raw_context().is_null());
+
+        // Contexts can be dropped in creation order as well
+        let first = Context::create_for(&imgui);
+        let second = Context::create_for(&imgui);
+        drop(first);
+        assert!(second.is_current());
+        drop(second);
+        assert!(current_raw_context().is_null());
+
+        // Getting a plot ui switches the current context, with_current switches only temporarily
+        let first = Context::create_for(&imgui);
+        let second = Context::create_for(&imgui);
+        #[allow(deprecated)]
+        let _plot_ui = first.get_plot_ui_unchecked();
+        assert!(first.is_current());
+        assert!(second.with_current(|| second.is_current()));
+        assert!(first.is_current());
+    }
+
+    #[test]
+    fn test_drop_after_imgui_context() {
+        let _guard = TEST_MUTEX.lock();
+
+        // The intended shutdown sequence: the implot context is dropped first
+        let imgui = imgui::Context::create();
+        let context = Context::create_for(&imgui);
+        drop(context);
+        drop(imgui);
+
+        // The other way around, the implot context must not be destroyed through the freed
+        // imgui context. It is no longer current either.
+        let imgui = imgui::Context::create();
+        let context = Context::create_for(&imgui);
+        drop(imgui);
+        drop(context);
+        assert!(current_raw_context().is_null());
+    }
+
+    #[test]
+    fn test_borrowed_context_is_not_destroyed() {
+        let _guard = TEST_MUTEX.lock();
+        let imgui = imgui::Context::create();
+        let owner = Context::create_for(&imgui);
+
+        let borrowed = unsafe { Context::from_raw(owner.raw()) };
+        assert_eq!(borrowed.raw(), owner.raw());
+        assert!(borrowed.is_current());
+        drop(borrowed);
+        // Still the current context, so it has not been destroyed
+        assert!(owner.is_current());
+        drop(owner);
+        assert!(current_raw_context().is_null());
+    }
+
+    #[test]
+    fn test_borrowed_context_changes_style() {
+        let _guard = TEST_MUTEX.lock();
+        let imgui = imgui::Context::create();
+        let owner = Context::create_for(&imgui);
+        let mut borrowed = unsafe { Context::from_raw(owner.raw()) };
+
+        let mut style = owner.style();
+        style.line_weight = 2.0;
+        borrowed.set_style(&style);
+        assert_eq!(owner.style().line_weight, 2.0);
+
+        let red = ImVec4::from([1.0, 0.0, 0.0, 1.0]);
+        borrowed.set_style_color(PlotColorElement::Line, red);
+        assert_eq!(owner.style().colors[PlotColorElement::Line as usize], red);
+        assert_eq!(
+            borrowed.style_color_override(PlotColorElement::Line),
+            Some(red)
+        );
+        borrowed.clear_style_color_override(PlotColorElement::Line);
+        assert_eq!(borrowed.style_color_override(PlotColorElement::Line), None);
+
+        borrowed.set_default_plot_size([300.0, 200.0]);
+        assert_eq!(owner.default_plot_size(), ImVec2::new(300.0, 200.0));
+        borrowed.set_min_plot_size([30.0, 20.0]);
+        assert_eq!(owner.min_plot_size(), ImVec2::new(30.0, 20.0));
+
+        crate::StyleBuilder::new()
+            .marker_size(7.0)
+            .apply(&mut borrowed)
+            .unwrap();
+        assert_eq!(owner.style().marker_size, 7.0);
+
+        drop(borrowed);
+        drop(owner);
+    }
+
+    #[test]
+    fn test_thread_check() {
+        let _guard = TEST_MUTEX.lock();
+        let imgui = imgui::Context::create();
+        let context = Context::create_for(&imgui);
+        debug_check_thread();
+        let other_thread = thread::spawn(debug_check_thread).join();
+        assert_eq!(other_thread.is_err(), cfg!(debug_assertions));
+        drop(context);
     }
 }
@@ -0,0 +1,280 @@
+//! # Coordinates module
+//!
+//! This module contains [`PlotPoint`], [`PlotRange`] and [`PlotLimits`], the types this crate
+//! uses for positions and ranges in plot coordinates. They have the same layout as the
+//! `ImPlotPoint`, `ImPlotRange` and `ImPlotLimits` structs of implot-sys, and convert from and to
+//! them with `From`, but use Rust naming for their fields. The sys types stay re-exported for
+//! code that works with the low-level bindings, and the `*_raw` variants of the getters return
+//! them.
+use crate::{ImPlotLimits, ImPlotPoint, ImPlotRange};
+use std::ops::{Range, RangeInclusive};
+
+/// A position in plot coordinates.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlotPoint {
+    /// Position along the X axis
+    pub x: f64,
+    /// Position along the Y axis
+    pub y: f64,
+}
+
+impl PlotPoint {
+    /// Create a point from its coordinates.
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+}
+
+/// A range of values along one plot axis. Like the ranges of ImPlot, `min` can be larger than
+/// `max`, see [`PlotRange::normalized`].
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlotRange {
+    /// Start of the range
+    pub min: f64,
+    /// End of the range
+    pub max: f64,
+}
+
+impl PlotRange {
+    /// Create a range from its bounds.
+    pub fn new(min: f64, max: f64) -> Self {
+        Self { min, max }
+    }
+
+    /// Returns the range with the bounds swapped if needed, so that `min <= max`.
+    pub fn normalized(&self) -> Self {
+        ImPlotRange::from(*self).normalized().into()
+    }
+
+    /// Returns the size of the range, which is negative if `min > max`.
+    pub fn size(&self) -> f64 {
+        self.max - self.min
+    }
+
+    /// Returns true if the value is within the range, including the bounds. Inverted ranges
+    /// contain the same values as normalized ones.
+    pub fn contains(&self, value: f64) -> bool {
+        ImPlotRange::from(*self).contains(value)
+    }
+}
+
+/// The limits of a plot: a range along the X axis and a range along a Y axis.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlotLimits {
+    /// Range along the X axis
+    pub x: PlotRange,
+    /// Range along the Y axis
+    pub y: PlotRange,
+}
+
+impl PlotLimits {
+    /// Create limits from the ranges along the two axes.
+    pub fn new<X: Into<PlotRange>, Y: Into<PlotRange>>(x: X, y: Y) -> Self {
+        Self {
+            x: x.into(),
+            y: y.into(),
+        }
+    }
+
+    /// Returns true if the point is within the limits, including their bounds.
+    pub fn contains(&self, point: impl Into<PlotPoint>) -> bool {
+        let point = point.into();
+        self.x.contains(point.x) && self.y.contains(point.y)
+    }
+}
+
+impl From<ImPlotPoint> for PlotPoint {
+    fn from(point: ImPlotPoint) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+        }
+    }
+}
+
+impl From<&ImPlotPoint> for PlotPoint {
+    fn from(point: &ImPlotPoint) -> Self {
+        Self::from(*point)
+    }
+}
+
+impl From<&PlotPoint> for PlotPoint {
+    fn from(point: &PlotPoint) -> Self {
+        *point
+    }
+}
+
+impl From<PlotPoint> for ImPlotPoint {
+    fn from(point: PlotPoint) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+        }
+    }
+}
+
+impl From<[f64; 2]> for PlotPoint {
+    fn from([x, y]: [f64; 2]) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<(f64, f64)> for PlotPoint {
+    fn from((x, y): (f64, f64)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<ImPlotRange> for PlotRange {
+    fn from(range: ImPlotRange) -> Self {
+        Self {
+            min: range.Min,
+            max: range.Max,
+        }
+    }
+}
+
+impl From<&ImPlotRange> for PlotRange {
+    fn from(range: &ImPlotRange) -> Self {
+        Self::from(*range)
+    }
+}
+
+impl From<&PlotRange> for PlotRange {
+    fn from(range: &PlotRange) -> Self {
+        *range
+    }
+}
+
+impl From<PlotRange> for ImPlotRange {
+    fn from(range: PlotRange) -> Self {
+        Self {
+            Min: range.min,
+            Max: range.max,
+        }
+    }
+}
+
+impl From<[f64; 2]> for PlotRange {
+    fn from([min, max]: [f64; 2]) -> Self {
+        Self { min, max }
+    }
+}
+
+impl From<Range<f64>> for PlotRange {
+    fn from(range: Range<f64>) -> Self {
+        Self {
+            min: range.start,
+            max: range.end,
+        }
+    }
+}
+
+impl From<RangeInclusive<f64>> for PlotRange {
+    fn from(range: RangeInclusive<f64>) -> Self {
+        Self {
+            min: *range.start(),
+            max: *range.end(),
+        }
+    }
+}
+
+impl From<PlotRange> for Range<f64> {
+    fn from(range: PlotRange) -> Self {
+        range.min..range.max
+    }
+}
+
+impl From<ImPlotLimits> for PlotLimits {
+    fn from(limits: ImPlotLimits) -> Self {
+        Self {
+            x: limits.X.into(),
+            y: limits.Y.into(),
+        }
+    }
+}
+
+impl From<&ImPlotLimits> for PlotLimits {
+    fn from(limits: &ImPlotLimits) -> Self {
+        Self::from(*limits)
+    }
+}
+
+impl From<&PlotLimits> for PlotLimits {
+    fn from(limits: &PlotLimits) -> Self {
+        *limits
+    }
+}
+
+impl From<PlotLimits> for ImPlotLimits {
+    fn from(limits: PlotLimits) -> Self {
+        Self {
+            X: limits.x.into(),
+            Y: limits.y.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::{align_of, size_of};
+
+    #[test]
+    fn test_layouts_match_sys_types() {
+        assert_eq!(size_of::<PlotPoint>(), size_of::<ImPlotPoint>());
+        assert_eq!(align_of::<PlotPoint>(), align_of::<ImPlotPoint>());
+        assert_eq!(size_of::<PlotRange>(), size_of::<ImPlotRange>());
+        assert_eq!(align_of::<PlotRange>(), align_of::<ImPlotRange>());
+        assert_eq!(size_of::<PlotLimits>(), size_of::<ImPlotLimits>());
+        assert_eq!(align_of::<PlotLimits>(), align_of::<ImPlotLimits>());
+    }
+
+    #[test]
+    fn test_conversions_keep_values() {
+        let point = ImPlotPoint { x: 1.5, y: -2.0 };
+        assert_eq!(PlotPoint::from(point), PlotPoint::new(1.5, -2.0));
+        assert_eq!(PlotPoint::from(&point), PlotPoint::from([1.5, -2.0]));
+        let round_trip = ImPlotPoint::from(PlotPoint::from(point));
+        assert_eq!((round_trip.x, round_trip.y), (1.5, -2.0));
+
+        let limits = ImPlotLimits {
+            X: ImPlotRange { Min: 0.0, Max: 1.0 },
+            Y: ImPlotRange {
+                Min: -5.0,
+                Max: 5.0,
+            },
+        };
+        let converted = PlotLimits::from(limits);
+        assert_eq!(converted, PlotLimits::new(0.0..1.0, [-5.0, 5.0]));
+        let round_trip = ImPlotLimits::from(converted);
+        assert_eq!(
+            (
+                round_trip.X.Min,
+                round_trip.X.Max,
+                round_trip.Y.Min,
+                round_trip.Y.Max
+            ),
+            (0.0, 1.0, -5.0, 5.0)
+        );
+    }
+
+    #[test]
+    fn test_range_and_limits_helpers() {
+        let inverted = PlotRange::new(3.0, 1.0);
+        assert_eq!(inverted.normalized(), PlotRange::new(1.0, 3.0));
+        assert_eq!(inverted.size(), -2.0);
+        assert!(inverted.contains(2.0));
+        assert!(!inverted.contains(0.0));
+
+        let limits = PlotLimits::new(0.0..=1.0, 0.0..=2.0);
+        assert!(limits.contains([0.5, 2.0]));
+        assert!(!limits.contains((1.5, 1.0)));
+    }
+}
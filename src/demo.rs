@@ -0,0 +1,308 @@
+//! # Demo module
+//!
+//! This module contains a demo window that shows the features of this crate, as opposed to
+//! the C++ demo (see [`show_demo_window`](fn.show_demo_window.html)), which also shows features
+//! that are not wrapped yet. Each section of the window is implemented in its own function
+//! below, so the code can be copied from there. Only available with the `demo` feature.
+//!
+//! Drag lines and drag points are not wrapped yet, so they are not part of the demo.
+use crate::imgui_compat::{Condition, Ui};
+use crate::{
+    colormap_selector, plot_overlay_text, push_colormap, push_style_var, with_style_color,
+    with_style_var, ColormapChoice, HeatmapWithScale, LegendSolo, Marker, MeasureTool, Plot,
+    PlotBars, PlotColorElement, PlotEvents, PlotHeatmap, PlotLimits, PlotLine, PlotLocation,
+    PlotPoint, PlotScatter, PlotStairs, PlotStems, PlotText, PlotUi, StyleVarValue,
+};
+use imgui::CollapsingHeader;
+
+/// State of the Rust demo window, see [`show_rust_demo_window`]. Create this once and keep it
+/// around between frames.
+pub struct DemoState {
+    /// Colormap picked in the colormap section
+    colormap: ColormapChoice,
+    /// Soloing of legend entries in the legend section
+    legend_solo: LegendSolo,
+    /// Measurements in the interaction section
+    measure_tool: MeasureTool,
+    /// Latest query of the interaction section
+    last_query: Option<PlotLimits>,
+}
+
+impl DemoState {
+    /// Create a new demo state with default values in it.
+    pub fn new() -> Self {
+        Self {
+            colormap: crate::Colormap::Viridis.into(),
+            legend_solo: LegendSolo::new(),
+            measure_tool: MeasureTool::new(),
+            last_query: None,
+        }
+    }
+}
+
+impl Default for DemoState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Show a window that demonstrates the idiomatic API of this crate, with one collapsible
+/// section per feature. Call this once per frame:
+/// ```no_run
+/// # use implot::{show_rust_demo_window, DemoState, PlotUi};
+/// # fn example(ui: &imgui::Ui, plot_ui: &PlotUi, demo_state: &mut DemoState) {
+/// show_rust_demo_window(ui, plot_ui, demo_state);
+/// # }
+/// ```
+pub fn show_rust_demo_window(ui: &Ui, plot_ui: &PlotUi, state: &mut DemoState) {
+    ui.window("implot-rs demo")
+        .size([600.0, 700.0], Condition::FirstUseEver)
+        .build(|| {
+            ui.text_wrapped(
+                "Everything in here is built with the Rust API of implot-rs. Have a look at \
+                 src/demo.rs in the implot crate to see how.",
+            );
+            if CollapsingHeader::new("Line plots").build(ui) {
+                show_line_plot(plot_ui);
+            }
+            if CollapsingHeader::new("Scatter plots").build(ui) {
+                show_scatter_plot(plot_ui);
+            }
+            if CollapsingHeader::new("Bar plots").build(ui) {
+                show_bar_plots(plot_ui);
+            }
+            if CollapsingHeader::new("Stairs and stem plots").build(ui) {
+                show_stairs_and_stems(plot_ui);
+            }
+            if CollapsingHeader::new("Text").build(ui) {
+                show_text_plot(plot_ui);
+            }
+            if CollapsingHeader::new("Heatmaps").build(ui) {
+                show_heatmap(plot_ui);
+            }
+            if CollapsingHeader::new("Styling and colormaps").build(ui) {
+                show_styling(ui, plot_ui, &mut state.colormap);
+            }
+            if CollapsingHeader::new("Custom ticks").build(ui) {
+                show_custom_ticks(plot_ui);
+            }
+            if CollapsingHeader::new("Interaction").build(ui) {
+                show_interaction(ui, plot_ui, state);
+            }
+        });
+}
+
+/// Returns `count` points of a sine wave with the given frequency over `[0, 1]`.
+fn sine_wave(count: usize, frequency: f64) -> (Vec<f64>, Vec<f64>) {
+    let x: Vec<f64> = (0..count).map(|i| i as f64 / (count - 1) as f64).collect();
+    let y = x
+        .iter()
+        .map(|x| 0.5 + 0.5 * (2.0 * std::f64::consts::PI * frequency * x).sin())
+        .collect();
+    (x, y)
+}
+
+/// Lines, with a style variable pushed for one of them.
+fn show_line_plot(plot_ui: &PlotUi) {
+    let (x, slow) = sine_wave(200, 1.0);
+    let (_, fast) = sine_wave(200, 3.0);
+    Plot::new("Line plot")
+        .size([-1.0, 250.0])
+        .x_label("time")
+        .y_label("value")
+        .build(plot_ui, || {
+            PlotLine::new("slow").plot(&x, &slow);
+            with_style_var(plot_ui, StyleVarValue::LineWeight(3.0), || {
+                PlotLine::new("fast, thick").plot(&x, &fast);
+            });
+        });
+}
+
+/// Scatter plots with different markers.
+fn show_scatter_plot(plot_ui: &PlotUi) {
+    let x: Vec<f64> = (0..30).map(|i| i as f64 / 30.0).collect();
+    let y1: Vec<f64> = x.iter().map(|x| x * x).collect();
+    let y2: Vec<f64> = x.iter().map(|x| x.sqrt()).collect();
+    Plot::new("Scatter plot")
+        .size([-1.0, 250.0])
+        .build(plot_ui, || {
+            PlotScatter::new("squares").plot(&x, &y1);
+            let marker = push_style_var(plot_ui, StyleVarValue::Marker(Marker::Diamond));
+            let size = push_style_var(plot_ui, StyleVarValue::MarkerSize(6.0));
+            PlotScatter::new("square roots, diamonds").plot(&x, &y2);
+            size.pop();
+            marker.pop();
+        });
+}
+
+/// Vertical and horizontal bars.
+fn show_bar_plots(plot_ui: &PlotUi) {
+    let positions = [1.0, 2.0, 3.0, 4.0];
+    let values = [3.0, 5.0, 2.0, 4.0];
+    Plot::new("Vertical bars")
+        .size([-1.0, 200.0])
+        .build(plot_ui, || {
+            PlotBars::new("vertical")
+                .with_bar_width(0.5)
+                .plot(&positions, &values);
+        });
+    Plot::new("Horizontal bars")
+        .size([-1.0, 200.0])
+        .build(plot_ui, || {
+            PlotBars::new("horizontal")
+                .with_bar_height(0.5)
+                .with_horizontal_bars()
+                .plot(&positions, &values);
+        });
+}
+
+/// Stairs and stems, which show the same data in different ways.
+fn show_stairs_and_stems(plot_ui: &PlotUi) {
+    let (x, y) = sine_wave(20, 1.0);
+    Plot::new("Stairs and stems")
+        .size([-1.0, 250.0])
+        .build(plot_ui, || {
+            PlotStairs::new("stairs").plot(&x, &y);
+            PlotStems::new("stems").with_reference_y(0.5).plot(&x, &y);
+        });
+}
+
+/// Text at positions in plot coordinates.
+fn show_text_plot(plot_ui: &PlotUi) {
+    Plot::new("Text")
+        .size([-1.0, 200.0])
+        .x_limits([0.0, 1.0], Condition::FirstUseEver)
+        .y1_limits([0.0, 1.0], Condition::FirstUseEver)
+        .build(plot_ui, || {
+            PlotText::new("horizontal").plot(0.3, 0.5, false);
+            PlotText::new("vertical").plot(0.7, 0.5, true);
+            PlotText::new("offset by 20 pixels")
+                .with_pixel_offset(0.0, 20.0)
+                .plot(0.3, 0.5, false);
+        });
+}
+
+/// A heatmap next to its color scale, with the hovered cell in a tooltip.
+fn show_heatmap(plot_ui: &PlotUi) {
+    const ROWS: u32 = 8;
+    const COLS: u32 = 8;
+    let values: Vec<f64> = (0..ROWS * COLS)
+        .map(|i| ((i / COLS) as f64 * 0.7 + (i % COLS) as f64 * 0.4).sin())
+        .collect();
+    HeatmapWithScale::new(
+        Plot::new("Heatmap").size([-1.0, 300.0]),
+        PlotHeatmap::new("values").with_label_format(None),
+    )
+    .build(plot_ui, &values, ROWS, COLS, |heatmap| {
+        heatmap.show_hovered_cell_tooltip(&values, ROWS, COLS);
+    });
+}
+
+/// Style colors and variables pushed for some items, and a colormap picked from a selector.
+fn show_styling(ui: &Ui, plot_ui: &PlotUi, colormap: &mut ColormapChoice) {
+    colormap_selector(ui, "Colormap", colormap);
+    let (x, y) = sine_wave(100, 2.0);
+    let pushed_colormap = push_colormap(plot_ui, *colormap);
+    Plot::new("Styled plot")
+        .size([-1.0, 250.0])
+        .build(plot_ui, || {
+            for offset in 0..4 {
+                let shifted: Vec<f64> = y.iter().map(|y| y + offset as f64 * 0.25).collect();
+                PlotLine::new(&format!("colormap color {}", offset)).plot(&x, &shifted);
+            }
+            with_style_color(
+                plot_ui,
+                PlotColorElement::Line,
+                [1.0, 0.2, 0.2, 1.0],
+                || {
+                    let below: Vec<f64> = y.iter().map(|y| y - 0.5).collect();
+                    PlotLine::new("explicitly red").plot(&x, &below);
+                },
+            );
+        });
+    pushed_colormap.pop();
+}
+
+/// Ticks at custom positions, with custom labels.
+fn show_custom_ticks(plot_ui: &PlotUi) {
+    let (x, y) = sine_wave(100, 1.0);
+    let x_ticks: Vec<(f64, String)> = [(0.0, "start"), (0.5, "middle"), (1.0, "end")]
+        .iter()
+        .map(|(position, label)| (*position, label.to_string()))
+        .collect();
+    Plot::new("Custom ticks")
+        .size([-1.0, 250.0])
+        .x_ticks_with_labels(&x_ticks, false)
+        .y_ticks(
+            crate::YAxisChoice::First,
+            &[0.0, 0.25, 0.5, 0.75, 1.0],
+            false,
+        )
+        .build(plot_ui, || {
+            PlotLine::new("sine").plot(&x, &y);
+        });
+}
+
+/// Hover and query readouts, legend soloing and the measure tool.
+fn show_interaction(ui: &Ui, plot_ui: &PlotUi, state: &mut DemoState) {
+    ui.text_wrapped(
+        "Drag with the middle mouse button to query, double click a legend entry to solo it, \
+         and drag with the left mouse button while holding shift to measure.",
+    );
+    let (x, slow) = sine_wave(200, 1.0);
+    let (_, fast) = sine_wave(200, 4.0);
+    let last_query = &mut state.last_query;
+    let events = PlotEvents::new()
+        .with_on_hover(|position: PlotPoint| {
+            plot_overlay_text(
+                PlotLocation::NorthEast,
+                &format!("x: {:.3}\ny: {:.3}", position.x, position.y),
+            );
+        })
+        .with_on_query(|query| *last_query = Some(query));
+    let legend_solo = &mut state.legend_solo;
+    let measure_tool = &mut state.measure_tool;
+    Plot::new("Interaction")
+        .size([-1.0, 300.0])
+        .build_with_events(plot_ui, events, || {
+            legend_solo.item("slow");
+            PlotLine::new("slow").plot(&x, &slow);
+            legend_solo.item("fast");
+            PlotLine::new("fast").plot(&x, &fast);
+            measure_tool.show();
+        });
+    match &state.last_query {
+        Some(query) => ui.text(format!(
+            "Query: x from {:.3} to {:.3}, y from {:.3} to {:.3}",
+            query.x.min, query.x.max, query.y.min, query.y.max
+        )),
+        None => ui.text("No query yet"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::with_test_ui_frames;
+
+    #[test]
+    fn test_demo_window_and_sections() {
+        let mut state = DemoState::new();
+        with_test_ui_frames(2, |ui, plot_ui, _| {
+            show_rust_demo_window(ui, plot_ui, &mut state);
+            // The sections are collapsed at first, so they are shown in a window of their own
+            ui.window("demo sections").build(|| {
+                show_line_plot(plot_ui);
+                show_scatter_plot(plot_ui);
+                show_bar_plots(plot_ui);
+                show_stairs_and_stems(plot_ui);
+                show_text_plot(plot_ui);
+                show_heatmap(plot_ui);
+                show_styling(ui, plot_ui, &mut state.colormap);
+                show_custom_ticks(plot_ui);
+                show_interaction(ui, plot_ui, &mut state);
+            });
+            assert_eq!(crate::current_plot_id(), None);
+        });
+    }
+}
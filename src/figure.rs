@@ -0,0 +1,252 @@
+//! # Figure module
+//!
+//! This module contains [`Figure`], a declarative layer on top of [`Plot`] and the plot
+//! elements. A figure holds the plot configuration and a list of [`Series`], and draws all of
+//! them with a single call to [`Figure::show`]. Figures are meant to be created once and kept
+//! around between frames: the labels are converted to C strings when the series are added, so
+//! showing a figure does not allocate anything for its series.
+//!
+//! The data of each series is either borrowed or owned, see [`Series`]. Shaded plots, pie
+//! charts, error bars and the other plot types that are not wrapped by this crate yet are not
+//! available as series.
+use crate::{
+    AxisSettings, Plot, PlotAxis, PlotBars, PlotLine, PlotScatter, PlotStairs, PlotStems, PlotUi,
+};
+use std::borrow::Cow;
+
+/// One series of a [`Figure`], made up of a plot element and its data. The data is stored as a
+/// `Cow`, so it can either borrow data that lives longer than the figure, or own data that is
+/// updated in place between frames through [`Figure::series_mut`].
+pub enum Series<'a> {
+    /// A line plot, see [`PlotLine`]
+    Line(PlotLine, Cow<'a, [f64]>, Cow<'a, [f64]>),
+    /// A scatter plot, see [`PlotScatter`]
+    Scatter(PlotScatter, Cow<'a, [f64]>, Cow<'a, [f64]>),
+    /// A stairs plot, see [`PlotStairs`]
+    Stairs(PlotStairs, Cow<'a, [f64]>, Cow<'a, [f64]>),
+    /// A bar plot with the axis positions and the values of the bars, see [`PlotBars`]
+    Bars(PlotBars, Cow<'a, [f64]>, Cow<'a, [f64]>),
+    /// A stem plot with the axis positions and the values of the stems, see [`PlotStems`]
+    Stems(PlotStems, Cow<'a, [f64]>, Cow<'a, [f64]>),
+}
+
+impl<'a> Series<'a> {
+    /// Draw the series. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build), [`Figure::show`] calls it for every
+    /// series of the figure.
+    pub fn plot(&self) {
+        match self {
+            Series::Line(element, x, y) => element.plot(x, y),
+            Series::Scatter(element, x, y) => element.plot(x, y),
+            Series::Stairs(element, x, y) => element.plot(x, y),
+            Series::Bars(element, positions, values) => element.plot(positions, values),
+            Series::Stems(element, positions, values) => element.plot(positions, values),
+        }
+    }
+
+    /// Returns the data of the series, as X values (or axis positions) and Y values.
+    pub fn data(&self) -> (&[f64], &[f64]) {
+        match self {
+            Series::Line(_, x, y)
+            | Series::Scatter(_, x, y)
+            | Series::Stairs(_, x, y)
+            | Series::Bars(_, x, y)
+            | Series::Stems(_, x, y) => (x, y),
+        }
+    }
+
+    /// Returns the data of the series for changing it in place, see [`Series::data`]. Borrowed
+    /// data is copied the first time this is called, after that the owned data is reused.
+    pub fn data_mut(&mut self) -> (&mut Vec<f64>, &mut Vec<f64>) {
+        match self {
+            Series::Line(_, x, y)
+            | Series::Scatter(_, x, y)
+            | Series::Stairs(_, x, y)
+            | Series::Bars(_, x, y)
+            | Series::Stems(_, x, y) => (x.to_mut(), y.to_mut()),
+        }
+    }
+}
+
+/// A plot together with the series shown in it, which is drawn with one call to
+/// [`Figure::show`]. Create the figure once and show it every frame:
+/// ```no_run
+/// # use implot::{AxisSettings, Figure, PlotAxis, PlotUi};
+/// # fn example(plot_ui: &PlotUi) {
+/// let x = vec![0.0, 1.0, 2.0, 3.0];
+/// let mut figure = Figure::new("Dashboard")
+///     .with_axis(PlotAxis::X, AxisSettings::new().with_label("time [s]"));
+/// // Borrowed data
+/// figure.add_line("measured", &x, &[0.0, 1.0, 4.0, 9.0]);
+/// // Owned data
+/// figure.add_scatter("predicted", x.clone(), vec![0.0, 1.1, 3.9, 9.2]);
+///
+/// // Every frame
+/// figure.show(plot_ui);
+/// # }
+/// ```
+pub struct Figure<'a> {
+    /// Plot that the series are shown in
+    plot: Plot,
+    /// Series shown in the plot, in the order they are drawn in
+    series: Vec<Series<'a>>,
+}
+
+impl<'a> Figure<'a> {
+    /// Create a figure with a new plot with the given title, and no series.
+    ///
+    /// # Panics
+    /// Will panic if the title string contains internal null bytes.
+    pub fn new(title: &str) -> Self {
+        Self::from_plot(Plot::new(title))
+    }
+
+    /// Create a figure that shows its series in the given plot, for using all the configuration
+    /// options of [`Plot`].
+    pub fn from_plot(plot: Plot) -> Self {
+        Self {
+            plot,
+            series: Vec::new(),
+        }
+    }
+
+    /// Apply the settings to the given axis of the plot, see [`Plot::with_axis`].
+    pub fn with_axis(mut self, axis: PlotAxis, settings: AxisSettings) -> Self {
+        self.plot = self.plot.with_axis(axis, settings);
+        self
+    }
+
+    /// Add a series, for series with a customized plot element, such as bars with a different
+    /// width.
+    pub fn add_series(&mut self, series: Series<'a>) -> &mut Self {
+        self.series.push(series);
+        self
+    }
+
+    /// Add a line plot with the given label and data.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn add_line<X, Y>(&mut self, label: &str, x: X, y: Y) -> &mut Self
+    where
+        X: Into<Cow<'a, [f64]>>,
+        Y: Into<Cow<'a, [f64]>>,
+    {
+        self.add_series(Series::Line(PlotLine::new(label), x.into(), y.into()))
+    }
+
+    /// Add a scatter plot with the given label and data.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn add_scatter<X, Y>(&mut self, label: &str, x: X, y: Y) -> &mut Self
+    where
+        X: Into<Cow<'a, [f64]>>,
+        Y: Into<Cow<'a, [f64]>>,
+    {
+        self.add_series(Series::Scatter(PlotScatter::new(label), x.into(), y.into()))
+    }
+
+    /// Add a stairs plot with the given label and data.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn add_stairs<X, Y>(&mut self, label: &str, x: X, y: Y) -> &mut Self
+    where
+        X: Into<Cow<'a, [f64]>>,
+        Y: Into<Cow<'a, [f64]>>,
+    {
+        self.add_series(Series::Stairs(PlotStairs::new(label), x.into(), y.into()))
+    }
+
+    /// Add a vertical bar plot with the given label, bar positions and values. For other bar
+    /// settings, create the [`PlotBars`] yourself and use [`Figure::add_series`].
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn add_bars<P, V>(&mut self, label: &str, positions: P, values: V) -> &mut Self
+    where
+        P: Into<Cow<'a, [f64]>>,
+        V: Into<Cow<'a, [f64]>>,
+    {
+        self.add_series(Series::Bars(
+            PlotBars::new(label),
+            positions.into(),
+            values.into(),
+        ))
+    }
+
+    /// Add a stem plot with the given label, stem positions and values.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn add_stems<P, V>(&mut self, label: &str, positions: P, values: V) -> &mut Self
+    where
+        P: Into<Cow<'a, [f64]>>,
+        V: Into<Cow<'a, [f64]>>,
+    {
+        self.add_series(Series::Stems(
+            PlotStems::new(label),
+            positions.into(),
+            values.into(),
+        ))
+    }
+
+    /// Returns the series of the figure, in the order they are drawn in.
+    pub fn series(&self) -> &[Series<'a>] {
+        &self.series
+    }
+
+    /// Returns the series of the figure for changing them, for example to update their data
+    /// with [`Series::data_mut`].
+    pub fn series_mut(&mut self) -> &mut [Series<'a>] {
+        &mut self.series
+    }
+
+    /// Remove all series from the figure, keeping the plot configuration.
+    pub fn clear_series(&mut self) {
+        self.series.clear();
+    }
+
+    /// Show the plot with all its series. Call this once per frame.
+    #[rustversion::attr(since(1.48), doc(alias = "BeginPlot"))]
+    pub fn show(&self, plot_ui: &PlotUi) {
+        if let Some(token) = self.plot.begin(plot_ui) {
+            for series in &self.series {
+                series.plot();
+            }
+            token.end();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_borrowed_and_owned_series() {
+        let x = vec![0.0, 1.0, 2.0];
+        let mut figure = Figure::new("Figure");
+        figure
+            .add_line("borrowed", &x, &[1.0, 2.0, 3.0][..])
+            .add_bars("owned", vec![0.5], vec![4.0]);
+
+        match &figure.series()[0] {
+            Series::Line(_, Cow::Borrowed(_), Cow::Borrowed(_)) => {}
+            _ => panic!("Slices should be borrowed"),
+        }
+        match &figure.series()[1] {
+            Series::Bars(_, Cow::Owned(_), Cow::Owned(_)) => {}
+            _ => panic!("Vectors should be owned"),
+        }
+
+        // Changing borrowed data copies it, and leaves the original alone
+        figure.series_mut()[0].data_mut().1[0] = 10.0;
+        assert_eq!(figure.series()[0].data(), (&x[..], &[10.0, 2.0, 3.0][..]));
+        assert_eq!(x, [0.0, 1.0, 2.0]);
+
+        figure.clear_series();
+        assert!(figure.series().is_empty());
+    }
+}
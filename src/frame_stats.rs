@@ -0,0 +1,298 @@
+//! # Frame stats module
+//!
+//! This module counts what is submitted to ImPlot per frame, for finding out what makes plotting
+//! slow: plots, plotted items, points and style pushes. Counting is off by default and turned on
+//! for each context with [`Context::set_frame_stats_enabled`]. While no context of the thread
+//! counts, the plotting functions only check an empty list. The counts can be read as
+//! [`FrameStats`] or shown with [`show_stats_overlay`].
+use crate::context::current_raw_context;
+use crate::imgui_compat::{frame_count, show_text_window};
+use crate::{sys, Context, PlotUi};
+use std::cell::RefCell;
+
+/// Counts of what was submitted to ImPlot in one frame, see [`Context::frame_stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FrameStats {
+    /// Number of plots that were begun and rendered
+    pub plots_begun: usize,
+    /// Number of plot elements that were plotted, such as lines, bars or heatmaps
+    pub items_plotted: usize,
+    /// Number of points passed to ImPlot by the plotted items. Heatmaps count each value, text
+    /// counts as one point, histograms count each bin and lines across the plot count none.
+    pub points_submitted: usize,
+    /// Number of entries pushed to the style color, style variable and colormap stacks, through
+    /// the push functions or as style overrides of plots
+    pub style_pushes: usize,
+}
+
+/// Internal state of the frame stats of one context.
+#[derive(Default)]
+struct FrameStatsState {
+    /// The raw context the stats are counted for, as a number since raw pointers are not `Send`
+    context: usize,
+    /// Whether counting is turned on for the context
+    enabled: bool,
+    /// imgui frame counter of the frame that `current` is for
+    frame: i32,
+    /// Counts of the current frame so far
+    current: FrameStats,
+    /// Counts of the frame before the current one
+    previous: FrameStats,
+}
+
+impl FrameStatsState {
+    /// Move on to the given frame if it is not the current one. The frames without any counts
+    /// in between get no state of their own, so the previous frame's counts are all zero unless
+    /// the current ones are from the frame right before.
+    fn advance_to(&mut self, frame: i32) {
+        if frame != self.frame {
+            self.previous = if frame == self.frame.wrapping_add(1) {
+                self.current
+            } else {
+                FrameStats::default()
+            };
+            self.current = FrameStats::default();
+            self.frame = frame;
+        }
+    }
+}
+
+thread_local! {
+    /// Frame stats of the contexts that counting was turned on for, see
+    /// `Context::set_frame_stats_enabled`. ImPlot contexts can only be used on the thread they
+    /// were created on, so these are contexts of this thread.
+    static FRAME_STATS: RefCell<Vec<FrameStatsState>> = RefCell::new(Vec::new());
+}
+
+/// Internal helper that applies `count` to the stats of the current frame of the current
+/// context, if frame stats are enabled for it.
+fn record<F: FnOnce(&mut FrameStats)>(count: F) {
+    FRAME_STATS.with(|states| {
+        let mut states = states.borrow_mut();
+        if states.is_empty() {
+            return;
+        }
+        let context = current_raw_context() as usize;
+        if let Some(state) = states
+            .iter_mut()
+            .find(|state| state.context == context && state.enabled)
+        {
+            state.advance_to(frame_count());
+            count(&mut state.current);
+        }
+    });
+}
+
+/// Internal helper that returns the stats of the given context for the previous frame, or for
+/// the current frame so far.
+fn read_stats(context: *mut sys::ImPlotContext, previous: bool) -> FrameStats {
+    FRAME_STATS.with(|states| {
+        let mut states = states.borrow_mut();
+        let state = match states
+            .iter_mut()
+            .find(|state| state.context == context as usize)
+        {
+            Some(state) => state,
+            None => return FrameStats::default(),
+        };
+        state.advance_to(frame_count());
+        if previous {
+            state.previous
+        } else {
+            state.current
+        }
+    })
+}
+
+/// Internal helper that returns whether frame stats are counted for the given context.
+fn stats_enabled(context: *mut sys::ImPlotContext) -> bool {
+    FRAME_STATS.with(|states| {
+        states
+            .borrow()
+            .iter()
+            .any(|state| state.context == context as usize && state.enabled)
+    })
+}
+
+/// Forget the frame stats of a context that is destroyed, so a context created at the same
+/// address later on starts out without stats.
+pub(crate) fn forget_frame_stats_of(context: *mut sys::ImPlotContext) {
+    FRAME_STATS.with(|states| {
+        states
+            .borrow_mut()
+            .retain(|state| state.context != context as usize)
+    });
+}
+
+/// Count a plot that was begun and is rendered.
+pub(crate) fn record_plot_begun() {
+    record(|stats| stats.plots_begun += 1);
+}
+
+/// Count a plotted item with the given number of points.
+pub(crate) fn record_item_plotted(points: usize) {
+    record(|stats| {
+        stats.items_plotted += 1;
+        stats.points_submitted += points;
+    });
+}
+
+/// Count the given number of pushes to the style and colormap stacks.
+pub(crate) fn record_style_pushes(count: usize) {
+    record(|stats| stats.style_pushes += count);
+}
+
+impl Context {
+    /// Turn counting of [`FrameStats`] for this context on or off. It is off by default. Each
+    /// context counts what is plotted while it is the current one.
+    pub fn set_frame_stats_enabled(&self, enabled: bool) {
+        let context = self.raw() as usize;
+        FRAME_STATS.with(|states| {
+            let mut states = states.borrow_mut();
+            match states.iter_mut().find(|state| state.context == context) {
+                Some(state) => state.enabled = enabled,
+                None if enabled => states.push(FrameStatsState {
+                    context,
+                    enabled,
+                    ..FrameStatsState::default()
+                }),
+                None => {}
+            }
+        });
+    }
+
+    /// Returns true if [`FrameStats`] are counted for this context, see
+    /// [`Context::set_frame_stats_enabled`].
+    pub fn frame_stats_enabled(&self) -> bool {
+        stats_enabled(self.raw())
+    }
+
+    /// Returns the counts of the previous frame, which is complete, unlike the ones of the
+    /// current frame. The counts are zero while counting is turned off. This uses imgui's frame
+    /// counter, so it has to be called while an imgui frame is active.
+    pub fn frame_stats(&self) -> FrameStats {
+        read_stats(self.raw(), true)
+    }
+
+    /// Returns the counts of the current frame so far. Like [`Context::frame_stats`], this has
+    /// to be called while an imgui frame is active.
+    pub fn current_frame_stats(&self) -> FrameStats {
+        read_stats(self.raw(), false)
+    }
+}
+
+/// Show a small imgui window with the [`FrameStats`] of the previous frame of the context of
+/// `plot_ui`. Call this once per frame, anywhere outside of plots. If counting is turned off,
+/// the window says so instead.
+pub fn show_stats_overlay(plot_ui: &PlotUi) {
+    let context = plot_ui.context;
+    let text = if context.frame_stats_enabled() {
+        let stats = context.frame_stats();
+        format!(
+            "plots: {}\nitems: {}\npoints: {}\nstyle pushes: {}",
+            stats.plots_begun, stats.items_plotted, stats.points_submitted, stats.style_pushes
+        )
+    } else {
+        "Frame stats are turned off, see Context::set_frame_stats_enabled".to_owned()
+    };
+    show_text_window("implot frame stats", &text);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "imgui-rs")]
+    use crate::test_support::with_test_frames;
+
+    #[test]
+    fn test_advance_to_next_frame() {
+        let mut state = FrameStatsState::default();
+        state.advance_to(10);
+        state.current.plots_begun = 2;
+        state.advance_to(10);
+        assert_eq!(state.current.plots_begun, 2);
+
+        state.advance_to(11);
+        assert_eq!(state.previous.plots_begun, 2);
+        assert_eq!(state.current, FrameStats::default());
+
+        // Frame 12 had no counts, so frame 13 has to report zeros for it
+        state.current.items_plotted = 5;
+        state.advance_to(13);
+        assert_eq!(state.previous, FrameStats::default());
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_frame_stats_count_per_frame() {
+        let build_frame = |plot_ui: &PlotUi| {
+            let token = crate::push_style_var(plot_ui, crate::StyleVarValue::LineWeight(2.0));
+            crate::Plot::new("stats")
+                .with_style_override(crate::StyleVarValue::MarkerSize(3.0))
+                .build(plot_ui, || {});
+            token.pop();
+            show_stats_overlay(plot_ui);
+        };
+
+        let mut frame = 0;
+        with_test_frames(4, |plot_ui, context| {
+            match frame {
+                0 => {
+                    build_frame(plot_ui);
+                    assert_eq!(context.current_frame_stats(), FrameStats::default());
+                    context.set_frame_stats_enabled(true);
+                }
+                1 | 2 => {
+                    build_frame(plot_ui);
+                    let expected = FrameStats {
+                        plots_begun: 1,
+                        items_plotted: 0,
+                        points_submitted: 0,
+                        style_pushes: 2,
+                    };
+                    assert_eq!(context.current_frame_stats(), expected);
+                }
+                _ => {
+                    assert_eq!(context.frame_stats().plots_begun, 1);
+                    assert_eq!(context.frame_stats().style_pushes, 2);
+                    assert_eq!(context.current_frame_stats(), FrameStats::default());
+                    context.set_frame_stats_enabled(false);
+                }
+            }
+            frame += 1;
+        });
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_frame_stats_are_per_context() {
+        let _guard = crate::test_support::TEST_MUTEX.lock();
+        let mut imgui = imgui::Context::create();
+        imgui.io_mut().display_size = [800.0, 600.0];
+        imgui.fonts().build_rgba32_texture();
+        let counted = Context::create_for(&imgui);
+        let other = Context::create_for(&imgui);
+        counted.set_frame_stats_enabled(true);
+        assert!(counted.frame_stats_enabled());
+        assert!(!other.frame_stats_enabled());
+
+        let ui = imgui.new_frame();
+        crate::Plot::new("counted").build(&counted.get_plot_ui(ui), || {});
+        crate::Plot::new("other").build(&other.get_plot_ui(ui), || {});
+        assert_eq!(counted.current_frame_stats().plots_begun, 1);
+        assert_eq!(other.current_frame_stats(), FrameStats::default());
+        imgui.render();
+
+        // Dropping the context forgets its stats, so a later context at the same address
+        // doesn't start out with them
+        let counted_raw = counted.raw();
+        drop(counted);
+        FRAME_STATS.with(|states| {
+            assert!(states
+                .borrow()
+                .iter()
+                .all(|state| state.context != counted_raw as usize))
+        });
+        drop(other);
+    }
+}
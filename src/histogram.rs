@@ -0,0 +1,504 @@
+//! # Histogram module
+//!
+//! This module contains histogram binning in plain Rust, for when the bin edges and counts are
+//! needed themselves, for example to export them or to overlay a fitted curve, and the
+//! [`PlotHistogram`] element that draws them. The element bins its data with the same code, so
+//! a histogram plotted from values looks exactly like one plotted from precomputed [`Bins`].
+use crate::frame_stats::record_item_plotted;
+use crate::plot::check_inside_plot;
+use crate::plot_elements::{checked_point_count, report_plot_error, PlotError};
+use crate::sys;
+use std::ffi::CString;
+use std::os::raw::c_char;
+
+/// How the number of bins of a histogram is chosen. All methods use bins of equal width.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BinMethod {
+    /// A fixed number of bins. Zero is treated as one bin.
+    Count(usize),
+    /// The square root of the number of values, rounded up
+    Sqrt,
+    /// Sturges' rule, `1 + log2(n)` bins rounded up, which suits roughly normal data
+    Sturges,
+    /// The Rice rule, `2 * cbrt(n)` bins rounded up
+    Rice,
+    /// Scott's rule, which picks a bin width of `3.49 * stddev / cbrt(n)`
+    Scott,
+}
+
+/// Bins of a histogram, as returned by [`histogram_bins`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Bins {
+    /// Edges of the bins, in increasing order. There is one edge more than there are bins, and
+    /// bin `i` covers the values from `edges[i]` to `edges[i + 1]`. Each bin includes its lower
+    /// edge, and the last bin also includes its upper edge.
+    pub edges: Vec<f64>,
+    /// Number of values in each bin
+    pub counts: Vec<u64>,
+}
+
+impl Bins {
+    /// Returns the number of bins.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Returns true if there are no bins.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Returns the number of values in all bins together.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Returns the center of each bin.
+    pub fn centers(&self) -> Vec<f64> {
+        self.edges
+            .windows(2)
+            .map(|edges| 0.5 * (edges[0] + edges[1]))
+            .collect()
+    }
+
+    /// Returns the probability density in each bin: the count divided by the total count and by
+    /// the width of the bin, so the area of all bins together is one. All densities are zero if
+    /// there are no values.
+    pub fn density(&self) -> Vec<f64> {
+        let total = self.total();
+        self.counts
+            .iter()
+            .zip(self.edges.windows(2))
+            .map(|(count, edges)| {
+                if total == 0 {
+                    0.0
+                } else {
+                    *count as f64 / (total as f64 * (edges[1] - edges[0]))
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the cumulative counts, where each bin holds the number of values in it and in
+    /// all bins before it.
+    pub fn cumulative(&self) -> Vec<u64> {
+        self.counts
+            .iter()
+            .scan(0, |sum, count| {
+                *sum += count;
+                Some(*sum)
+            })
+            .collect()
+    }
+}
+
+/// Sort the values into bins of equal width between the ends of `range`, or between the
+/// smallest and the largest value if there is no range. Values outside of the range and values
+/// that are not finite are left out. If the range is empty, such as when all values are equal,
+/// a single bin of width one is centered on it. Without values and without a range, there are
+/// no bins.
+/// ```
+/// # use implot::{histogram_bins, BinMethod};
+/// let bins = histogram_bins(&[0.0, 1.5, 1.7, 3.0], BinMethod::Count(3), None);
+/// assert_eq!(bins.edges, [0.0, 1.0, 2.0, 3.0]);
+/// assert_eq!(bins.counts, [1, 2, 1]);
+/// ```
+pub fn histogram_bins(values: &[f64], method: BinMethod, range: Option<(f64, f64)>) -> Bins {
+    let (min, max) = match range {
+        Some((a, b)) => (a.min(b), a.max(b)),
+        None => {
+            let mut finite = values.iter().copied().filter(|value| value.is_finite());
+            let first = match finite.next() {
+                Some(first) => first,
+                None => return Bins::default(),
+            };
+            finite.fold((first, first), |(min, max), value| {
+                (min.min(value), max.max(value))
+            })
+        }
+    };
+    let in_range = || {
+        values
+            .iter()
+            .copied()
+            .filter(move |value| value.is_finite() && min <= *value && *value <= max)
+    };
+
+    let (min, max, bin_count) = if max > min {
+        (min, max, bin_count(in_range(), method, max - min))
+    } else {
+        (min - 0.5, max + 0.5, 1)
+    };
+    let width = (max - min) / bin_count as f64;
+    let mut edges: Vec<f64> = (0..bin_count).map(|bin| min + bin as f64 * width).collect();
+    // The last edge is the end of the range itself, without rounding errors
+    edges.push(max);
+
+    let mut counts = vec![0; bin_count];
+    for value in in_range() {
+        // The largest value belongs to the last bin, not to one past it
+        let bin = (((value - min) / width) as usize).min(bin_count - 1);
+        counts[bin] += 1;
+    }
+    Bins { edges, counts }
+}
+
+/// Internal helper that returns the number of bins for the method, which is at least one.
+fn bin_count<I: Iterator<Item = f64> + Clone>(values: I, method: BinMethod, span: f64) -> usize {
+    let n = values.clone().count() as f64;
+    let count = match method {
+        BinMethod::Count(count) => count as f64,
+        BinMethod::Sqrt => n.sqrt().ceil(),
+        BinMethod::Sturges => (1.0 + n.log2()).ceil(),
+        BinMethod::Rice => (2.0 * n.cbrt()).ceil(),
+        BinMethod::Scott => {
+            if n < 2.0 {
+                1.0
+            } else {
+                let mean = values.clone().sum::<f64>() / n;
+                let variance = values.map(|value| (value - mean).powi(2)).sum::<f64>() / n;
+                let width = 3.49 * variance.sqrt() / n.cbrt();
+                (span / width).round()
+            }
+        }
+    };
+    // Also catches NaN and infinite counts, for example from a standard deviation of zero
+    if count.is_finite() && count >= 1.0 {
+        count as usize
+    } else {
+        1
+    }
+}
+
+/// Struct to provide histogram plotting functionality. The histogram is drawn as bars that fill
+/// their bins.
+pub struct PlotHistogram {
+    /// Label to show in the legend for this histogram
+    label: CString,
+    /// How the number of bins is chosen when plotting values
+    method: BinMethod,
+    /// Range of the bins when plotting values, if not the range of the values
+    range: Option<(f64, f64)>,
+    /// Whether densities are drawn instead of counts
+    density: bool,
+    /// Whether cumulative counts are drawn
+    cumulative: bool,
+}
+
+impl PlotHistogram {
+    /// Create a new histogram to be shown, with Sturges' rule for the number of bins. Does not
+    /// draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes, see
+    /// [`PlotHistogram::try_new`] for a version that returns an error instead.
+    pub fn new(label: &str) -> Self {
+        Self::try_new(label)
+            .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label))
+    }
+
+    /// Like [`PlotHistogram::new`], but returns [`PlotError::LabelContainsNul`] if the label
+    /// string contains internal null bytes.
+    pub fn try_new(label: &str) -> Result<Self, PlotError> {
+        Ok(Self {
+            label: CString::new(label).map_err(|_| PlotError::LabelContainsNul)?,
+            method: BinMethod::Sturges,
+            range: None,
+            density: false,
+            cumulative: false,
+        })
+    }
+
+    /// Set how the number of bins is chosen, see [`BinMethod`].
+    pub fn with_bin_method(mut self, method: BinMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Set the range that is divided into bins. Values outside of it are left out.
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Draw the probability density instead of the counts, see [`Bins::density`].
+    pub fn with_density(mut self) -> Self {
+        self.density = true;
+        self
+    }
+
+    /// Draw cumulative counts, see [`Bins::cumulative`]. Together with
+    /// [`PlotHistogram::with_density`], this draws the cumulative distribution instead.
+    pub fn with_cumulative(mut self) -> Self {
+        self.cumulative = true;
+        self
+    }
+
+    /// Sort the values into bins with the method and range of this histogram, and draw them.
+    /// Use this in closures passed to [`Plot::build()`](struct.Plot.html#method.build).
+    ///
+    /// Nothing is drawn without values. For the other errors [`PlotHistogram::try_plot`]
+    /// returns, this fails a debug assertion, and release builds skip the histogram.
+    #[track_caller]
+    pub fn plot(&self, values: &[f64]) {
+        match self.try_plot(values) {
+            Ok(()) | Err(PlotError::EmptyData) => {}
+            Err(error) => report_plot_error(&self.label, &error),
+        }
+    }
+
+    /// Like [`PlotHistogram::plot`], but returns an error instead of plotting the histogram if
+    /// the range set with [`PlotHistogram::with_range`] is not finite, or for the reasons
+    /// [`PlotHistogram::try_plot_bins`] returns one for the resulting bins.
+    #[track_caller]
+    pub fn try_plot(&self, values: &[f64]) -> Result<(), PlotError> {
+        if let Some((min, max)) = self.range {
+            if !min.is_finite() || !max.is_finite() {
+                return Err(PlotError::NonFiniteBounds);
+            }
+        }
+        self.try_plot_bins(&histogram_bins(values, self.method, self.range))
+    }
+
+    /// Draw precomputed bins, such as ones returned by [`histogram_bins`]. The bin method and
+    /// range of this histogram are not used. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build).
+    ///
+    /// Nothing is drawn without bins. For the other errors [`PlotHistogram::try_plot_bins`]
+    /// returns, this fails a debug assertion, and release builds skip the histogram.
+    #[track_caller]
+    pub fn plot_bins(&self, bins: &Bins) {
+        match self.try_plot_bins(bins) {
+            Ok(()) | Err(PlotError::EmptyData) => {}
+            Err(error) => report_plot_error(&self.label, &error),
+        }
+    }
+
+    /// Like [`PlotHistogram::plot_bins`], but returns an error instead of plotting the histogram
+    /// if there are no bins, if there isn't exactly one edge more than there are bins, if any of
+    /// the edges is not finite, or if there are too many bins.
+    #[track_caller]
+    pub fn try_plot_bins(&self, bins: &Bins) -> Result<(), PlotError> {
+        if !check_inside_plot("PlotHistogram::plot") {
+            return Ok(());
+        }
+        let number_of_bins = checked_bin_count(bins)?;
+        let centers = bins.centers();
+        let heights = self.bar_heights(bins);
+        record_item_plotted(number_of_bins as usize);
+        // The calls share the label, so ImPlot shows them as a single item
+        for (first_bin, count, width) in equal_width_runs(&bins.edges) {
+            unsafe {
+                sys::ImPlot_PlotBarsdoublePtrdoublePtr(
+                    self.label.as_ptr() as *const c_char,
+                    centers[first_bin..].as_ptr(),
+                    heights[first_bin..].as_ptr(),
+                    count as i32,
+                    width,
+                    0,                                 // No offset
+                    std::mem::size_of::<f64>() as i32, // Stride of one f64
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Internal helper returning the heights of the bars for the bins.
+    fn bar_heights(&self, bins: &Bins) -> Vec<f64> {
+        match (self.density, self.cumulative) {
+            (false, false) => bins.counts.iter().map(|count| *count as f64).collect(),
+            (false, true) => bins.cumulative().iter().map(|sum| *sum as f64).collect(),
+            (true, false) => bins.density(),
+            (true, true) => {
+                let total = bins.total().max(1) as f64;
+                bins.cumulative()
+                    .iter()
+                    .map(|sum| *sum as f64 / total)
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Internal helper that splits bins into runs of bins of the same width, since ImPlot draws all
+/// bars of one call with the same width. Returns the first bin, the number of bins and the
+/// width of each run. Widths that only differ by rounding errors, like the ones of the bins from
+/// [`histogram_bins`], count as the same.
+fn equal_width_runs(edges: &[f64]) -> Vec<(usize, usize, f64)> {
+    let mut runs: Vec<(usize, usize, f64)> = Vec::new();
+    for (bin, bin_edges) in edges.windows(2).enumerate() {
+        let width = bin_edges[1] - bin_edges[0];
+        match runs.last_mut() {
+            Some((_, count, run_width)) if (width - *run_width).abs() <= 1e-9 * run_width.abs() => {
+                *count += 1
+            }
+            _ => runs.push((bin, 1, width)),
+        }
+    }
+    runs
+}
+
+/// Internal helper that checks the dimensions of the given bins for plotting them, and returns
+/// the number of bins to pass to ImPlot. With this many bins, there are as many centers and
+/// heights, so ImPlot can't read past them.
+fn checked_bin_count(bins: &Bins) -> Result<i32, PlotError> {
+    if bins.is_empty() {
+        return Err(PlotError::EmptyData);
+    }
+    if bins.edges.len() != bins.len() + 1 {
+        return Err(PlotError::DimensionMismatch {
+            expected: bins.len() + 1,
+            actual: bins.edges.len(),
+        });
+    }
+    if !bins.edges.iter().all(|edge| edge.is_finite()) {
+        return Err(PlotError::NonFiniteBounds);
+    }
+    checked_point_count(bins.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "imgui-rs")]
+    use crate::test_support::with_test_frames;
+
+    #[test]
+    fn test_bin_count_matches_edges() {
+        for edges in 0..8 {
+            for counts in 0..8 {
+                let bins = Bins {
+                    edges: (0..edges).map(|edge| edge as f64).collect(),
+                    counts: vec![1; counts],
+                };
+                let expected = if counts == 0 {
+                    Err(PlotError::EmptyData)
+                } else if edges == counts + 1 {
+                    Ok(counts as i32)
+                } else {
+                    Err(PlotError::DimensionMismatch {
+                        expected: counts + 1,
+                        actual: edges,
+                    })
+                };
+                assert_eq!(checked_bin_count(&bins), expected);
+            }
+        }
+        let bins = Bins {
+            edges: vec![0.0, f64::NAN],
+            counts: vec![1],
+        };
+        assert_eq!(checked_bin_count(&bins), Err(PlotError::NonFiniteBounds));
+        assert_eq!(
+            PlotHistogram::try_new("nul\0").err(),
+            Some(PlotError::LabelContainsNul)
+        );
+    }
+
+    #[test]
+    fn test_fixed_count_reference() {
+        // Reference counts from numpy.histogram([...], bins=4)
+        let values = [1.0, 2.0, 2.0, 3.0, 3.5, 4.0, 7.0, 9.0];
+        let bins = histogram_bins(&values, BinMethod::Count(4), None);
+        assert_eq!(bins.edges, [1.0, 3.0, 5.0, 7.0, 9.0]);
+        assert_eq!(bins.counts, [3, 3, 0, 2]);
+
+        // numpy.histogram([...], bins=2, range=(0, 4)) leaves out 7 and 9
+        let bins = histogram_bins(&values, BinMethod::Count(2), Some((4.0, 0.0)));
+        assert_eq!(bins.edges, [0.0, 2.0, 4.0]);
+        assert_eq!(bins.counts, [1, 5]);
+    }
+
+    #[test]
+    fn test_bin_methods() {
+        let values: Vec<f64> = (0..100).map(|i| i as f64).collect();
+        let count = |method| histogram_bins(&values, method, None).len();
+        assert_eq!(count(BinMethod::Sqrt), 10);
+        // 1 + log2(100) = 7.64
+        assert_eq!(count(BinMethod::Sturges), 8);
+        // 2 * cbrt(100) = 9.28
+        assert_eq!(count(BinMethod::Rice), 10);
+        // The standard deviation is 28.87, so the width is 21.7 and 99 / 21.7 = 4.56
+        assert_eq!(count(BinMethod::Scott), 5);
+        assert_eq!(count(BinMethod::Count(0)), 1);
+        for method in &[BinMethod::Sqrt, BinMethod::Sturges, BinMethod::Scott] {
+            assert_eq!(histogram_bins(&values, *method, None).total(), 100);
+        }
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        assert_eq!(histogram_bins(&[], BinMethod::Sqrt, None), Bins::default());
+        assert_eq!(
+            histogram_bins(&[f64::NAN, f64::INFINITY], BinMethod::Sqrt, None),
+            Bins::default()
+        );
+
+        // Equal values get a single bin around them, whatever the method
+        let bins = histogram_bins(&[2.0, 2.0], BinMethod::Scott, None);
+        assert_eq!(bins.edges, [1.5, 2.5]);
+        assert_eq!(bins.counts, [2]);
+
+        // A range without values in it still has bins
+        let bins = histogram_bins(&[], BinMethod::Count(2), Some((0.0, 1.0)));
+        assert_eq!(bins.counts, [0, 0]);
+        assert_eq!(bins.density(), [0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_density_and_cumulative() {
+        let bins = Bins {
+            edges: vec![0.0, 0.5, 1.0, 1.5],
+            counts: vec![1, 2, 1],
+        };
+        assert_eq!(bins.centers(), [0.25, 0.75, 1.25]);
+        assert_eq!(bins.density(), [0.5, 1.0, 0.5]);
+        let area: f64 = bins.density().iter().map(|density| density * 0.5).sum();
+        assert_eq!(area, 1.0);
+        assert_eq!(bins.cumulative(), [1, 3, 4]);
+
+        let histogram = PlotHistogram::new("histogram")
+            .with_density()
+            .with_cumulative();
+        assert_eq!(histogram.bar_heights(&bins), [0.25, 0.75, 1.0]);
+    }
+
+    #[test]
+    fn test_equal_width_runs() {
+        assert_eq!(equal_width_runs(&[0.0, 1.0, 2.0, 3.0]), [(0, 3, 1.0)]);
+        assert_eq!(
+            equal_width_runs(&[0.0, 1.0, 2.0, 4.0, 6.0, 7.0]),
+            [(0, 2, 1.0), (2, 2, 2.0), (4, 1, 1.0)]
+        );
+        assert_eq!(equal_width_runs(&[0.0]), []);
+
+        // Rounding errors in the edges don't split the bins
+        let bins = histogram_bins(&[0.1, 0.7, 1.3, 2.9], BinMethod::Count(7), None);
+        assert_eq!(equal_width_runs(&bins.edges).len(), 1);
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_inconsistent_bins_do_not_reach_implot() {
+        // Any of these reaching ImPlot would make it read past the data, which the test build
+        // turns into an abort
+        let values = [1.0; 6];
+        with_test_frames(2, |plot_ui, _| {
+            crate::Plot::new("inconsistent bins").build(plot_ui, || {
+                let histogram = PlotHistogram::new("histogram");
+                for edges in 0..6 {
+                    for counts in 0..6 {
+                        if edges == counts + 1 && counts > 0 {
+                            continue;
+                        }
+                        let bins = Bins {
+                            edges: values[..edges].to_vec(),
+                            counts: vec![1; counts],
+                        };
+                        assert!(histogram.try_plot_bins(&bins).is_err());
+                    }
+                }
+            });
+        });
+    }
+}
@@ -0,0 +1,285 @@
+//! # imgui compatibility module
+//!
+//! Everything this crate uses from imgui-rs goes through this module: the re-exported types
+//! that are part of the public API, and internal wrappers around the low-level imgui functions
+//! and fields that are not part of ImPlot. When imgui-rs renames or changes one of these, which
+//! happens regularly between releases, this module is the only place that needs to change. The
+//! remaining direct uses of imgui-rs are calls of `Ui` methods in [`colormap_selector`] and in
+//! the demo window, which only use long-standing widget functions.
+//!
+//! The low-level functions go through imgui-sys directly, so they also work without imgui-rs.
+//! Without the `imgui-rs` feature, this module defines the [`Condition`] and [`MouseButton`]
+//! enums itself, with the same values as imgui.
+//!
+//! There are no features for picking the imgui-rs version, see the "Optional features" section
+//! of the README for why. Supporting a new imgui-rs release means updating the `imgui` and
+//! `imgui-sys` versions in both manifests and, if needed, the functions below.
+//!
+//! [`colormap_selector`]: ../fn.colormap_selector.html
+use crate::{ImVec2, ImVec4};
+use imgui_sys as sys;
+
+#[cfg(feature = "imgui-rs")]
+pub use imgui::{Condition, Context as ImguiContext, MouseButton, Ui};
+
+/// Condition for setting values, such as the limits of a plot axis. This is the same as
+/// `imgui::Condition`, which takes its place with the `imgui-rs` feature.
+#[cfg(not(feature = "imgui-rs"))]
+#[repr(i32)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Condition {
+    /// Never set the value
+    Never = -1,
+    /// Always set the value
+    Always = sys::ImGuiCond_Always as i32,
+    /// Set the value once per runtime session (only the first call will succeed)
+    Once = sys::ImGuiCond_Once as i32,
+    /// Set the value if the object has no persistently saved data (no entry in the ini file)
+    FirstUseEver = sys::ImGuiCond_FirstUseEver as i32,
+    /// Set the value if the object is appearing after being hidden or inactive
+    Appearing = sys::ImGuiCond_Appearing as i32,
+}
+
+/// A mouse button. This is the same as `imgui::MouseButton`, which takes its place with the
+/// `imgui-rs` feature.
+#[cfg(not(feature = "imgui-rs"))]
+#[repr(u32)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    /// The left mouse button
+    Left = 0,
+    /// The right mouse button
+    Right = 1,
+    /// The middle mouse button
+    Middle = 2,
+    /// The first extra mouse button
+    Extra1 = 3,
+    /// The second extra mouse button
+    Extra2 = 4,
+}
+
+#[cfg(not(feature = "imgui-rs"))]
+impl MouseButton {
+    /// All mouse buttons, in the order of their values
+    pub const VARIANTS: [MouseButton; 5] = [
+        MouseButton::Left,
+        MouseButton::Right,
+        MouseButton::Middle,
+        MouseButton::Extra1,
+        MouseButton::Extra2,
+    ];
+}
+
+/// The imgui key modifier flags, which [`KeyModifiers`](../struct.KeyModifiers.html) uses.
+pub(crate) const KEY_MOD_NONE: u32 = sys::ImGuiKeyModFlags_None;
+pub(crate) const KEY_MOD_CTRL: u32 = sys::ImGuiKeyModFlags_Ctrl;
+pub(crate) const KEY_MOD_SHIFT: u32 = sys::ImGuiKeyModFlags_Shift;
+pub(crate) const KEY_MOD_ALT: u32 = sys::ImGuiKeyModFlags_Alt;
+pub(crate) const KEY_MOD_SUPER: u32 = sys::ImGuiKeyModFlags_Super;
+
+/// imgui style colors that ImPlot derives its automatic colors from.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum ImguiColor {
+    Text,
+    FrameBg,
+    WindowBg,
+    Border,
+    PopupBg,
+}
+
+/// Returns true if there is a current imgui context.
+pub(crate) fn imgui_context_exists() -> bool {
+    !unsafe { sys::igGetCurrentContext() }.is_null()
+}
+
+/// Returns the imgui frame counter.
+pub(crate) fn frame_count() -> i32 {
+    unsafe { sys::igGetFrameCount() }
+}
+
+/// Returns the ID at the top of imgui's ID stack, which stands for the current window along with
+/// the IDs pushed in it. The ID of the empty string is the seed it is hashed with, which is that.
+pub(crate) fn current_id_scope() -> u32 {
+    unsafe { sys::igGetID_Str(b"\0".as_ptr() as *const std::os::raw::c_char) }
+}
+
+/// Returns whether the control, shift, alt and super keys are held down, in that order.
+pub(crate) fn key_modifiers_held() -> [bool; 4] {
+    let io = unsafe { &*sys::igGetIO() };
+    [io.KeyCtrl, io.KeyShift, io.KeyAlt, io.KeySuper]
+}
+
+/// Returns true if the mouse button is held down.
+pub(crate) fn mouse_down(button: MouseButton) -> bool {
+    let io = unsafe { &*sys::igGetIO() };
+    io.MouseDown[button as usize]
+}
+
+/// Returns true if the mouse button was clicked in this frame.
+pub(crate) fn mouse_clicked(button: MouseButton) -> bool {
+    let io = unsafe { &*sys::igGetIO() };
+    io.MouseClicked[button as usize]
+}
+
+/// Returns true if the mouse button was double clicked in this frame.
+pub(crate) fn mouse_double_clicked(button: MouseButton) -> bool {
+    let io = unsafe { &*sys::igGetIO() };
+    io.MouseDoubleClicked[button as usize]
+}
+
+/// Returns the size of imgui's draw list indices in bytes, which is 2 unless imgui was built
+/// with 32-bit indices.
+pub(crate) fn draw_index_size() -> usize {
+    std::mem::size_of::<sys::ImDrawIdx>()
+}
+
+/// Returns true if the renderer of the current imgui context supports vertex offsets, which
+/// lets draw lists grow beyond the 16-bit index range.
+pub(crate) fn renderer_has_vtx_offset() -> bool {
+    if !imgui_context_exists() {
+        return false;
+    }
+    let io = unsafe { &*sys::igGetIO() };
+    io.BackendFlags as u32 & sys::ImGuiBackendFlags_RendererHasVtxOffset != 0
+}
+
+/// Returns a color of the current imgui style.
+///
+/// # Panics
+/// Will panic if there is no current imgui context.
+pub(crate) fn style_color(color: ImguiColor) -> ImVec4 {
+    assert!(
+        imgui_context_exists(),
+        "Automatic style colors can only be resolved while an imgui context exists"
+    );
+    let index = match color {
+        ImguiColor::Text => sys::ImGuiCol_Text,
+        ImguiColor::FrameBg => sys::ImGuiCol_FrameBg,
+        ImguiColor::WindowBg => sys::ImGuiCol_WindowBg,
+        ImguiColor::Border => sys::ImGuiCol_Border,
+        ImguiColor::PopupBg => sys::ImGuiCol_PopupBg,
+    };
+    unsafe { (*sys::igGetStyle()).Colors[index as usize] }
+}
+
+/// Returns the horizontal and vertical item spacing of the current imgui style.
+pub(crate) fn item_spacing() -> ImVec2 {
+    unsafe { (*sys::igGetStyle()).ItemSpacing }
+}
+
+/// Returns the space that is left in the current window, in pixels.
+pub(crate) fn content_region_avail() -> ImVec2 {
+    let mut available = ImVec2 { x: 0.0, y: 0.0 };
+    unsafe {
+        sys::igGetContentRegionAvail(&mut available as *mut ImVec2);
+    }
+    available
+}
+
+/// Places the next item on the same line as the previous one, with the default spacing.
+pub(crate) fn same_line() {
+    unsafe {
+        sys::igSameLine(0.0, -1.0);
+    }
+}
+
+/// Shows a plain text tooltip next to the mouse cursor.
+pub(crate) fn show_tooltip(text: &str) {
+    let range = text.as_bytes().as_ptr_range();
+    unsafe {
+        sys::igBeginTooltip();
+        sys::igTextUnformatted(range.start as *const _, range.end as *const _);
+        sys::igEndTooltip();
+    }
+}
+
+/// Shows a small window with plain text in it, sized to fit the text.
+pub(crate) fn show_text_window(title: &str, text: &str) {
+    let title = std::ffi::CString::new(title).expect("Window title contained internal null bytes");
+    let range = text.as_bytes().as_ptr_range();
+    let flags = sys::ImGuiWindowFlags_AlwaysAutoResize
+        | sys::ImGuiWindowFlags_NoFocusOnAppearing
+        | sys::ImGuiWindowFlags_NoNav;
+    unsafe {
+        if sys::igBegin(
+            title.as_ptr(),
+            std::ptr::null_mut(),
+            flags as sys::ImGuiWindowFlags,
+        ) {
+            sys::igTextUnformatted(range.start as *const _, range.end as *const _);
+        }
+        // Unlike most imgui functions, End has to be called even if Begin returned false
+        sys::igEnd();
+    }
+}
+
+/// Returns the size of the text in pixels, with the current font.
+pub(crate) fn calc_text_size(text: &str) -> ImVec2 {
+    let range = text.as_bytes().as_ptr_range();
+    let mut size = ImVec2 { x: 0.0, y: 0.0 };
+    unsafe {
+        sys::igCalcTextSize(
+            &mut size as *mut ImVec2,
+            range.start as *const _,
+            range.end as *const _,
+            false,
+            -1.0,
+        );
+    }
+    size
+}
+
+/// Draw list of the current plot, for drawing on top of it. Only use this between pushing and
+/// popping the plot clip rect, within a plot.
+pub(crate) struct PlotDrawList {
+    raw: *mut sys::ImDrawList,
+}
+
+impl PlotDrawList {
+    /// Returns the draw list of the current plot.
+    pub(crate) fn current() -> Self {
+        Self {
+            raw: unsafe { crate::sys::ImPlot_GetPlotDrawList() },
+        }
+    }
+
+    /// Draw a filled rectangle between the given corners.
+    pub(crate) fn add_rect_filled(&self, min: ImVec2, max: ImVec2, color: ImVec4) {
+        unsafe {
+            sys::ImDrawList_AddRectFilled(self.raw, min, max, color_to_u32(color), 0.0, 0);
+        }
+    }
+
+    /// Draw text with its top left corner at the given position.
+    pub(crate) fn add_text(&self, position: ImVec2, color: ImVec4, text: &str) {
+        let range = text.as_bytes().as_ptr_range();
+        unsafe {
+            sys::ImDrawList_AddText_Vec2(
+                self.raw,
+                position,
+                color_to_u32(color),
+                range.start as *const _,
+                range.end as *const _,
+            );
+        }
+    }
+
+    /// Draw a line between the given points.
+    pub(crate) fn add_line(&self, start: ImVec2, end: ImVec2, color: ImVec4, thickness: f32) {
+        unsafe {
+            sys::ImDrawList_AddLine(self.raw, start, end, color_to_u32(color), thickness);
+        }
+    }
+
+    /// Draw a filled circle, with an automatically chosen number of segments.
+    pub(crate) fn add_circle_filled(&self, center: ImVec2, radius: f32, color: ImVec4) {
+        unsafe {
+            sys::ImDrawList_AddCircleFilled(self.raw, center, radius, color_to_u32(color), 0);
+        }
+    }
+}
+
+/// Converts a color to imgui's packed 32-bit format.
+fn color_to_u32(color: ImVec4) -> u32 {
+    unsafe { sys::igColorConvertFloat4ToU32(color) }
+}
@@ -0,0 +1,329 @@
+//! # Input module
+//!
+//! This module contains types for describing mouse and keyboard input, as used by the
+//! interactive helpers of this crate, and the input map that configures how ImPlot itself
+//! reacts to input.
+use crate::imgui_compat::{key_modifiers_held, MouseButton};
+use crate::{sys, Context};
+use bitflags::bitflags;
+use std::ops::{Deref, DerefMut};
+
+#[rustversion::attr(since(1.48), doc(alias = "ImGuiKeyModFlags"))]
+bitflags! {
+    /// Keyboard modifier keys. These use the same values as imgui's `ImGuiKeyModFlags`.
+    #[repr(transparent)]
+    pub struct KeyModifiers: u32 {
+        /// No modifier keys
+        const NONE = crate::imgui_compat::KEY_MOD_NONE;
+        /// Either of the control keys
+        const CTRL = crate::imgui_compat::KEY_MOD_CTRL;
+        /// Either of the shift keys
+        const SHIFT = crate::imgui_compat::KEY_MOD_SHIFT;
+        /// Either of the alt keys
+        const ALT = crate::imgui_compat::KEY_MOD_ALT;
+        /// Either of the super (Cmd/Windows) keys
+        const SUPER = crate::imgui_compat::KEY_MOD_SUPER;
+    }
+}
+
+impl KeyModifiers {
+    /// Returns the modifier keys that are currently held down, according to imgui.
+    pub fn current() -> Self {
+        let [ctrl, shift, alt, super_key] = key_modifiers_held();
+        let mut modifiers = Self::NONE;
+        modifiers.set(Self::CTRL, ctrl);
+        modifiers.set(Self::SHIFT, shift);
+        modifiers.set(Self::ALT, alt);
+        modifiers.set(Self::SUPER, super_key);
+        modifiers
+    }
+}
+
+/// Returns the mouse button with the given imgui index.
+///
+/// # Panics
+/// Will panic if the index is not one of imgui's mouse buttons.
+fn mouse_button_from_raw(raw: sys::ImGuiMouseButton) -> MouseButton {
+    MouseButton::VARIANTS
+        .iter()
+        .copied()
+        .find(|button| *button as sys::ImGuiMouseButton == raw)
+        .unwrap_or_else(|| panic!("{} is not an imgui mouse button", raw))
+}
+
+/// Returns the modifier flags with the given imgui value, ignoring unknown bits.
+fn key_modifiers_from_raw(raw: sys::ImGuiKeyModFlags) -> KeyModifiers {
+    KeyModifiers::from_bits_truncate(raw as u32)
+}
+
+/// The mouse buttons and modifier keys ImPlot uses for interacting with plots. The defaults pan
+/// with the left mouse button, fit on double clicks with the left mouse button, box select with
+/// the right mouse button, and query with the middle mouse button. Change them for the current
+/// context with [`Context::input_map_mut`]:
+/// ```no_run
+/// # use implot::{Context, KeyModifiers};
+/// # use imgui::MouseButton;
+/// # fn example(context: &mut Context) {
+/// let mut input_map = context.input_map_mut();
+/// // Box select with plain left drags, pan while holding shift
+/// input_map.pan_modifiers = KeyModifiers::SHIFT;
+/// input_map.select_button = MouseButton::Left;
+/// input_map.select_modifiers = KeyModifiers::NONE;
+/// # }
+/// ```
+/// A gesture with modifiers takes priority over one with the same button without modifiers.
+///
+/// In the bundled version of ImPlot, [`InputMap::horizontal_modifiers`] and
+/// [`InputMap::vertical_modifiers`] only apply to box selections and queries, panning always
+/// moves both axes. Panning can be restricted to one axis by dragging on the axis itself
+/// instead of the plot area, or for good by locking the other axis with
+/// [`AxisFlags::LOCK_MIN`](struct.AxisFlags.html#associatedconstant.LOCK_MIN) and
+/// [`AxisFlags::LOCK_MAX`](struct.AxisFlags.html#associatedconstant.LOCK_MAX). A locked axis
+/// is neither panned nor zoomed, so locking only one of the limits of the Y axis keeps zooming
+/// around that limit possible.
+#[rustversion::attr(since(1.48), doc(alias = "ImPlotInputMap"))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct InputMap {
+    /// Button for panning by dragging
+    pub pan_button: MouseButton,
+    /// Modifiers that have to be held for panning and fitting
+    pub pan_modifiers: KeyModifiers,
+    /// Button for fitting the data on double clicks
+    pub fit_button: MouseButton,
+    /// Button for opening the context menu with a click
+    pub context_menu_button: MouseButton,
+    /// Button for box selection by dragging
+    pub select_button: MouseButton,
+    /// Modifiers that have to be held for box selection
+    pub select_modifiers: KeyModifiers,
+    /// Button for canceling a box selection in progress
+    pub select_cancel_button: MouseButton,
+    /// Button for making queries by dragging
+    pub query_button: MouseButton,
+    /// Modifiers that have to be held for making queries
+    pub query_modifiers: KeyModifiers,
+    /// Modifiers that turn the active box selection into a query while held
+    pub query_toggle_modifiers: KeyModifiers,
+    /// Modifiers that expand the active box selection or query horizontally to the edges of the
+    /// plot while held, so it only constrains the Y axis. See the notes on [`InputMap`] for
+    /// panning.
+    pub horizontal_modifiers: KeyModifiers,
+    /// Modifiers that expand the active box selection or query vertically to the edges of the
+    /// plot while held, so it only constrains the X axis. See the notes on [`InputMap`] for
+    /// panning.
+    pub vertical_modifiers: KeyModifiers,
+}
+
+impl InputMap {
+    /// The default input map of ImPlot, for use with a mouse. This sets:
+    /// * Panning by dragging with the left mouse button, and fitting by double clicking it
+    /// * The context menu on right clicks
+    /// * Box selection by dragging with the right mouse button, canceled with the left one
+    /// * Queries by dragging with the middle mouse button, and turning box selections into
+    ///   queries by holding ctrl
+    /// * Expanding selections and queries to the plot edges horizontally by holding alt, and
+    ///   vertically by holding shift
+    pub fn mouse_preset() -> Self {
+        Self {
+            pan_button: MouseButton::Left,
+            pan_modifiers: KeyModifiers::NONE,
+            fit_button: MouseButton::Left,
+            context_menu_button: MouseButton::Right,
+            select_button: MouseButton::Right,
+            select_modifiers: KeyModifiers::NONE,
+            select_cancel_button: MouseButton::Left,
+            query_button: MouseButton::Middle,
+            query_modifiers: KeyModifiers::NONE,
+            query_toggle_modifiers: KeyModifiers::CTRL,
+            horizontal_modifiers: KeyModifiers::ALT,
+            vertical_modifiers: KeyModifiers::SHIFT,
+        }
+    }
+
+    /// An input map for touch screens, where imgui sees a single finger as the left mouse
+    /// button. Compared to [`InputMap::mouse_preset`], this sets:
+    /// * Panning by dragging with one finger, and fitting by double tapping, both without
+    ///   modifiers
+    /// * Box selection and queries on the middle mouse button, without modifiers to turn
+    ///   selections into queries or expand them. They can't be started by touch, so a finger
+    ///   that slips can't start one by accident, but remain usable with a mouse.
+    /// * The context menu stays on right clicks. imgui has no long press gesture, so the
+    ///   menu is only available with a mouse or a pen with a barrel button.
+    ///
+    /// Zooming with the scroll wheel and the hover-dependent features are not part of the
+    /// input map. For the latter, build the plots with
+    /// [`Plot::with_plot_flags`](struct.Plot.html#method.with_plot_flags) and
+    /// `PlotFlags::NO_MOUSE_POSITION | PlotFlags::NO_HIGHLIGHT`, which hides the mouse position
+    /// text and the highlighting of hovered legend entries, as a finger leaves neither in a
+    /// meaningful place. `PlotFlags::NO_MENUS` removes the context menus altogether.
+    /// ```no_run
+    /// # use implot::{Context, InputMap};
+    /// # fn example(context: &mut Context) {
+    /// *context.input_map_mut() = InputMap::touch_preset();
+    /// # }
+    /// ```
+    pub fn touch_preset() -> Self {
+        Self {
+            select_button: MouseButton::Middle,
+            select_cancel_button: MouseButton::Right,
+            query_toggle_modifiers: KeyModifiers::NONE,
+            horizontal_modifiers: KeyModifiers::NONE,
+            vertical_modifiers: KeyModifiers::NONE,
+            ..Self::mouse_preset()
+        }
+    }
+
+    /// Internal helper that converts from the ImPlot representation.
+    fn from_raw(raw: &sys::ImPlotInputMap) -> Self {
+        Self {
+            pan_button: mouse_button_from_raw(raw.PanButton),
+            pan_modifiers: key_modifiers_from_raw(raw.PanMod),
+            fit_button: mouse_button_from_raw(raw.FitButton),
+            context_menu_button: mouse_button_from_raw(raw.ContextMenuButton),
+            select_button: mouse_button_from_raw(raw.BoxSelectButton),
+            select_modifiers: key_modifiers_from_raw(raw.BoxSelectMod),
+            select_cancel_button: mouse_button_from_raw(raw.BoxSelectCancelButton),
+            query_button: mouse_button_from_raw(raw.QueryButton),
+            query_modifiers: key_modifiers_from_raw(raw.QueryMod),
+            query_toggle_modifiers: key_modifiers_from_raw(raw.QueryToggleMod),
+            horizontal_modifiers: key_modifiers_from_raw(raw.HorizontalMod),
+            vertical_modifiers: key_modifiers_from_raw(raw.VerticalMod),
+        }
+    }
+
+    /// Internal helper that converts to the ImPlot representation.
+    fn to_raw(self) -> sys::ImPlotInputMap {
+        sys::ImPlotInputMap {
+            PanButton: self.pan_button as sys::ImGuiMouseButton,
+            PanMod: self.pan_modifiers.bits() as sys::ImGuiKeyModFlags,
+            FitButton: self.fit_button as sys::ImGuiMouseButton,
+            ContextMenuButton: self.context_menu_button as sys::ImGuiMouseButton,
+            BoxSelectButton: self.select_button as sys::ImGuiMouseButton,
+            BoxSelectMod: self.select_modifiers.bits() as sys::ImGuiKeyModFlags,
+            BoxSelectCancelButton: self.select_cancel_button as sys::ImGuiMouseButton,
+            QueryButton: self.query_button as sys::ImGuiMouseButton,
+            QueryMod: self.query_modifiers.bits() as sys::ImGuiKeyModFlags,
+            QueryToggleMod: self.query_toggle_modifiers.bits() as sys::ImGuiKeyModFlags,
+            HorizontalMod: self.horizontal_modifiers.bits() as sys::ImGuiKeyModFlags,
+            VerticalMod: self.vertical_modifiers.bits() as sys::ImGuiKeyModFlags,
+        }
+    }
+}
+
+/// Mutable access to the input map of a context, returned by [`Context::input_map_mut`]. The
+/// changes are written back to the context when this is dropped.
+///
+/// Like the [`Context`] it borrows, this can't be sent to other threads:
+/// ```compile_fail
+/// fn check<T: Send>() {}
+/// check::<implot::InputMapGuard>();
+/// ```
+pub struct InputMapGuard<'a> {
+    context: &'a mut Context,
+    input_map: InputMap,
+}
+
+impl Deref for InputMapGuard<'_> {
+    type Target = InputMap;
+
+    fn deref(&self) -> &InputMap {
+        &self.input_map
+    }
+}
+
+impl DerefMut for InputMapGuard<'_> {
+    fn deref_mut(&mut self) -> &mut InputMap {
+        &mut self.input_map
+    }
+}
+
+impl Drop for InputMapGuard<'_> {
+    fn drop(&mut self) {
+        let raw = self.input_map.to_raw();
+        self.context.with_current(|| unsafe {
+            *raw_input_map() = raw;
+        });
+    }
+}
+
+/// Internal helper that returns the input map of the current context.
+fn raw_input_map() -> *mut sys::ImPlotInputMap {
+    let input_map = unsafe { sys::ImPlot_GetInputMap() };
+    assert!(!input_map.is_null(), "ImPlot returned no input map");
+    input_map
+}
+
+impl Context {
+    /// Returns a copy of the input map of this context, see [`InputMap`].
+    #[rustversion::attr(since(1.48), doc(alias = "GetInputMap"))]
+    pub fn input_map(&self) -> InputMap {
+        self.with_current(|| InputMap::from_raw(unsafe { &*raw_input_map() }))
+    }
+
+    /// Returns mutable access to the input map of this context, see [`InputMap`]. The changes
+    /// take effect when the returned guard is dropped. This takes `&mut self` so it can't be
+    /// called while plots are being built with a [`PlotUi`](struct.PlotUi.html) of this
+    /// context.
+    #[rustversion::attr(since(1.48), doc(alias = "GetInputMap"))]
+    pub fn input_map_mut(&mut self) -> InputMapGuard<'_> {
+        let input_map = self.input_map();
+        InputMapGuard {
+            context: self,
+            input_map,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "imgui-rs")]
+    use crate::test_support::with_test_frame;
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_mouse_preset_is_the_default() {
+        with_test_frame(|_, context| {
+            assert_eq!(context.input_map(), InputMap::mouse_preset());
+        });
+    }
+
+    #[test]
+    fn test_input_map_raw_round_trip() {
+        let input_map = InputMap {
+            pan_button: MouseButton::Left,
+            pan_modifiers: KeyModifiers::SHIFT,
+            fit_button: MouseButton::Left,
+            context_menu_button: MouseButton::Right,
+            select_button: MouseButton::Left,
+            select_modifiers: KeyModifiers::NONE,
+            select_cancel_button: MouseButton::Right,
+            query_button: MouseButton::Left,
+            query_modifiers: KeyModifiers::ALT,
+            query_toggle_modifiers: KeyModifiers::CTRL | KeyModifiers::SUPER,
+            horizontal_modifiers: KeyModifiers::ALT,
+            vertical_modifiers: KeyModifiers::SHIFT,
+        };
+        let raw = input_map.to_raw();
+        assert_eq!(raw.BoxSelectButton, 0);
+        assert_eq!(raw.ContextMenuButton, 1);
+        assert_eq!(InputMap::from_raw(&raw), input_map);
+    }
+
+    #[test]
+    fn test_touch_preset_keeps_gestures_off_the_left_button() {
+        let touch = InputMap::touch_preset();
+        assert_eq!(touch.pan_button, MouseButton::Left);
+        assert_eq!(touch.pan_modifiers, KeyModifiers::NONE);
+        assert_eq!(touch.fit_button, MouseButton::Left);
+        for button in &[
+            touch.select_button,
+            touch.select_cancel_button,
+            touch.query_button,
+            touch.context_menu_button,
+        ] {
+            assert_ne!(*button, MouseButton::Left);
+        }
+        assert_eq!(touch.query_toggle_modifiers, KeyModifiers::NONE);
+    }
+}
@@ -0,0 +1,198 @@
+//! # Legend module
+//!
+//! This module contains helpers for extending the interactions with the plot legend beyond what
+//! ImPlot provides by itself.
+use crate::context::debug_check_thread;
+use crate::imgui_compat::{mouse_clicked, mouse_double_clicked, MouseButton};
+use crate::{current_plot_id, hide_next_item, is_legend_entry_hovered, Condition, PlotStateMap};
+use std::collections::HashMap;
+
+/// Internal state of [`LegendSolo`] for a single plot. This is kept separate from the imgui
+/// input handling so the transitions can be reasoned about (and tested) on their own.
+#[derive(Clone, Default, Debug, PartialEq)]
+struct SoloState {
+    /// Visibility of each item seen so far. Items that are not in here yet are visible.
+    visibility: HashMap<String, bool>,
+    /// Visibility from before the current solo started, if an item is soloed
+    visibility_before_solo: Option<HashMap<String, bool>>,
+    /// The item that is currently soloed, if any
+    soloed_item: Option<String>,
+    /// The state from before the most recent single click, for undoing that click if it turns
+    /// out to be the first half of a double click
+    state_before_click: Option<Box<SoloState>>,
+}
+
+impl SoloState {
+    /// Make sure the given item is known, so it takes part in soloing.
+    fn register(&mut self, label: &str) {
+        if !self.visibility.contains_key(label) {
+            self.visibility.insert(label.to_owned(), true);
+        }
+    }
+
+    /// Returns whether the given item is visible.
+    fn is_visible(&self, label: &str) -> bool {
+        self.visibility.get(label).copied().unwrap_or(true)
+    }
+
+    /// Handle a single click on the legend entry of the given item, which toggles its visibility
+    /// like ImPlot does by itself. This ends any current solo, since the user chose a custom
+    /// visibility.
+    fn click(&mut self, label: &str) {
+        let mut state_before_click = self.clone();
+        state_before_click.state_before_click = None;
+        let visible = self.is_visible(label);
+        self.visibility.insert(label.to_owned(), !visible);
+        self.visibility_before_solo = None;
+        self.soloed_item = None;
+        self.state_before_click = Some(Box::new(state_before_click));
+    }
+
+    /// Handle a double click on the legend entry of the given item. Soloes the item, or restores
+    /// the visibility from before the solo if the item is already soloed.
+    fn double_click(&mut self, label: &str) {
+        // The first click of the double click already toggled the item, undo that
+        if let Some(state_before_click) = self.state_before_click.take() {
+            *self = *state_before_click;
+        }
+        self.register(label);
+
+        if self.soloed_item.as_deref() == Some(label) {
+            if let Some(visibility) = self.visibility_before_solo.take() {
+                self.visibility = visibility;
+            }
+            self.soloed_item = None;
+        } else {
+            // When switching the solo from one item to another, keep the visibility from before
+            // the first solo so that it is what gets restored in the end
+            if self.visibility_before_solo.is_none() {
+                self.visibility_before_solo = Some(self.visibility.clone());
+            }
+            for (item, visible) in self.visibility.iter_mut() {
+                *visible = item == label;
+            }
+            self.soloed_item = Some(label.to_owned());
+        }
+    }
+}
+
+/// Plotly-style legend interaction: double-clicking the legend entry of an item hides all other
+/// items ("solo"), and double-clicking it again restores the visibility from before. Single
+/// clicks toggle the visibility of items like they do without this helper.
+///
+/// To take over the visibility of the items, this helper has to be told about each item before
+/// it is plotted, by calling [`LegendSolo::item`] with the label of the item. This then hides
+/// the item with [`hide_next_item`](fn.hide_next_item.html) if necessary. The state is kept
+/// per plot, so one helper can be used for several plots:
+/// ```no_run
+/// # use implot::{LegendSolo, Plot, PlotLine, PlotUi};
+/// # fn example(plot_ui: &PlotUi, legend_solo: &mut LegendSolo, x: &[f64], y: &[f64]) {
+/// // Created once, for example in the application state:
+/// // let legend_solo = LegendSolo::new();
+/// Plot::new("Plot with solo").build(plot_ui, || {
+///     legend_solo.item("first");
+///     PlotLine::new("first").plot(x, y);
+///     legend_solo.item("second");
+///     PlotLine::new("second").plot(x, y);
+/// });
+/// # }
+/// ```
+#[derive(Default)]
+pub struct LegendSolo {
+    /// Solo state of each plot the helper is used in
+    states: PlotStateMap<SoloState>,
+}
+
+impl LegendSolo {
+    /// Create a new legend solo helper.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handle clicks on the legend entry of the item with the given label and set its visibility
+    /// for the item that is plotted next. Use this right before plotting the item, in the
+    /// closure passed to [`Plot::build()`](struct.Plot.html#method.build). Outside of plots,
+    /// this does nothing.
+    pub fn item(&mut self, label: &str) {
+        debug_check_thread();
+        let plot_id = match current_plot_id() {
+            Some(plot_id) => plot_id,
+            None => return,
+        };
+        let state = self.states.entry(&plot_id);
+        state.register(label);
+
+        if is_legend_entry_hovered(label) {
+            if mouse_double_clicked(MouseButton::Left) {
+                state.double_click(label);
+            } else if mouse_clicked(MouseButton::Left) {
+                state.click(label);
+            }
+        }
+        hide_next_item(!state.is_visible(label), Condition::Always);
+    }
+
+    /// Returns the label of the item that is currently soloed in the plot with the given ID, if
+    /// any.
+    pub fn soloed_item(&self, plot_id: &str) -> Option<&str> {
+        self.states.get(plot_id)?.soloed_item.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn visible_items(state: &SoloState) -> Vec<&str> {
+        let mut items = state
+            .visibility
+            .iter()
+            .filter(|(_, visible)| **visible)
+            .map(|(item, _)| item.as_str())
+            .collect::<Vec<_>>();
+        items.sort_unstable();
+        items
+    }
+
+    #[test]
+    fn test_solo_restores_previous_visibility() {
+        let mut state = SoloState::default();
+        for label in &["a", "b", "c"] {
+            state.register(label);
+        }
+        state.click("a");
+        assert_eq!(visible_items(&state), vec!["b", "c"]);
+
+        // A double click arrives as a click followed by a double click
+        state.click("b");
+        state.double_click("b");
+        assert_eq!(visible_items(&state), vec!["b"]);
+        assert_eq!(state.soloed_item.as_deref(), Some("b"));
+
+        // Soloing another item keeps the visibility from before the first solo
+        state.click("c");
+        state.double_click("c");
+        assert_eq!(visible_items(&state), vec!["c"]);
+
+        state.click("c");
+        state.double_click("c");
+        assert_eq!(visible_items(&state), vec!["b", "c"]);
+        assert_eq!(state.soloed_item, None);
+    }
+
+    #[test]
+    fn test_single_click_ends_solo() {
+        let mut state = SoloState::default();
+        for label in &["a", "b"] {
+            state.register(label);
+        }
+        state.click("a");
+        state.double_click("a");
+        assert_eq!(visible_items(&state), vec!["a"]);
+
+        state.click("b");
+        assert_eq!(visible_items(&state), vec!["a", "b"]);
+        assert_eq!(state.soloed_item, None);
+        assert_eq!(state.visibility_before_solo, None);
+    }
+}
@@ -16,21 +16,88 @@
 //! the C++ name - some doc aliases are defined to increase the chances of that working. If this
 //! does not yield any results, you can also try cloning the source and doing a full-text search to
 //! see if the feature is used somewhere internally the code.
+//!
+//! ## Threading
+//! ImPlot and imgui keep their state in globals that can only be used from one thread. The types
+//! that give access to that state, which are the [`Context`], the [`PlotUi`] and everything
+//! borrowing it (the style and colormap tokens, [`PlotToken`], [`PlotScope`],
+//! [`InputMapGuard`] and [`BorrowedContext`]), are therefore neither `Send` nor `Sync`. Plain
+//! data types, such as the plot elements, [`Style`], [`InputMap`], [`PlotLimits`] and the state
+//! of helpers like [`LegendSolo`], are `Send` and `Sync`, so they can be prepared on other
+//! threads and used on the UI thread. [`Plot`] is not, because plots with linked axes share
+//! their limits through an `Rc`. The free functions can be called on any thread
+//! as far as the compiler is concerned; in debug builds, they panic when called on another
+//! thread than the one the current context was created on.
+// `const` initializers for `thread_local!` need Rust 1.59, which is newer than the minimum
+// version in the README.
+#![allow(clippy::missing_const_for_thread_local)]
 use implot_sys as sys;
 
 // TODO(4bb4) facade-wrap these?
-pub use self::{context::*, plot::*, plot_elements::*};
-use std::os::raw::c_char;
-pub use sys::{ImPlotLimits, ImPlotPoint, ImPlotRange, ImVec2, ImVec4};
+#[cfg(all(feature = "demo", feature = "imgui-rs"))]
+pub use self::demo::*;
+pub use self::{
+    color::*, colormap::*, context::*, coordinates::*, figure::*, frame_stats::*, histogram::*,
+    input::*, legend::*, mirrored_axis::*, overlays::*, plot::*, plot_elements::*, plot_events::*,
+    plot_scope::*, plot_state::*, selection::*, stack_check::*, style::*, style_builder::*,
+    ticks::*,
+};
+#[cfg(feature = "serde")]
+pub use self::{style_serde::*, sys_serde::*};
+use context::debug_check_thread;
+use plot::{check_inside_plot, is_inside_plot};
+use stack_check::{new_push_id, pop_from_stacks, record_pushes, take_pushes, PushId, StackKind};
+use std::convert::TryFrom;
+use std::ffi::CString;
+use std::fmt;
+use std::marker::PhantomData;
+use std::str::FromStr;
+pub use sys::{ImPlotLimits, ImPlotPoint, ImPlotRange, ImVec2, ImVec4, IMPLOT_VERSION};
 
+mod color;
+mod colormap;
 mod context;
+mod coordinates;
+#[cfg(all(feature = "demo", feature = "imgui-rs"))]
+mod demo;
+mod figure;
+mod frame_stats;
+mod histogram;
+mod imgui_compat;
+mod input;
+mod legend;
+mod mirrored_axis;
+mod overlays;
 mod plot;
 mod plot_elements;
+mod plot_events;
+mod plot_scope;
+mod plot_state;
+pub mod quick;
+mod selection;
+mod stack_check;
+mod style;
+mod style_builder;
+#[cfg(feature = "serde")]
+mod style_serde;
+#[cfg(feature = "serde")]
+mod sys_serde;
+#[cfg(all(test, feature = "imgui-rs"))]
+mod test_support;
+mod ticks;
 
 // The bindings for some reason don't contain this - it has to match the IMPLOT_AUTO from
 // the original C++ header for things to work properly.
 const IMPLOT_AUTO: i32 = -1;
 
+// Same for IMPLOT_AUTO_COL, which is used where a color can be picked automatically.
+const IMPLOT_AUTO_COL: ImVec4 = ImVec4 {
+    x: 0.0,
+    y: 0.0,
+    z: 0.0,
+    w: -1.0,
+};
+
 // Number of Y axes, this is used in a bunch of places for storing things like settings.
 // If this changes, also change the YAxisChoice enum.
 const NUMBER_OF_Y_AXES: usize = 3;
@@ -42,7 +109,7 @@ const NUMBER_OF_Y_AXES: usize = 3;
 // so we can store data about individual axes in arrays, so this pretty much should stay
 // just a mapping of words to numbers.
 #[rustversion::attr(since(1.48), doc(alias = "ImPlotYAxis"))]
-#[derive(Clone)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(u32)]
 pub enum YAxisChoice {
     First = sys::ImPlotYAxis__ImPlotYAxis_1,
@@ -50,6 +117,59 @@ pub enum YAxisChoice {
     Third = sys::ImPlotYAxis__ImPlotYAxis_3,
 }
 
+impl YAxisChoice {
+    /// All Y axes, in order.
+    pub const ALL: [YAxisChoice; NUMBER_OF_Y_AXES] =
+        [YAxisChoice::First, YAxisChoice::Second, YAxisChoice::Third];
+
+    /// Returns all Y axes, for code that does something for every axis. This is
+    /// [`YAxisChoice::ALL`] as a slice.
+    /// ```
+    /// # use implot::YAxisChoice;
+    /// for axis in YAxisChoice::all() {
+    ///     println!("{:?} has index {}", axis, axis.index());
+    /// }
+    /// ```
+    pub fn all() -> &'static [YAxisChoice] {
+        &YAxisChoice::ALL
+    }
+
+    /// Returns the index of the axis, from 0 for the first Y axis to 2 for the third. Use this
+    /// to store per-axis settings in arrays.
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+impl TryFrom<i32> for YAxisChoice {
+    type Error = InvalidYAxisError;
+
+    /// Convert an ImPlot Y axis index into a choice, failing for indices that don't refer to
+    /// an axis - including `IMPLOT_AUTO`, which is `None` in this crate.
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        YAxisChoice::ALL
+            .iter()
+            .copied()
+            .find(|axis| *axis as i32 == value)
+            .ok_or(InvalidYAxisError { value })
+    }
+}
+
+/// Error for converting an integer that does not refer to any Y axis into a [`YAxisChoice`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvalidYAxisError {
+    /// The integer that was given
+    pub value: i32,
+}
+
+impl fmt::Display for InvalidYAxisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is not the index of a Y axis", self.value)
+    }
+}
+
+impl std::error::Error for InvalidYAxisError {}
+
 /// Turn an Option<YAxisChoice> into an i32. Picks IMPLOT_AUTO for None.
 #[rustversion::attr(since(1.48), doc(alias = "IMPLOT_AUTO"))]
 fn y_axis_choice_option_to_i32(y_axis_choice: Option<YAxisChoice>) -> i32 {
@@ -62,6 +182,31 @@ fn y_axis_choice_option_to_i32(y_axis_choice: Option<YAxisChoice>) -> i32 {
 /// A temporary reference for building plots. This does not really do anything on its own at
 /// this point, but it is used to enforce that a context is created and active for other features,
 /// such as creating plots.
+///
+/// ImPlot and imgui can only be used from a single thread, so neither the [`Context`] nor the
+/// `PlotUi` or the tokens borrowing it can be sent to or shared with other threads:
+/// ```compile_fail
+/// fn check<T: Send>() {}
+/// check::<implot::Context>();
+/// ```
+/// ```compile_fail
+/// fn check<T: Sync>() {}
+/// check::<implot::Context>();
+/// ```
+/// ```compile_fail
+/// fn check<T: Send>() {}
+/// check::<implot::PlotUi>();
+/// ```
+/// ```compile_fail
+/// fn check<T: Sync>() {}
+/// check::<implot::PlotUi>();
+/// ```
+/// ```compile_fail
+/// fn check<T: Send>() {}
+/// check::<implot::StyleColorToken>();
+/// ```
+/// The free functions of this crate can't be restricted like that. In debug builds, they panic
+/// when they are called on another thread than the one the current context was created on.
 pub struct PlotUi<'ui> {
     context: &'ui Context,
 }
@@ -96,6 +241,85 @@ pub enum Marker {
     Asterisk = sys::ImPlotMarker__ImPlotMarker_Asterisk,
 }
 
+impl Marker {
+    /// All the available markers, in the order ImPlot defines them in.
+    pub const ALL: [Marker; 11] = [
+        Marker::None,
+        Marker::Circle,
+        Marker::Square,
+        Marker::Diamond,
+        Marker::Up,
+        Marker::Down,
+        Marker::Left,
+        Marker::Right,
+        Marker::Cross,
+        Marker::Plus,
+        Marker::Asterisk,
+    ];
+
+    /// Returns all the available markers, for example to offer them in a settings UI. This is
+    /// [`Marker::ALL`] as a slice.
+    pub fn all() -> &'static [Marker] {
+        &Marker::ALL
+    }
+
+    /// Returns the name ImPlot uses for this marker, such as `"Circle"`.
+    #[rustversion::attr(since(1.48), doc(alias = "GetMarkerName"))]
+    pub fn name(self) -> &'static str {
+        match self {
+            Marker::None => "None",
+            Marker::Circle => "Circle",
+            Marker::Square => "Square",
+            Marker::Diamond => "Diamond",
+            Marker::Up => "Up",
+            Marker::Down => "Down",
+            Marker::Left => "Left",
+            Marker::Right => "Right",
+            Marker::Cross => "Cross",
+            Marker::Plus => "Plus",
+            Marker::Asterisk => "Asterisk",
+        }
+    }
+
+    /// Returns the marker with the given name, or `None` if there is none. The comparison is
+    /// case-sensitive.
+    pub fn from_name(name: &str) -> Option<Marker> {
+        Marker::ALL
+            .iter()
+            .copied()
+            .find(|marker| marker.name() == name)
+    }
+}
+
+impl FromStr for Marker {
+    type Err = UnknownNameError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Marker::from_name(name).ok_or_else(|| UnknownNameError {
+            kind: "marker",
+            name: name.to_owned(),
+        })
+    }
+}
+
+/// Error for parsing a [`Marker`] or [`Colormap`] from a name that does not belong to any of
+/// its variants.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnknownNameError {
+    /// What the name was supposed to refer to, such as `"marker"`
+    pub kind: &'static str,
+    /// The name that is not known
+    pub name: String,
+}
+
+impl fmt::Display for UnknownNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\" is not the name of a {}", self.name, self.kind)
+    }
+}
+
+impl std::error::Error for UnknownNameError {}
+
 /// Colorable plot elements. These are called "ImPlotCol" in ImPlot itself, but I found that
 /// name somewhat confusing because we are not referring to colors, but _which_ thing can
 /// be colored - hence I added the "Element".
@@ -244,6 +468,103 @@ pub enum StyleVar {
     PlotMinSize = sys::ImPlotStyleVar__ImPlotStyleVar_PlotMinSize,
 }
 
+/// A style variable together with a value of the type that the variable has, for
+/// [`push_style_var`]. This makes it impossible to push a value of the wrong type for a
+/// variable, which ImPlot would not notice.
+#[derive(Copy, Clone, Debug)]
+pub enum StyleVarValue {
+    /// Line weight in pixels
+    LineWeight(f32),
+    /// Marker specification
+    Marker(Marker),
+    /// Marker size in pixels (roughly the marker's "radius")
+    MarkerSize(f32),
+    /// Outline weight of markers in pixels
+    MarkerWeight(f32),
+    /// Alpha modifier applied to all plot item fills
+    FillAlpha(f32),
+    /// Error bar whisker width in pixels
+    ErrorBarSize(f32),
+    /// Error bar whisker weight in pixels
+    ErrorBarWeight(f32),
+    /// Digital channels bit height (at 1) in pixels
+    DigitalBitHeight(f32),
+    /// Digital channels bit padding gap in pixels
+    DigitalBitGap(f32),
+    /// Thickness of border around plot area
+    PlotBorderSize(f32),
+    /// Alpha multiplier applied to minor axis grid lines
+    MinorAlpha(f32),
+    /// Major tick lengths for X and Y axes
+    MajorTickLen(ImVec2),
+    /// Minor tick lengths for X and Y axes
+    MinorTickLen(ImVec2),
+    /// Line thickness of major ticks
+    MajorTickSize(ImVec2),
+    /// Line thickness of minor ticks
+    MinorTickSize(ImVec2),
+    /// Line thickness of major grid lines
+    MajorGridSize(ImVec2),
+    /// Line thickness of minor grid lines
+    MinorGridSize(ImVec2),
+    /// Padding between widget frame and plot area and/or labels
+    PlotPadding(ImVec2),
+    /// Padding between axes labels, tick labels, and plot edge
+    LabelPadding(ImVec2),
+    /// Legend padding from top-left of plot
+    LegendPadding(ImVec2),
+    /// Legend inner padding from legend edges
+    LegendInnerPadding(ImVec2),
+    /// Spacing between legend entries
+    LegendSpacing(ImVec2),
+    /// Padding between plot edge and interior info text
+    MousePosPadding(ImVec2),
+    /// Text padding around annotation labels
+    AnnotationPadding(ImVec2),
+    /// Additional fit padding as a percentage of the fit extents
+    /// (e.g. ImVec2(0.1f,0.1f) adds 10% to the fit extents of X and Y)
+    FitPadding(ImVec2),
+    /// Default size used when ImVec2(0,0) is passed to BeginPlot
+    PlotDefaultSize(ImVec2),
+    /// Minimum size plot frame can be when shrunk
+    PlotMinSize(ImVec2),
+}
+
+impl StyleVarValue {
+    /// Returns the style variable this value is for.
+    pub fn style_var(&self) -> StyleVar {
+        match self {
+            StyleVarValue::LineWeight(_) => StyleVar::LineWeight,
+            StyleVarValue::Marker(_) => StyleVar::Marker,
+            StyleVarValue::MarkerSize(_) => StyleVar::MarkerSize,
+            StyleVarValue::MarkerWeight(_) => StyleVar::MarkerWeight,
+            StyleVarValue::FillAlpha(_) => StyleVar::FillAlpha,
+            StyleVarValue::ErrorBarSize(_) => StyleVar::ErrorBarSize,
+            StyleVarValue::ErrorBarWeight(_) => StyleVar::ErrorBarWeight,
+            StyleVarValue::DigitalBitHeight(_) => StyleVar::DigitalBitHeight,
+            StyleVarValue::DigitalBitGap(_) => StyleVar::DigitalBitGap,
+            StyleVarValue::PlotBorderSize(_) => StyleVar::PlotBorderSize,
+            StyleVarValue::MinorAlpha(_) => StyleVar::MinorAlpha,
+            StyleVarValue::MajorTickLen(_) => StyleVar::MajorTickLen,
+            StyleVarValue::MinorTickLen(_) => StyleVar::MinorTickLen,
+            StyleVarValue::MajorTickSize(_) => StyleVar::MajorTickSize,
+            StyleVarValue::MinorTickSize(_) => StyleVar::MinorTickSize,
+            StyleVarValue::MajorGridSize(_) => StyleVar::MajorGridSize,
+            StyleVarValue::MinorGridSize(_) => StyleVar::MinorGridSize,
+            StyleVarValue::PlotPadding(_) => StyleVar::PlotPadding,
+            StyleVarValue::LabelPadding(_) => StyleVar::LabelPadding,
+            StyleVarValue::LegendPadding(_) => StyleVar::LegendPadding,
+            StyleVarValue::LegendInnerPadding(_) => StyleVar::LegendInnerPadding,
+            StyleVarValue::LegendSpacing(_) => StyleVar::LegendSpacing,
+            StyleVarValue::MousePosPadding(_) => StyleVar::MousePosPadding,
+            StyleVarValue::AnnotationPadding(_) => StyleVar::AnnotationPadding,
+            StyleVarValue::FitPadding(_) => StyleVar::FitPadding,
+            StyleVarValue::PlotDefaultSize(_) => StyleVar::PlotDefaultSize,
+            StyleVarValue::PlotMinSize(_) => StyleVar::PlotMinSize,
+        }
+    }
+}
+
 /// Used to position items on a plot (e.g. legends, labels, etc.)
 #[rustversion::attr(since(1.48), doc(alias = "ImPlotLocation"))]
 #[repr(u32)]
@@ -282,177 +603,895 @@ pub enum PlotOrientation {
 /// linearly resampled.
 #[rustversion::attr(since(1.48), doc(alias = "SetColormap"))]
 pub fn set_colormap_from_preset(preset: Colormap, samples: u32) {
+    debug_check_thread();
     unsafe {
         // "as" casts saturate as of Rust 1.45. This is safe here, and at least the enum
         // values are not expected to go outside the range of an i32 anyway, so there is no
         // risk of changed values.
         sys::ImPlot_SetColormapPlotColormap(preset as i32, samples as i32);
     }
+    colormap::release_stored_colormap();
 }
 
-/// Set a custom colormap in the form of a vector of colors.
+/// Set a custom colormap in the form of a vector of colors. See [`set_colormap_from_slice`] for
+/// details, this is a variant of it that panics instead of returning an error.
+///
+/// # Panics
+/// Will panic if there are fewer than two colors.
 #[rustversion::attr(since(1.48), doc(alias = "SetColormap"))]
 pub fn set_colormap_from_vec(colors: Vec<ImVec4>) {
+    debug_check_thread();
+    if let Err(error) = set_colormap_from_slice(&colors) {
+        panic!("Could not set colormap: {}", error);
+    }
+}
+
+/// Set a custom colormap in the form of a slice of colors. ImPlot needs at least two colors, for
+/// fewer colors this returns an error and leaves the colormap unchanged.
+///
+/// ImPlot refers to the colors of custom colormaps instead of copying them, so they are copied
+/// into storage that is kept while ImPlot can still use them: until another colormap is set, and
+/// as long as colormaps pushed on top of them have not been popped. Setting the same colors
+/// repeatedly reuses that storage. Colormaps pushed on top of them outside of plots and never
+/// popped keep the colors stored for the rest of the program.
+#[rustversion::attr(since(1.48), doc(alias = "SetColormap"))]
+pub fn set_colormap_from_slice(colors: &[ImVec4]) -> Result<(), ColormapError> {
+    debug_check_thread();
+    if colors.len() < 2 {
+        return Err(ColormapError::TooFewColors(colors.len()));
+    }
+    colormap::set_stored_colormap(colors);
+    Ok(())
+}
+
+/// Like [`set_colormap_from_slice`], but takes the colors as `[red, green, blue, alpha]` arrays,
+/// which is how palettes are usually written down in Rust code.
+#[rustversion::attr(since(1.48), doc(alias = "SetColormap"))]
+pub fn set_colormap_from_f32_arrays(colors: &[[f32; 4]]) -> Result<(), ColormapError> {
+    debug_check_thread();
+    // ImVec4 is a repr(C) struct of four f32 values, so it has the same layout as [f32; 4]
+    let colors =
+        unsafe { std::slice::from_raw_parts(colors.as_ptr() as *const ImVec4, colors.len()) };
+    set_colormap_from_slice(colors)
+}
+
+/// Returns the number of colors in the current colormap.
+#[rustversion::attr(since(1.48), doc(alias = "GetColormapSize"))]
+pub fn get_colormap_size() -> usize {
+    debug_check_thread();
+    unsafe { sys::ImPlot_GetColormapSize() as usize }
+}
+
+/// Returns the color with the given index in the current colormap. Indices past the end of the
+/// colormap wrap around, the same way ImPlot does it, so index `i` gives the same color as index
+/// `i % get_colormap_size()`. This is useful for giving series stable colors that don't change
+/// when other series are added or hidden, unlike the automatic colors that depend on the order
+/// in which items are plotted.
+#[rustversion::attr(since(1.48), doc(alias = "GetColormapColor"))]
+pub fn get_colormap_color(index: usize) -> ImVec4 {
+    debug_check_thread();
+    let mut color = ImVec4 {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+        w: 0.0,
+    };
+    // Wrap here already so large indices don't overflow the C int
+    let index = index % get_colormap_size().max(1);
+    unsafe {
+        sys::ImPlot_GetColormapColor(&mut color as *mut ImVec4, index as i32);
+    }
+    color
+}
+
+/// Returns the color of the current colormap at the given position, where 0.0 is the start and
+/// 1.0 the end of the colormap. Positions in between are linearly interpolated between the
+/// colors of the colormap, positions outside of `[0, 1]` are clamped to that range. This is
+/// useful for coloring custom-drawn things consistently with heatmaps.
+#[rustversion::attr(since(1.48), doc(alias = "LerpColormap"))]
+pub fn lerp_colormap(t: f32) -> ImVec4 {
+    debug_check_thread();
+    let mut color = ImVec4 {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+        w: 0.0,
+    };
     unsafe {
-        sys::ImPlot_SetColormapVec4Ptr(colors.as_ptr(), colors.len() as i32);
+        sys::ImPlot_LerpColormap(&mut color as *mut ImVec4, t.clamp(0.0, 1.0));
+    }
+    color
+}
+
+/// Fills `out` with evenly spaced samples of the current colormap, from its start to its end,
+/// using [`lerp_colormap`]. The first and last sample are exactly the first and last color of the
+/// colormap, a single sample is the first color.
+#[rustversion::attr(since(1.48), doc(alias = "LerpColormap"))]
+pub fn lerp_colormap_samples(out: &mut [ImVec4]) {
+    debug_check_thread();
+    let last_index = out.len().saturating_sub(1).max(1) as f32;
+    for (index, color) in out.iter_mut().enumerate() {
+        *color = lerp_colormap(index as f32 / last_index);
+    }
+}
+
+/// Returns the next color of the current colormap, which is the color ImPlot would give to the
+/// next item plotted without an explicit color. Use this in closures passed to
+/// [`Plot::build()`](struct.Plot.html#method.build), for example to color custom-drawn things
+/// consistently with automatically colored items.
+///
+/// Note that this advances ImPlot's color rotation for the current plot, the same way plotting an
+/// item does. Items plotted after calling this get the colors after the returned one.
+#[rustversion::attr(since(1.48), doc(alias = "NextColormapColor"))]
+pub fn next_colormap_color() -> ImVec4 {
+    debug_check_thread();
+    let mut color = ImVec4 {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+        w: 0.0,
+    };
+    unsafe {
+        sys::ImPlot_NextColormapColor(&mut color as *mut ImVec4);
+    }
+    color
+}
+
+/// Show a vertical color scale for the current colormap, labeled with values from `scale_min` at
+/// the bottom to `scale_max` at the top, with the given height in pixels. This is an imgui widget
+/// to be used outside of plots, for example next to a heatmap plotted with the same scale:
+/// ```no_run
+/// # use implot::{show_colormap_scale, Plot, PlotHeatmap, PlotUi};
+/// # fn example(ui: &imgui::Ui, plot_ui: &PlotUi, values: &[f64]) {
+/// Plot::new("Heatmap").size([300.0, 300.0]).build(plot_ui, || {
+///     PlotHeatmap::new("values").with_scale(0.0, 10.0).plot(values, 10, 10);
+/// });
+/// ui.same_line();
+/// show_colormap_scale(0.0, 10.0, 300.0);
+/// # }
+/// ```
+#[rustversion::attr(since(1.48), doc(alias = "ShowColormapScale"))]
+pub fn show_colormap_scale(scale_min: f64, scale_max: f64, height: f32) {
+    debug_check_thread();
+    unsafe {
+        sys::ImPlot_ShowColormapScale(scale_min, scale_max, height);
     }
 }
 
 // --- Push/pop utils -------------------------------------------------------------------------
-// Currently not in a struct yet. imgui-rs has some smarts about dealing with stacks, in particular
-// leak detection, which I'd like to replicate here at some point.
-/// Push a style color to the stack, giving an element and the four components of the color.
-/// The components should be between 0.0 (no intensity) and 1.0 (full intensity).
+// Currently not in a struct yet. Pushes made in a plot that are never popped are popped at the
+// end of the plot, and reported in debug builds, see the stack_check module.
+/// Push a style color to the stack, giving an element and the color. The color can be anything
+/// that converts into an `ImVec4`, such as `[f32; 4]` or `(f32, f32, f32, f32)` with the red,
+/// green, blue and alpha components between 0.0 (no intensity) and 1.0 (full intensity). Packed
+/// `u32` colors can be converted with [`ImVec4Ext::from_rgba_u32`] first.
 /// The return value is a token that gets used for removing the style color from the stack again:
 /// ```no_run
-/// # use implot::{push_style_color, PlotColorElement};
-/// let pushed_var = push_style_color(&PlotColorElement::Line, 1.0, 1.0, 1.0, 0.2);
+/// # use implot::{push_style_color, PlotColorElement, PlotUi};
+/// # fn example(plot_ui: &PlotUi) {
+/// let pushed_var = push_style_color(plot_ui, PlotColorElement::Line, [1.0, 1.0, 1.0, 0.2]);
 /// // Plot some things
 /// pushed_var.pop();
+/// # }
 /// ```
 #[rustversion::attr(since(1.48), doc(alias = "PushStyleColor"))]
-pub fn push_style_color(
-    element: &PlotColorElement,
-    red: f32,
-    green: f32,
-    blue: f32,
-    alpha: f32,
-) -> StyleColorToken {
+#[track_caller]
+pub fn push_style_color<'ui>(
+    plot_ui: &'ui PlotUi,
+    element: PlotColorElement,
+    color: impl Into<ImVec4>,
+) -> StyleColorToken<'ui> {
     unsafe {
-        sys::ImPlot_PushStyleColorVec4(
-            *element as sys::ImPlotCol,
-            sys::ImVec4 {
-                x: red,
-                y: green,
-                z: blue,
-                w: alpha,
-            },
-        );
+        sys::ImPlot_PushStyleColorVec4(element as sys::ImPlotCol, color.into());
     }
-    StyleColorToken { was_popped: false }
+    let push_id = new_push_id();
+    record_pushes(push_id, StackKind::StyleColor, 1);
+    StyleColorToken::new(plot_ui, push_id)
 }
 
-/// Tracks a change pushed to the style color stack
-pub struct StyleColorToken {
+/// Tracks a change pushed to the style color stack. The change is undone when the token is
+/// popped, or when it is dropped without being popped, for example during a panic.
+///
+/// Like all style and colormap tokens, this borrows the [`PlotUi`] it was pushed with, so it
+/// can't be kept around for popping in a later frame:
+/// ```compile_fail
+/// # use implot::{push_style_color, Context, PlotColorElement};
+/// # fn example(context: &Context, ui: &imgui::Ui) {
+/// let token = {
+///     let plot_ui = context.get_plot_ui(ui);
+///     push_style_color(&plot_ui, PlotColorElement::Line, [1.0, 0.0, 0.0, 1.0])
+/// };
+/// # }
+/// ```
+#[must_use = "the style color is popped again when the token is dropped"]
+pub struct StyleColorToken<'ui> {
+    /// Identifies the push of this token
+    push_id: PushId,
     /// Whether this token has been popped or not.
     was_popped: bool,
+    /// Ties the token to the borrow of the `PlotUi` it was pushed with, so it can't be kept
+    /// beyond the frame or sent to another thread.
+    plot_ui: PhantomData<&'ui PlotUi<'ui>>,
 }
 
-impl StyleColorToken {
+impl<'ui> StyleColorToken<'ui> {
+    /// Internal helper that creates the token for a push made with the given `PlotUi`.
+    fn new(_plot_ui: &'ui PlotUi, push_id: PushId) -> Self {
+        Self {
+            push_id,
+            was_popped: false,
+            plot_ui: PhantomData,
+        }
+    }
+
     #[rustversion::attr(since(1.48), doc(alias = "PopStyleColor"))]
     pub fn pop(mut self) {
-        if self.was_popped {
-            panic!("Attempted to pop a style color token twice.")
-        }
-        self.was_popped = true;
-        unsafe {
-            sys::ImPlot_PopStyleColor(1);
+        self.pop_if_needed();
+    }
+
+    /// Internal helper that pops the token unless it has been popped before.
+    fn pop_if_needed(&mut self) {
+        if !self.was_popped {
+            self.was_popped = true;
+            pop_from_stacks(take_pushes(self.push_id));
         }
     }
 }
 
+impl Drop for StyleColorToken<'_> {
+    fn drop(&mut self) {
+        self.pop_if_needed();
+    }
+}
+
+/// Push a style variable to the stack. The value determines both the variable and its new
+/// value, so the value always has the right type for the variable. The returned token is used
+/// for removing the variable from the stack again:
+/// ```no_run
+/// # use implot::{push_style_var, PlotUi, StyleVarValue};
+/// # fn example(plot_ui: &PlotUi) {
+/// let pushed_var = push_style_var(plot_ui, StyleVarValue::LineWeight(11.0));
+/// // Plot some things
+/// pushed_var.pop();
+/// # }
+/// ```
+#[rustversion::attr(since(1.48), doc(alias = "PushStyleVar"))]
+#[track_caller]
+pub fn push_style_var<'ui>(plot_ui: &'ui PlotUi, value: StyleVarValue) -> StyleVarToken<'ui> {
+    push_style_var_value(value);
+    let push_id = new_push_id();
+    record_pushes(push_id, StackKind::StyleVar, 1);
+    StyleVarToken::new(plot_ui, push_id)
+}
+
+/// Internal helper that pushes a style variable without creating a token for it.
+fn push_style_var_value(value: StyleVarValue) {
+    let element = value.style_var() as sys::ImPlotStyleVar;
+    match value {
+        StyleVarValue::Marker(marker) => unsafe {
+            sys::ImPlot_PushStyleVarInt(element, marker as i32);
+        },
+        StyleVarValue::LineWeight(value)
+        | StyleVarValue::MarkerSize(value)
+        | StyleVarValue::MarkerWeight(value)
+        | StyleVarValue::FillAlpha(value)
+        | StyleVarValue::ErrorBarSize(value)
+        | StyleVarValue::ErrorBarWeight(value)
+        | StyleVarValue::DigitalBitHeight(value)
+        | StyleVarValue::DigitalBitGap(value)
+        | StyleVarValue::PlotBorderSize(value)
+        | StyleVarValue::MinorAlpha(value) => unsafe {
+            sys::ImPlot_PushStyleVarFloat(element, value);
+        },
+        StyleVarValue::MajorTickLen(value)
+        | StyleVarValue::MinorTickLen(value)
+        | StyleVarValue::MajorTickSize(value)
+        | StyleVarValue::MinorTickSize(value)
+        | StyleVarValue::MajorGridSize(value)
+        | StyleVarValue::MinorGridSize(value)
+        | StyleVarValue::PlotPadding(value)
+        | StyleVarValue::LabelPadding(value)
+        | StyleVarValue::LegendPadding(value)
+        | StyleVarValue::LegendInnerPadding(value)
+        | StyleVarValue::LegendSpacing(value)
+        | StyleVarValue::MousePosPadding(value)
+        | StyleVarValue::AnnotationPadding(value)
+        | StyleVarValue::FitPadding(value)
+        | StyleVarValue::PlotDefaultSize(value)
+        | StyleVarValue::PlotMinSize(value) => unsafe {
+            sys::ImPlot_PushStyleVarVec2(element, value);
+        },
+    }
+}
+
 /// Push a f32 style variable to the stack. The returned token is used for removing
 /// the variable from the stack again:
 /// ```no_run
-/// # use implot::{push_style_var_f32, StyleVar};
-/// let pushed_var = push_style_var_f32(&StyleVar::LineWeight, 11.0);
+/// # use implot::{push_style_var_f32, PlotUi, StyleVar};
+/// # fn example(plot_ui: &PlotUi) {
+/// let pushed_var = push_style_var_f32(plot_ui, &StyleVar::LineWeight, 11.0);
 /// // Plot some things
 /// pushed_var.pop();
+/// # }
 /// ```
 #[rustversion::attr(since(1.48), doc(alias = "PushStyleVar"))]
-pub fn push_style_var_f32(element: &StyleVar, value: f32) -> StyleVarToken {
+#[deprecated(
+    since = "0.7.0",
+    note = "use push_style_var, which makes sure the value has the right type"
+)]
+#[track_caller]
+pub fn push_style_var_f32<'ui>(
+    plot_ui: &'ui PlotUi,
+    element: &StyleVar,
+    value: f32,
+) -> StyleVarToken<'ui> {
     unsafe {
         sys::ImPlot_PushStyleVarFloat(*element as sys::ImPlotStyleVar, value);
     }
-    StyleVarToken { was_popped: false }
+    let push_id = new_push_id();
+    record_pushes(push_id, StackKind::StyleVar, 1);
+    StyleVarToken::new(plot_ui, push_id)
 }
 
 /// Push an u32 style variable to the stack. The only i32 style variable is Marker
-/// at the moment, for that, use something like
-/// ```no_run
-/// # use implot::{push_style_var_i32, StyleVar, Marker};
-/// let markerchoice = push_style_var_i32(&StyleVar::Marker, Marker::Cross as i32);
-/// // plot things
-/// markerchoice.pop()
-/// ```
+/// at the moment, for which [`push_style_var_marker`] can be used instead.
 #[rustversion::attr(since(1.48), doc(alias = "PushStyleVar"))]
-pub fn push_style_var_i32(element: &StyleVar, value: i32) -> StyleVarToken {
+#[deprecated(
+    since = "0.7.0",
+    note = "use push_style_var, which makes sure the value has the right type"
+)]
+#[track_caller]
+pub fn push_style_var_i32<'ui>(
+    plot_ui: &'ui PlotUi,
+    element: &StyleVar,
+    value: i32,
+) -> StyleVarToken<'ui> {
     unsafe {
         sys::ImPlot_PushStyleVarInt(*element as sys::ImPlotStyleVar, value);
     }
-    StyleVarToken { was_popped: false }
+    let push_id = new_push_id();
+    record_pushes(push_id, StackKind::StyleVar, 1);
+    StyleVarToken::new(plot_ui, push_id)
+}
+
+/// Push a marker to the style variable stack, which makes it the marker of all following items
+/// until the returned token is popped. This is a shorthand for [`push_style_var`] with
+/// [`StyleVarValue::Marker`]. For changing the marker of a single item,
+/// [`set_next_marker_style`] is simpler.
+/// ```no_run
+/// # use implot::{push_style_var_marker, Marker, PlotUi};
+/// # fn example(plot_ui: &PlotUi) {
+/// let markerchoice = push_style_var_marker(plot_ui, Marker::Cross);
+/// // Plot some things
+/// markerchoice.pop();
+/// # }
+/// ```
+#[rustversion::attr(since(1.48), doc(alias = "PushStyleVar"))]
+#[track_caller]
+pub fn push_style_var_marker<'ui>(plot_ui: &'ui PlotUi, marker: Marker) -> StyleVarToken<'ui> {
+    push_style_var(plot_ui, StyleVarValue::Marker(marker))
 }
 
 /// Push an ImVec2 style variable to the stack. The returned token is used for removing
 /// the variable from the stack again.
-pub fn push_style_var_imvec2(element: &StyleVar, value: ImVec2) -> StyleVarToken {
+#[deprecated(
+    since = "0.7.0",
+    note = "use push_style_var, which makes sure the value has the right type"
+)]
+#[track_caller]
+pub fn push_style_var_imvec2<'ui>(
+    plot_ui: &'ui PlotUi,
+    element: &StyleVar,
+    value: impl Into<ImVec2>,
+) -> StyleVarToken<'ui> {
     unsafe {
-        sys::ImPlot_PushStyleVarVec2(*element as sys::ImPlotStyleVar, value);
+        sys::ImPlot_PushStyleVarVec2(*element as sys::ImPlotStyleVar, value.into());
     }
-    StyleVarToken { was_popped: false }
+    let push_id = new_push_id();
+    record_pushes(push_id, StackKind::StyleVar, 1);
+    StyleVarToken::new(plot_ui, push_id)
 }
 
-/// Tracks a change pushed to the style variable stack
-pub struct StyleVarToken {
+/// Tracks a change pushed to the style variable stack. The change is undone when the token is
+/// popped, or when it is dropped without being popped, for example during a panic.
+///
+/// Like the [`PlotUi`] it borrows, this can't be sent to other threads:
+/// ```compile_fail
+/// fn check<T: Send>() {}
+/// check::<implot::StyleVarToken>();
+/// ```
+#[must_use = "the style variable is popped again when the token is dropped"]
+pub struct StyleVarToken<'ui> {
+    /// Identifies the push of this token
+    push_id: PushId,
     /// Whether this token has been popped or not.
     was_popped: bool,
+    /// Ties the token to the borrow of the `PlotUi` it was pushed with, so it can't be kept
+    /// beyond the frame or sent to another thread.
+    plot_ui: PhantomData<&'ui PlotUi<'ui>>,
 }
 
-impl StyleVarToken {
+impl<'ui> StyleVarToken<'ui> {
+    /// Internal helper that creates the token for a push made with the given `PlotUi`.
+    fn new(_plot_ui: &'ui PlotUi, push_id: PushId) -> Self {
+        Self {
+            push_id,
+            was_popped: false,
+            plot_ui: PhantomData,
+        }
+    }
+
     /// Pop this token from the stack.
     #[rustversion::attr(since(1.48), doc(alias = "PopStyleVar"))]
     pub fn pop(mut self) {
-        if self.was_popped {
-            panic!("Attempted to pop a style var token twice.")
+        self.pop_if_needed();
+    }
+
+    /// Internal helper that pops the token unless it has been popped before.
+    fn pop_if_needed(&mut self) {
+        if !self.was_popped {
+            self.was_popped = true;
+            pop_from_stacks(take_pushes(self.push_id));
         }
-        self.was_popped = true;
+    }
+}
+
+impl Drop for StyleVarToken<'_> {
+    fn drop(&mut self) {
+        self.pop_if_needed();
+    }
+}
+
+/// Push several style colors and style variables at once. The returned token pops all of them
+/// together, which is less noisy than handling one token per push:
+/// ```no_run
+/// # use implot::{push_style, Marker, PlotColorElement, PlotUi, StyleVarValue};
+/// # fn example(plot_ui: &PlotUi) {
+/// let pushed_style = push_style(
+///     plot_ui,
+///     &[(PlotColorElement::Line, [1.0, 0.5, 0.0, 1.0].into())],
+///     &[
+///         StyleVarValue::LineWeight(2.0),
+///         StyleVarValue::Marker(Marker::Circle),
+///     ],
+/// );
+/// // Plot some things
+/// pushed_style.pop();
+/// # }
+/// ```
+/// Since the values always have the right type for their style variable, none of the pushes
+/// can fail, so all of them are in effect until the token is popped.
+#[rustversion::attr(since(1.48), doc(alias = "PushStyleColor"))]
+#[rustversion::attr(since(1.48), doc(alias = "PushStyleVar"))]
+#[track_caller]
+pub fn push_style<'ui>(
+    plot_ui: &'ui PlotUi,
+    colors: &[(PlotColorElement, ImVec4)],
+    vars: &[StyleVarValue],
+) -> StyleToken<'ui> {
+    for (element, color) in colors {
         unsafe {
-            sys::ImPlot_PopStyleVar(1);
+            sys::ImPlot_PushStyleColorVec4(*element as sys::ImPlotCol, *color);
         }
     }
+    for value in vars {
+        push_style_var_value(*value);
+    }
+    let push_id = new_push_id();
+    record_pushes(push_id, StackKind::StyleColor, colors.len());
+    record_pushes(push_id, StackKind::StyleVar, vars.len());
+    StyleToken::new(plot_ui, push_id)
+}
+
+/// Tracks several changes pushed to the style color and style variable stacks with
+/// [`push_style`]. The changes are undone when the token is popped, or when it is dropped
+/// without being popped, for example during a panic.
+///
+/// Like the [`PlotUi`] it borrows, this can't be sent to other threads:
+/// ```compile_fail
+/// fn check<T: Send>() {}
+/// check::<implot::StyleToken>();
+/// ```
+#[must_use = "the style is popped again when the token is dropped"]
+pub struct StyleToken<'ui> {
+    /// Identifies the pushes of this token
+    push_id: PushId,
+    /// Whether this token has been popped or not.
+    was_popped: bool,
+    /// Ties the token to the borrow of the `PlotUi` it was pushed with, so it can't be kept
+    /// beyond the frame or sent to another thread.
+    plot_ui: PhantomData<&'ui PlotUi<'ui>>,
+}
+
+impl<'ui> StyleToken<'ui> {
+    /// Internal helper that creates the token for pushes made with the given `PlotUi`.
+    fn new(_plot_ui: &'ui PlotUi, push_id: PushId) -> Self {
+        Self {
+            push_id,
+            was_popped: false,
+            plot_ui: PhantomData,
+        }
+    }
+
+    /// Pop all the colors and variables of this token from the stacks.
+    #[rustversion::attr(since(1.48), doc(alias = "PopStyleColor"))]
+    #[rustversion::attr(since(1.48), doc(alias = "PopStyleVar"))]
+    pub fn pop(mut self) {
+        self.pop_if_needed();
+    }
+
+    /// Internal helper that pops the token unless it has been popped before.
+    fn pop_if_needed(&mut self) {
+        if !self.was_popped {
+            self.was_popped = true;
+            pop_from_stacks(take_pushes(self.push_id));
+        }
+    }
+}
+
+impl Drop for StyleToken<'_> {
+    fn drop(&mut self) {
+        self.pop_if_needed();
+    }
+}
+
+/// Run `f` with the given style color pushed, and pop the color again afterwards. Returns the
+/// value returned by `f`. Unlike with [`push_style_color`], the pop can't be forgotten, for
+/// example due to an early return:
+/// ```no_run
+/// # use implot::{with_style_color, PlotColorElement, PlotLine, PlotUi};
+/// # fn example(plot_ui: &PlotUi, x: &[f64], y: &[f64]) {
+/// with_style_color(plot_ui, PlotColorElement::Line, [1.0, 0.0, 0.0, 1.0], || {
+///     PlotLine::new("red line").plot(x, y);
+/// });
+/// # }
+/// ```
+#[track_caller]
+pub fn with_style_color<R, F: FnOnce() -> R>(
+    plot_ui: &PlotUi,
+    element: PlotColorElement,
+    color: impl Into<ImVec4>,
+    f: F,
+) -> R {
+    let token = push_style_color(plot_ui, element, color);
+    let result = f();
+    token.pop();
+    result
+}
+
+/// Run `f` with all the given style colors pushed, and pop them again afterwards. Returns the
+/// value returned by `f`. See [`with_style_color`].
+#[track_caller]
+pub fn with_style_colors<R, F: FnOnce() -> R>(
+    plot_ui: &PlotUi,
+    colors: &[(PlotColorElement, ImVec4)],
+    f: F,
+) -> R {
+    let token = push_style(plot_ui, colors, &[]);
+    let result = f();
+    token.pop();
+    result
+}
+
+/// Run `f` with the given style variable pushed, and pop it again afterwards. Returns the value
+/// returned by `f`. Unlike with [`push_style_var`], the pop can't be forgotten, for example due
+/// to an early return:
+/// ```no_run
+/// # use implot::{with_style_var, PlotLine, PlotUi, StyleVarValue};
+/// # fn example(plot_ui: &PlotUi, x: &[f64], y: &[f64]) {
+/// with_style_var(plot_ui, StyleVarValue::LineWeight(3.0), || {
+///     PlotLine::new("thick line").plot(x, y);
+/// });
+/// # }
+/// ```
+#[track_caller]
+pub fn with_style_var<R, F: FnOnce() -> R>(plot_ui: &PlotUi, value: StyleVarValue, f: F) -> R {
+    let token = push_style_var(plot_ui, value);
+    let result = f();
+    token.pop();
+    result
+}
+
+/// Run `f` with all the given style variables pushed, and pop them again afterwards. Returns
+/// the value returned by `f`. See [`with_style_var`].
+#[track_caller]
+pub fn with_style_vars<R, F: FnOnce() -> R>(plot_ui: &PlotUi, values: &[StyleVarValue], f: F) -> R {
+    let token = push_style(plot_ui, &[], values);
+    let result = f();
+    token.pop();
+    result
+}
+
+/// Push a colormap to the colormap stack, which makes it the current colormap until the returned
+/// token is popped. This takes either one of the built-in preset colormaps or a custom colormap
+/// created with [`register_colormap`]. Unlike [`set_colormap_from_preset`], this does not change
+/// the colormap permanently, and pushes can be nested:
+/// ```no_run
+/// # use implot::{push_colormap, Colormap, PlotUi};
+/// # fn example(plot_ui: &PlotUi) {
+/// let pushed_colormap = push_colormap(plot_ui, Colormap::Viridis);
+/// // Plot some things
+/// pushed_colormap.pop();
+/// # }
+/// ```
+#[rustversion::attr(since(1.48), doc(alias = "PushColormap"))]
+#[track_caller]
+pub fn push_colormap<'ui, C: Into<ColormapChoice>>(
+    plot_ui: &'ui PlotUi,
+    colormap: C,
+) -> ColormapToken<'ui> {
+    push_colormap_choice(colormap.into());
+    let push_id = new_push_id();
+    record_pushes(push_id, StackKind::Colormap, 1);
+    ColormapToken::new(plot_ui, push_id)
+}
+
+/// Internal helper that pushes a colormap without creating a token for it. This is for pushes
+/// that are popped again right away, with `ImPlot_PopColormap`.
+pub(crate) fn push_colormap_choice(colormap: ColormapChoice) {
+    colormap::push_stored_colormap();
+    match colormap {
+        ColormapChoice::Preset(preset) => unsafe {
+            sys::ImPlot_PushColormapPlotColormap(preset as sys::ImPlotColormap);
+        },
+        // ImPlot keeps the pointer to the colors, which is fine since custom colormaps are
+        // never freed
+        ColormapChoice::Custom(custom) => unsafe {
+            sys::ImPlot_PushColormapVec4Ptr(custom.colors().as_ptr(), custom.colors().len() as i32);
+        },
+    }
+}
+
+/// Tracks a change pushed to the colormap stack. The change is undone when the token is popped,
+/// or when it is dropped without being popped, for example during a panic.
+///
+/// Like the [`PlotUi`] it borrows, this can't be sent to other threads:
+/// ```compile_fail
+/// fn check<T: Send>() {}
+/// check::<implot::ColormapToken>();
+/// ```
+#[must_use = "the colormap is popped again when the token is dropped"]
+pub struct ColormapToken<'ui> {
+    /// Identifies the push of this token
+    push_id: PushId,
+    /// Whether this token has been popped or not.
+    was_popped: bool,
+    /// Ties the token to the borrow of the `PlotUi` it was pushed with, so it can't be kept
+    /// beyond the frame or sent to another thread.
+    plot_ui: PhantomData<&'ui PlotUi<'ui>>,
+}
+
+impl<'ui> ColormapToken<'ui> {
+    /// Internal helper that creates the token for a push made with the given `PlotUi`.
+    fn new(_plot_ui: &'ui PlotUi, push_id: PushId) -> Self {
+        Self {
+            push_id,
+            was_popped: false,
+            plot_ui: PhantomData,
+        }
+    }
+
+    /// Pop this token from the stack, which restores the colormap that was current before it was
+    /// pushed.
+    #[rustversion::attr(since(1.48), doc(alias = "PopColormap"))]
+    pub fn pop(mut self) {
+        self.pop_if_needed();
+    }
+
+    /// Internal helper that pops the token unless it has been popped before.
+    fn pop_if_needed(&mut self) {
+        if !self.was_popped {
+            self.was_popped = true;
+            pop_from_stacks(take_pushes(self.push_id));
+        }
+    }
+}
+
+impl Drop for ColormapToken<'_> {
+    fn drop(&mut self) {
+        self.pop_if_needed();
+    }
+}
+
+// --- Next item style ---------------------------------------------------------------------------
+/// Turn an Option<f32> into an f32. Picks IMPLOT_AUTO for None.
+fn f32_option_or_auto(value: Option<f32>) -> f32 {
+    value.unwrap_or(IMPLOT_AUTO as f32)
+}
+
+/// Turn an Option<ImVec4> into an ImVec4. Picks IMPLOT_AUTO_COL for None.
+fn color_option_or_auto(color: Option<ImVec4>) -> ImVec4 {
+    color.unwrap_or(IMPLOT_AUTO_COL)
+}
+
+/// Set the line color and weight of the next item only, as a shorthand for pushing and popping
+/// [`PlotColorElement::Line`] and [`StyleVar::LineWeight`] around it. `None` keeps the automatic
+/// value, which is the next colormap color for the color and the style value for the weight.
+/// Colors given as `[f32; 4]` can be passed with `.into()`:
+/// ```no_run
+/// # use implot::{set_next_line_style, PlotLine};
+/// # fn example(x: &[f64], y: &[f64]) {
+/// set_next_line_style(Some([1.0, 0.0, 0.0, 1.0].into()), Some(2.0));
+/// PlotLine::new("red and thick").plot(x, y);
+/// # }
+/// ```
+#[rustversion::attr(since(1.48), doc(alias = "SetNextLineStyle"))]
+pub fn set_next_line_style(color: Option<ImVec4>, weight: Option<f32>) {
+    debug_check_thread();
+    unsafe {
+        sys::ImPlot_SetNextLineStyle(color_option_or_auto(color), f32_option_or_auto(weight));
+    }
+}
+
+/// Set the fill color of the next item only, for items with filled areas such as bars. This is
+/// a shorthand for pushing and popping [`PlotColorElement::Fill`] around it. The alpha of the
+/// fill color is multiplied by `alpha_multiplier`. `None` keeps the automatic value, which is
+/// the line color of the item for the color and [`StyleVar::FillAlpha`] for the multiplier.
+/// ```no_run
+/// # use implot::{set_next_fill_style, PlotBars};
+/// # fn example(positions: &[f64], values: &[f64]) {
+/// set_next_fill_style(Some([0.2, 0.6, 0.2, 1.0].into()), Some(0.5));
+/// PlotBars::new("translucent green").plot(positions, values);
+/// # }
+/// ```
+#[rustversion::attr(since(1.48), doc(alias = "SetNextFillStyle"))]
+pub fn set_next_fill_style(color: Option<ImVec4>, alpha_multiplier: Option<f32>) {
+    debug_check_thread();
+    unsafe {
+        sys::ImPlot_SetNextFillStyle(
+            color_option_or_auto(color),
+            f32_option_or_auto(alpha_multiplier),
+        );
+    }
+}
+
+/// Set the marker style of the next item only, as a shorthand for pushing and popping the
+/// marker style variables and colors around it. `None` keeps the automatic value for the
+/// respective setting, which is the style value for the marker, size and weight, and a color
+/// derived from the line color of the item for the fill and outline.
+///
+/// Note that ImPlot uses the same value for [`Marker::None`] and for "automatic", so
+/// `Some(Marker::None)` does not hide the markers if the style has a marker set - use
+/// [`push_style_var`] with [`StyleVarValue::Marker`] for that.
+/// ```no_run
+/// # use implot::{set_next_marker_style, Marker, PlotScatter};
+/// # fn example(x: &[f64], y: &[f64]) {
+/// set_next_marker_style(Some(Marker::Diamond), Some(8.0), None, None, None);
+/// PlotScatter::new("diamonds").plot(x, y);
+/// # }
+/// ```
+#[rustversion::attr(since(1.48), doc(alias = "SetNextMarkerStyle"))]
+pub fn set_next_marker_style(
+    marker: Option<Marker>,
+    size: Option<f32>,
+    fill: Option<ImVec4>,
+    weight: Option<f32>,
+    outline: Option<ImVec4>,
+) {
+    debug_check_thread();
+    unsafe {
+        sys::ImPlot_SetNextMarkerStyle(
+            marker.map_or(IMPLOT_AUTO, |marker| marker as i32),
+            f32_option_or_auto(size),
+            color_option_or_auto(fill),
+            f32_option_or_auto(weight),
+            color_option_or_auto(outline),
+        );
+    }
+}
+
+/// Set the error bar style of the next item only, as a shorthand for pushing and popping
+/// [`PlotColorElement::ErrorBar`], [`StyleVar::ErrorBarSize`] and [`StyleVar::ErrorBarWeight`]
+/// around it. `None` keeps the automatic value for the respective setting, which is the style
+/// value for all of them.
+#[rustversion::attr(since(1.48), doc(alias = "SetNextErrorBarStyle"))]
+pub fn set_next_error_bar_style(color: Option<ImVec4>, size: Option<f32>, weight: Option<f32>) {
+    debug_check_thread();
+    unsafe {
+        sys::ImPlot_SetNextErrorBarStyle(
+            color_option_or_auto(color),
+            f32_option_or_auto(size),
+            f32_option_or_auto(weight),
+        );
+    }
 }
 
 // --- Miscellaneous -----------------------------------------------------------------------------
-/// Returns true if the plot area in the current or most recent plot is hovered.
+// The functions here that read or change the state of the current plot only mean something in
+// the closure passed to Plot::build(). Outside of a plot, the `is_*` queries return false, since
+// nothing is hovered or queried there. The other functions fail a debug assertion that points at
+// the call, and in release builds they do nothing and return zeroed values; their `*_checked`
+// variants return `None` instead, and the methods of PlotScope can't be called outside a plot.
+/// Returns true if the plot area in the current plot is hovered. Returns false outside of a
+/// plot.
 #[rustversion::attr(since(1.48), doc(alias = "IsPlotHovered"))]
 pub fn is_plot_hovered() -> bool {
-    unsafe { sys::ImPlot_IsPlotHovered() }
+    debug_check_thread();
+    is_inside_plot() && unsafe { sys::ImPlot_IsPlotHovered() }
 }
 
-/// Returns true if the current or most recent plot is queried
+/// Returns true if the current plot is queried. Returns false outside of a plot.
 #[rustversion::attr(since(1.48), doc(alias = "IsPlotQueried"))]
 pub fn is_plot_queried() -> bool {
-    unsafe { sys::ImPlot_IsPlotQueried() }
+    debug_check_thread();
+    is_inside_plot() && unsafe { sys::ImPlot_IsPlotQueried() }
+}
+
+/// Returns the mouse position in x,y coordinates of the current plot, for the specified choice
+/// of Y axis. If `None` is the Y axis choice, that means the most recently selected Y axis is
+/// chosen.
+#[rustversion::attr(since(1.48), doc(alias = "GetPlotMousePos"))]
+#[track_caller]
+pub fn get_plot_mouse_position(y_axis_choice: Option<YAxisChoice>) -> PlotPoint {
+    debug_check_thread();
+    get_plot_mouse_position_raw(y_axis_choice).into()
 }
 
-/// Returns the mouse position in x,y coordinates of the current or most recent plot,
-/// for the specified choice of Y axis. If `None` is the Y axis choice, that means the
-/// most recently selected Y axis is chosen.
+/// Like [`get_plot_mouse_position`], but returns `None` outside of a plot instead of failing a
+/// debug assertion.
 #[rustversion::attr(since(1.48), doc(alias = "GetPlotMousePos"))]
-pub fn get_plot_mouse_position(y_axis_choice: Option<YAxisChoice>) -> ImPlotPoint {
+pub fn get_plot_mouse_position_checked(y_axis_choice: Option<YAxisChoice>) -> Option<PlotPoint> {
+    debug_check_thread();
+    if_inside_plot(|| get_plot_mouse_position(y_axis_choice))
+}
+
+/// Like [`get_plot_mouse_position`], but returns the sys type.
+#[rustversion::attr(since(1.48), doc(alias = "GetPlotMousePos"))]
+#[track_caller]
+pub fn get_plot_mouse_position_raw(y_axis_choice: Option<YAxisChoice>) -> ImPlotPoint {
+    debug_check_thread();
     let y_axis_choice_i32 = y_axis_choice_option_to_i32(y_axis_choice);
     let mut point = ImPlotPoint { x: 0.0, y: 0.0 }; // doesn't seem to have default()
-    unsafe {
-        sys::ImPlot_GetPlotMousePos(&mut point as *mut ImPlotPoint, y_axis_choice_i32);
+    if check_inside_plot("get_plot_mouse_position") {
+        unsafe {
+            sys::ImPlot_GetPlotMousePos(&mut point as *mut ImPlotPoint, y_axis_choice_i32);
+        }
     }
     point
 }
 
-/// Convert pixels, given as an `ImVec2`, to a position in the current plot's coordinate system.
-/// Uses the specified Y axis, if any, otherwise whatever was previously chosen.
+/// Convert pixels, given as an `ImVec2` or anything that converts into one, to a position in
+/// the current plot's coordinate system. Uses the specified Y axis, if any, otherwise whatever
+/// was previously chosen.
 #[rustversion::attr(since(1.48), doc(alias = "PixelsToPlot"))]
+#[track_caller]
 pub fn pixels_to_plot_vec2(
-    pixel_position: &ImVec2,
+    pixel_position: impl Into<ImVec2>,
+    y_axis_choice: Option<YAxisChoice>,
+) -> PlotPoint {
+    debug_check_thread();
+    pixels_to_plot_vec2_raw(pixel_position, y_axis_choice).into()
+}
+
+/// Like [`pixels_to_plot_vec2`], but returns `None` outside of a plot instead of failing a debug
+/// assertion.
+#[rustversion::attr(since(1.48), doc(alias = "PixelsToPlot"))]
+pub fn pixels_to_plot_vec2_checked(
+    pixel_position: impl Into<ImVec2>,
+    y_axis_choice: Option<YAxisChoice>,
+) -> Option<PlotPoint> {
+    debug_check_thread();
+    if_inside_plot(|| pixels_to_plot_vec2(pixel_position, y_axis_choice))
+}
+
+/// Like [`pixels_to_plot_vec2`], but returns the sys type.
+#[rustversion::attr(since(1.48), doc(alias = "PixelsToPlot"))]
+#[track_caller]
+pub fn pixels_to_plot_vec2_raw(
+    pixel_position: impl Into<ImVec2>,
     y_axis_choice: Option<YAxisChoice>,
 ) -> ImPlotPoint {
+    debug_check_thread();
     let y_axis_choice_i32 = y_axis_choice_option_to_i32(y_axis_choice);
     let mut point = ImPlotPoint { x: 0.0, y: 0.0 }; // doesn't seem to have default()
-    unsafe {
-        sys::ImPlot_PixelsToPlotVec2(
-            &mut point as *mut ImPlotPoint,
-            *pixel_position,
-            y_axis_choice_i32,
-        );
+    if check_inside_plot("pixels_to_plot_vec2") {
+        unsafe {
+            sys::ImPlot_PixelsToPlotVec2(
+                &mut point as *mut ImPlotPoint,
+                pixel_position.into(),
+                y_axis_choice_i32,
+            );
+        }
     }
     point
 }
@@ -460,132 +1499,704 @@ pub fn pixels_to_plot_vec2(
 /// Convert pixels, given as floats `x` and `y`, to a position in the current plot's coordinate
 /// system. Uses the specified Y axis, if any, otherwise whatever was previously chosen.
 #[rustversion::attr(since(1.48), doc(alias = "PixelsToPlot"))]
+#[track_caller]
 pub fn pixels_to_plot_f32(
     pixel_position_x: f32,
     pixel_position_y: f32,
     y_axis_choice: Option<YAxisChoice>,
+) -> PlotPoint {
+    debug_check_thread();
+    pixels_to_plot_f32_raw(pixel_position_x, pixel_position_y, y_axis_choice).into()
+}
+
+/// Like [`pixels_to_plot_f32`], but returns `None` outside of a plot instead of failing a debug
+/// assertion.
+#[rustversion::attr(since(1.48), doc(alias = "PixelsToPlot"))]
+pub fn pixels_to_plot_f32_checked(
+    pixel_position_x: f32,
+    pixel_position_y: f32,
+    y_axis_choice: Option<YAxisChoice>,
+) -> Option<PlotPoint> {
+    debug_check_thread();
+    if_inside_plot(|| pixels_to_plot_f32(pixel_position_x, pixel_position_y, y_axis_choice))
+}
+
+/// Like [`pixels_to_plot_f32`], but returns the sys type.
+#[rustversion::attr(since(1.48), doc(alias = "PixelsToPlot"))]
+#[track_caller]
+pub fn pixels_to_plot_f32_raw(
+    pixel_position_x: f32,
+    pixel_position_y: f32,
+    y_axis_choice: Option<YAxisChoice>,
 ) -> ImPlotPoint {
+    debug_check_thread();
     let y_axis_choice_i32 = y_axis_choice_option_to_i32(y_axis_choice);
     let mut point = ImPlotPoint { x: 0.0, y: 0.0 }; // doesn't seem to have default()
-    unsafe {
-        sys::ImPlot_PixelsToPlotFloat(
-            &mut point as *mut ImPlotPoint,
-            pixel_position_x,
-            pixel_position_y,
-            y_axis_choice_i32,
-        );
+    if check_inside_plot("pixels_to_plot_f32") {
+        unsafe {
+            sys::ImPlot_PixelsToPlotFloat(
+                &mut point as *mut ImPlotPoint,
+                pixel_position_x,
+                pixel_position_y,
+                y_axis_choice_i32,
+            );
+        }
     }
     point
 }
 
 /// Convert a position in the current plot's coordinate system to pixels. Uses the specified Y
-/// axis, if any, otherwise whatever was previously chosen.
-///
+/// axis, if any, otherwise whatever was previously chosen. The position can be a [`PlotPoint`],
+/// an `ImPlotPoint` or a reference to one of them, or an `[f64; 2]`.
 #[rustversion::attr(since(1.48), doc(alias = "PlotToPixels"))]
+#[track_caller]
 pub fn plot_to_pixels_vec2(
-    plot_position: &ImPlotPoint,
+    plot_position: impl Into<PlotPoint>,
     y_axis_choice: Option<YAxisChoice>,
 ) -> ImVec2 {
+    debug_check_thread();
     let y_axis_choice_i32 = y_axis_choice_option_to_i32(y_axis_choice);
     let mut pixel_position = ImVec2 { x: 0.0, y: 0.0 }; // doesn't seem to have default()
-    unsafe {
-        sys::ImPlot_PlotToPixelsPlotPoInt(
-            &mut pixel_position as *mut ImVec2,
-            *plot_position,
-            y_axis_choice_i32,
-        );
+    if check_inside_plot("plot_to_pixels_vec2") {
+        unsafe {
+            sys::ImPlot_PlotToPixelsPlotPoInt(
+                &mut pixel_position as *mut ImVec2,
+                plot_position.into().into(),
+                y_axis_choice_i32,
+            );
+        }
     }
     pixel_position
 }
 
+/// Like [`plot_to_pixels_vec2`], but returns `None` outside of a plot instead of failing a debug
+/// assertion.
+#[rustversion::attr(since(1.48), doc(alias = "PlotToPixels"))]
+pub fn plot_to_pixels_vec2_checked(
+    plot_position: impl Into<PlotPoint>,
+    y_axis_choice: Option<YAxisChoice>,
+) -> Option<ImVec2> {
+    debug_check_thread();
+    if_inside_plot(|| plot_to_pixels_vec2(plot_position, y_axis_choice))
+}
+
 /// Convert a position in the current plot's coordinate system to pixels. Uses the specified Y
 /// axis, if any, otherwise whatever was previously chosen.
 #[rustversion::attr(since(1.48), doc(alias = "PlotToPixels"))]
+#[track_caller]
 pub fn plot_to_pixels_f32(
     plot_position_x: f64,
     plot_position_y: f64,
     y_axis_choice: Option<YAxisChoice>,
 ) -> ImVec2 {
+    debug_check_thread();
     let y_axis_choice_i32 = y_axis_choice_option_to_i32(y_axis_choice);
     let mut pixel_position = ImVec2 { x: 0.0, y: 0.0 }; // doesn't seem to have default()
-    unsafe {
-        sys::ImPlot_PlotToPixelsdouble(
-            &mut pixel_position as *mut ImVec2,
-            plot_position_x,
-            plot_position_y,
-            y_axis_choice_i32,
-        );
+    if check_inside_plot("plot_to_pixels_f32") {
+        unsafe {
+            sys::ImPlot_PlotToPixelsdouble(
+                &mut pixel_position as *mut ImVec2,
+                plot_position_x,
+                plot_position_y,
+                y_axis_choice_i32,
+            );
+        }
     }
     pixel_position
 }
 
-/// Returns the current or most recent plot axis range for the specified choice of Y axis. If
-/// `None` is the Y axis choice, that means the most recently selected Y axis is chosen.
+/// Like [`plot_to_pixels_f32`], but returns `None` outside of a plot instead of failing a debug
+/// assertion.
+#[rustversion::attr(since(1.48), doc(alias = "PlotToPixels"))]
+pub fn plot_to_pixels_f32_checked(
+    plot_position_x: f64,
+    plot_position_y: f64,
+    y_axis_choice: Option<YAxisChoice>,
+) -> Option<ImVec2> {
+    debug_check_thread();
+    if_inside_plot(|| plot_to_pixels_f32(plot_position_x, plot_position_y, y_axis_choice))
+}
+
+/// Returns the current plot axis range for the specified choice of Y axis. If `None` is the Y
+/// axis choice, that means the most recently selected Y axis is chosen.
+#[rustversion::attr(since(1.48), doc(alias = "GetPlotLimits"))]
+#[track_caller]
+pub fn get_plot_limits(y_axis_choice: Option<YAxisChoice>) -> PlotLimits {
+    debug_check_thread();
+    get_plot_limits_raw(y_axis_choice).into()
+}
+
+/// Like [`get_plot_limits`], but returns `None` outside of a plot instead of failing a debug
+/// assertion.
 #[rustversion::attr(since(1.48), doc(alias = "GetPlotLimits"))]
-pub fn get_plot_limits(y_axis_choice: Option<YAxisChoice>) -> ImPlotLimits {
+pub fn get_plot_limits_checked(y_axis_choice: Option<YAxisChoice>) -> Option<PlotLimits> {
+    debug_check_thread();
+    if_inside_plot(|| get_plot_limits(y_axis_choice))
+}
+
+/// Like [`get_plot_limits`], but returns the sys type.
+#[rustversion::attr(since(1.48), doc(alias = "GetPlotLimits"))]
+#[track_caller]
+pub fn get_plot_limits_raw(y_axis_choice: Option<YAxisChoice>) -> ImPlotLimits {
+    debug_check_thread();
     let y_axis_choice_i32 = y_axis_choice_option_to_i32(y_axis_choice);
     // ImPlotLimits doesn't seem to have default()
     let mut limits = ImPlotLimits {
         X: ImPlotRange { Min: 0.0, Max: 0.0 },
         Y: ImPlotRange { Min: 0.0, Max: 0.0 },
     };
-    unsafe {
-        sys::ImPlot_GetPlotLimits(&mut limits as *mut ImPlotLimits, y_axis_choice_i32);
+    if check_inside_plot("get_plot_limits") {
+        unsafe {
+            sys::ImPlot_GetPlotLimits(&mut limits as *mut ImPlotLimits, y_axis_choice_i32);
+        }
     }
     limits
 }
 
-/// Returns the query limits of the current or most recent plot, for the specified choice of Y
-/// axis. If `None` is the Y axis choice, that means the most recently selected Y axis is chosen.
+/// Returns the query limits of the current plot, for the specified choice of Y axis. If `None`
+/// is the Y axis choice, that means the most recently selected Y axis is chosen.
 #[rustversion::attr(since(1.48), doc(alias = "GetPlotQuery"))]
-pub fn get_plot_query(y_axis_choice: Option<YAxisChoice>) -> ImPlotLimits {
+#[track_caller]
+pub fn get_plot_query(y_axis_choice: Option<YAxisChoice>) -> PlotLimits {
+    debug_check_thread();
+    get_plot_query_raw(y_axis_choice).into()
+}
+
+/// Like [`get_plot_query`], but returns the sys type.
+#[rustversion::attr(since(1.48), doc(alias = "GetPlotQuery"))]
+#[track_caller]
+pub fn get_plot_query_raw(y_axis_choice: Option<YAxisChoice>) -> ImPlotLimits {
+    debug_check_thread();
     let y_axis_choice_i32 = y_axis_choice_option_to_i32(y_axis_choice);
     // ImPlotLimits doesn't seem to have default()
     let mut limits = ImPlotLimits {
         X: ImPlotRange { Min: 0.0, Max: 0.0 },
         Y: ImPlotRange { Min: 0.0, Max: 0.0 },
     };
-    unsafe {
-        sys::ImPlot_GetPlotQuery(&mut limits as *mut ImPlotLimits, y_axis_choice_i32);
+    if check_inside_plot("get_plot_query") {
+        unsafe {
+            sys::ImPlot_GetPlotQuery(&mut limits as *mut ImPlotLimits, y_axis_choice_i32);
+        }
     }
     limits
 }
 
+/// Returns the query limits of the current plot if it is currently queried, and `None`
+/// otherwise, which includes being called outside of a plot. See [`get_plot_query`] for the
+/// meaning of the Y axis choice.
+#[rustversion::attr(since(1.48), doc(alias = "GetPlotQuery"))]
+#[track_caller]
+pub fn get_plot_query_checked(y_axis_choice: Option<YAxisChoice>) -> Option<PlotLimits> {
+    debug_check_thread();
+    get_plot_query_checked_raw(y_axis_choice).map(PlotLimits::from)
+}
+
+/// Like [`get_plot_query_checked`], but returns the sys type.
+#[rustversion::attr(since(1.48), doc(alias = "GetPlotQuery"))]
+#[track_caller]
+pub fn get_plot_query_checked_raw(y_axis_choice: Option<YAxisChoice>) -> Option<ImPlotLimits> {
+    debug_check_thread();
+    if is_plot_queried() {
+        Some(get_plot_query_raw(y_axis_choice))
+    } else {
+        None
+    }
+}
+
+/// Returns the query rectangle of the current plot in pixel coordinates if it is currently
+/// queried, and `None` otherwise, which includes being called outside of a plot. The first
+/// point is the top left corner and the second point the bottom right corner of the rectangle,
+/// regardless of whether any axes are inverted.
+/// See [`get_plot_query`] for the meaning of the Y axis choice.
+#[rustversion::attr(since(1.48), doc(alias = "GetPlotQuery"))]
+#[track_caller]
+pub fn get_plot_query_pixels(y_axis_choice: Option<YAxisChoice>) -> Option<(ImVec2, ImVec2)> {
+    debug_check_thread();
+    let limits = get_plot_query_checked(y_axis_choice)?;
+    let corner_a = plot_to_pixels_vec2([limits.x.min, limits.y.min], y_axis_choice);
+    let corner_b = plot_to_pixels_vec2([limits.x.max, limits.y.max], y_axis_choice);
+    Some(normalize_pixel_rect(corner_a, corner_b))
+}
+
+/// Internal helper that turns two opposite corners of a rectangle into its top left and bottom
+/// right corners.
+fn normalize_pixel_rect(corner_a: ImVec2, corner_b: ImVec2) -> (ImVec2, ImVec2) {
+    (
+        ImVec2 {
+            x: corner_a.x.min(corner_b.x),
+            y: corner_a.y.min(corner_b.y),
+        },
+        ImVec2 {
+            x: corner_a.x.max(corner_b.x),
+            y: corner_a.y.max(corner_b.y),
+        },
+    )
+}
+
 /// Set the Y axis to be used for any upcoming plot elements
 #[rustversion::attr(since(1.48), doc(alias = "SetPlotYAxis"))]
+#[track_caller]
 pub fn set_plot_y_axis(y_axis_choice: YAxisChoice) {
-    unsafe {
-        sys::ImPlot_SetPlotYAxis(y_axis_choice as i32);
+    debug_check_thread();
+    if check_inside_plot("set_plot_y_axis") {
+        unsafe {
+            sys::ImPlot_SetPlotYAxis(y_axis_choice as i32);
+        }
+    }
+}
+
+/// Returns the top-left position of the plot area of the current plot, in pixels.
+#[rustversion::attr(since(1.48), doc(alias = "GetPlotPos"))]
+#[track_caller]
+pub fn get_plot_pos() -> ImVec2 {
+    debug_check_thread();
+    let mut pos = ImVec2 { x: 0.0, y: 0.0 }; // doesn't seem to have default()
+    if check_inside_plot("get_plot_pos") {
+        unsafe {
+            sys::ImPlot_GetPlotPos(&mut pos as *mut ImVec2);
+        }
+    }
+    pos
+}
+
+/// Like [`get_plot_pos`], but returns `None` outside of a plot instead of failing a debug
+/// assertion.
+#[rustversion::attr(since(1.48), doc(alias = "GetPlotPos"))]
+pub fn get_plot_pos_checked() -> Option<ImVec2> {
+    debug_check_thread();
+    if_inside_plot(get_plot_pos)
+}
+
+/// Returns the size of the plot area of the current plot, in pixels.
+#[rustversion::attr(since(1.48), doc(alias = "GetPlotSize"))]
+#[track_caller]
+pub fn get_plot_size() -> ImVec2 {
+    debug_check_thread();
+    let mut size = ImVec2 { x: 0.0, y: 0.0 }; // doesn't seem to have default()
+    if check_inside_plot("get_plot_size") {
+        unsafe {
+            sys::ImPlot_GetPlotSize(&mut size as *mut ImVec2);
+        }
+    }
+    size
+}
+
+/// Like [`get_plot_size`], but returns `None` outside of a plot instead of failing a debug
+/// assertion.
+#[rustversion::attr(since(1.48), doc(alias = "GetPlotSize"))]
+pub fn get_plot_size_checked() -> Option<ImVec2> {
+    debug_check_thread();
+    if_inside_plot(get_plot_size)
+}
+
+/// Internal helper for the `*_checked` query functions, which runs `f` inside of a plot and
+/// returns `None` without running it anywhere else.
+fn if_inside_plot<T, F: FnOnce() -> T>(f: F) -> Option<T> {
+    if is_inside_plot() {
+        Some(f())
+    } else {
+        None
     }
 }
 
-/// Returns true if the XAxis plot area in the current plot is hovered.
+/// Returns true if the XAxis plot area in the current plot is hovered. Returns false outside of
+/// a plot.
 #[rustversion::attr(since(1.48), doc(alias = "IsPlotXAxisHovered"))]
 pub fn is_plot_x_axis_hovered() -> bool {
-    unsafe { sys::ImPlot_IsPlotXAxisHovered() }
+    debug_check_thread();
+    is_inside_plot() && unsafe { sys::ImPlot_IsPlotXAxisHovered() }
 }
 
 /// Returns true if the Y axis area of the given Y axis choice in the current plot is hovered. If
-/// `None` is the Y axis choice, that means the most recently selected Y axis is chosen.
+/// `None` is the Y axis choice, that means the most recently selected Y axis is chosen. Returns
+/// false outside of a plot.
 #[rustversion::attr(since(1.48), doc(alias = "IsPlotYAxisHovered"))]
 pub fn is_plot_y_axis_hovered(y_axis_choice: Option<YAxisChoice>) -> bool {
+    debug_check_thread();
     let y_axis_choice_i32 = y_axis_choice_option_to_i32(y_axis_choice);
-    unsafe { sys::ImPlot_IsPlotYAxisHovered(y_axis_choice_i32) }
+    is_inside_plot() && unsafe { sys::ImPlot_IsPlotYAxisHovered(y_axis_choice_i32) }
 }
 
-/// Returns true if the given item in the legend of the current plot is hovered.
+/// Returns true if the given item in the legend of the current plot is hovered. Returns false
+/// outside of a plot.
+///
+/// # Panics
+/// Will panic if the legend entry string contains internal null bytes.
 pub fn is_legend_entry_hovered(legend_entry: &str) -> bool {
-    unsafe { sys::ImPlot_IsLegendEntryHovered(legend_entry.as_ptr() as *const c_char) }
+    debug_check_thread();
+    let legend_entry =
+        CString::new(legend_entry).expect("Legend entry label contained internal null bytes");
+    is_inside_plot() && unsafe { sys::ImPlot_IsLegendEntryHovered(legend_entry.as_ptr()) }
+}
+
+/// Hide (or show) the next item that is plotted in the current plot. With `Condition::Always`,
+/// this overrides the visibility the user selected through the legend, otherwise it only sets
+/// the initial visibility.
+#[rustversion::attr(since(1.48), doc(alias = "HideNextItem"))]
+pub fn hide_next_item(hidden: bool, condition: Condition) {
+    debug_check_thread();
+    unsafe { sys::ImPlot_HideNextItem(hidden, condition as sys::ImGuiCond) }
+}
+
+/// Number of vertices ImPlot uses for each segment of a line, which most plot types are made of.
+const VERTICES_PER_LINE_SEGMENT: usize = 4;
+
+/// Returns roughly how many points can be plotted as lines in one plot before the vertex
+/// indices of the plot's draw list overflow, which shows up as corrupted rendering. Applications
+/// can use this to decide whether to downsample their data. Markers and filled areas use more
+/// vertices per point, so leave some headroom when using them.
+///
+/// There is no limit (`usize::MAX` is returned) if the renderer of the current imgui context
+/// supports vertex offsets (`ImGuiBackendFlags_RendererHasVtxOffset`), which lets imgui split
+/// large draw lists, or if imgui was built with 32-bit draw indices. Otherwise, the 16-bit
+/// indices limit a plot to 65536 vertices. Without a current imgui context, the limit for a
+/// renderer without vertex offsets is returned.
+pub fn max_safe_points() -> usize {
+    debug_check_thread();
+    if crate::imgui_compat::draw_index_size() > 2 || crate::imgui_compat::renderer_has_vtx_offset()
+    {
+        usize::MAX
+    } else {
+        (1 << 16) / VERTICES_PER_LINE_SEGMENT
+    }
 }
 
 // --- Demo window -------------------------------------------------------------------------------
 /// Show the demo window for poking around what functionality implot has to
 /// offer. Note that not all of this is necessarily implemented in implot-rs
 /// already - if you find something missing you'd really like, raise an issue.
-// This requires implot_demo.cpp to be in the list of sources in implot-sys.
+///
+/// This is only available with the `demo` feature, which is on by default.
+// The feature makes implot-sys compile implot_demo.cpp.
+#[cfg(feature = "demo")]
 #[rustversion::attr(since(1.48), doc(alias = "ShowDemoWindow"))]
 pub fn show_demo_window(show: &mut bool) {
+    debug_check_thread();
     unsafe {
         implot_sys::ImPlot_ShowDemoWindow(show);
     }
 }
+
+/// Show the ImPlot user guide, which lists the mouse and keyboard controls of plots. This draws
+/// its text into the current imgui window, so it fits well into for example a help menu or
+/// popup of your application.
+// Unlike the demo window, this is part of implot.cpp, so it's always available.
+#[rustversion::attr(since(1.48), doc(alias = "ShowUserGuide"))]
+pub fn show_user_guide() {
+    debug_check_thread();
+    unsafe {
+        implot_sys::ImPlot_ShowUserGuide();
+    }
+}
+
+/// Show the ImPlot metrics window, which displays the internal state of all plots and items for
+/// debugging. The window gets a close button that sets `show` to false when clicked.
+// Unlike the demo window, this is part of implot.cpp, so it's always available.
+#[rustversion::attr(since(1.48), doc(alias = "ShowMetricsWindow"))]
+pub fn show_metrics_window(show: &mut bool) {
+    debug_check_thread();
+    unsafe {
+        implot_sys::ImPlot_ShowMetricsWindow(show);
+    }
+}
+
+/// Controls for the `compile_fail` tests of the types that can't be sent to or shared with other
+/// threads. Each of these only differs from one of those tests in the trait bound, so those fail
+/// because of the missing `Send` or `Sync` and not because of a mistake elsewhere in the test.
+/// ```
+/// fn check<T: Sized>() {}
+/// check::<implot::Context>();
+/// ```
+/// ```
+/// fn check<T: Sized>() {}
+/// check::<implot::PlotUi>();
+/// ```
+/// ```
+/// fn check<T: Sized>() {}
+/// check::<implot::StyleColorToken>();
+/// ```
+/// ```
+/// fn check<T: Sized>() {}
+/// check::<implot::StyleVarToken>();
+/// ```
+/// ```
+/// fn check<T: Sized>() {}
+/// check::<implot::StyleToken>();
+/// ```
+/// ```
+/// fn check<T: Sized>() {}
+/// check::<implot::ColormapToken>();
+/// ```
+/// ```
+/// fn check<T: Sized>() {}
+/// check::<implot::BorrowedContext>();
+/// ```
+/// ```
+/// fn check<T: Sized>() {}
+/// check::<implot::PlotToken>();
+/// ```
+/// ```
+/// fn check<T: Sized>() {}
+/// check::<implot::InputMapGuard>();
+/// ```
+/// ```
+/// fn check<T: Sized>() {}
+/// check::<implot::PlotScope>();
+/// ```
+#[cfg(doctest)]
+pub struct ThreadSafetyControls;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_rect_eq(actual: (ImVec2, ImVec2), expected: ([f32; 2], [f32; 2])) {
+        assert_eq!([actual.0.x, actual.0.y], expected.0);
+        assert_eq!([actual.1.x, actual.1.y], expected.1);
+    }
+
+    #[test]
+    fn test_data_types_are_send_and_sync() {
+        // The types that access ImPlot's state are checked to be neither in their doc tests
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<PlotLine>();
+        assert_send_sync::<PlotScatter>();
+        assert_send_sync::<PlotBars>();
+        assert_send_sync::<PlotHeatmap>();
+        assert_send_sync::<PlotHistogram>();
+        assert_send_sync::<Style>();
+        assert_send_sync::<StyleBuilder>();
+        assert_send_sync::<InputMap>();
+        assert_send_sync::<ContextSettings>();
+        assert_send_sync::<PlotLimits>();
+        assert_send_sync::<CustomColormap>();
+        assert_send_sync::<ColormapChoice>();
+        assert_send_sync::<LegendSolo>();
+        assert_send_sync::<LimitChangeDetector>();
+        assert_send_sync::<PlotStateMap<f64>>();
+    }
+
+    #[test]
+    fn test_normalize_pixel_rect() {
+        let top_left = ImVec2 { x: 10.0, y: 20.0 };
+        let bottom_right = ImVec2 { x: 110.0, y: 220.0 };
+        let top_right = ImVec2 { x: 110.0, y: 20.0 };
+        let bottom_left = ImVec2 { x: 10.0, y: 220.0 };
+        let expected = ([10.0, 20.0], [110.0, 220.0]);
+
+        // Regular axes: the minimum of the limits is at the bottom left in pixels
+        assert_rect_eq(normalize_pixel_rect(bottom_left, top_right), expected);
+        // Inverted Y axis: the minimum of the limits is at the top left
+        assert_rect_eq(normalize_pixel_rect(top_left, bottom_right), expected);
+        // Inverted X axis: the minimum of the limits is at the bottom right
+        assert_rect_eq(normalize_pixel_rect(bottom_right, top_left), expected);
+        // Both axes inverted: the minimum of the limits is at the top right
+        assert_rect_eq(normalize_pixel_rect(top_right, bottom_left), expected);
+    }
+
+    #[test]
+    fn test_plot_queries_outside_plot() {
+        // Nothing is hovered or queried outside of a plot
+        assert!(!is_plot_hovered());
+        assert!(!is_plot_queried());
+        assert!(!is_plot_x_axis_hovered());
+        assert!(!is_plot_y_axis_hovered(None));
+        assert!(!is_legend_entry_hovered("entry"));
+        assert_eq!(get_plot_query_checked(None), None);
+        assert_eq!(get_plot_query_pixels(None), None);
+
+        assert_eq!(get_plot_mouse_position_checked(None), None);
+        assert_eq!(get_plot_limits_checked(None), None);
+        assert_eq!(pixels_to_plot_vec2_checked([0.0, 0.0], None), None);
+        assert_eq!(pixels_to_plot_f32_checked(0.0, 0.0, None), None);
+        assert!(plot_to_pixels_vec2_checked([0.0, 0.0], None).is_none());
+        assert!(plot_to_pixels_f32_checked(0.0, 0.0, None).is_none());
+        assert!(get_plot_pos_checked().is_none());
+        assert!(get_plot_size_checked().is_none());
+
+        // The unchecked versions fail a debug assertion, and return zeroed values otherwise
+        let result = std::panic::catch_unwind(|| get_plot_limits(None));
+        if cfg!(debug_assertions) {
+            let message = result.unwrap_err().downcast::<String>().unwrap();
+            assert!(message.contains("get_plot_limits was called outside of a plot"));
+        } else {
+            assert_eq!(result.unwrap(), PlotLimits::default());
+        }
+        let result = std::panic::catch_unwind(|| get_plot_mouse_position(None));
+        assert_eq!(
+            result.ok(),
+            Some(PlotPoint::default()).filter(|_| !cfg!(debug_assertions))
+        );
+        let result = std::panic::catch_unwind(|| pixels_to_plot_vec2([0.0, 0.0], None));
+        assert_eq!(
+            result.ok(),
+            Some(PlotPoint::default()).filter(|_| !cfg!(debug_assertions))
+        );
+        let result = std::panic::catch_unwind(|| plot_to_pixels_vec2([0.0, 0.0], None));
+        assert_eq!(result.is_err(), cfg!(debug_assertions));
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_max_safe_points_follows_the_renderer() {
+        let limit_of_16_bit_indices = if imgui_compat::draw_index_size() > 2 {
+            usize::MAX
+        } else {
+            (1 << 16) / VERTICES_PER_LINE_SEGMENT
+        };
+        crate::test_support::with_test_frame(|_, _| {
+            let io = unsafe { &mut *imgui_sys::igGetIO() };
+            let has_vtx_offset =
+                imgui_sys::ImGuiBackendFlags_RendererHasVtxOffset as imgui_sys::ImGuiBackendFlags;
+            io.BackendFlags &= !has_vtx_offset;
+            assert_eq!(max_safe_points(), limit_of_16_bit_indices);
+            io.BackendFlags |= has_vtx_offset;
+            assert_eq!(max_safe_points(), usize::MAX);
+        });
+        // Without an imgui context, there is no renderer that could support vertex offsets
+        let _guard = crate::test_support::TEST_MUTEX.lock();
+        assert_eq!(max_safe_points(), limit_of_16_bit_indices);
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_tokens_pop_on_drop() {
+        crate::test_support::with_test_frame(|plot_ui, _| {
+            let style = get_style();
+            let colormap_size = get_colormap_size();
+            {
+                let _var = push_style_var(plot_ui, StyleVarValue::LineWeight(7.0));
+                let _color =
+                    push_style_color(plot_ui, PlotColorElement::Line, [1.0, 0.0, 0.0, 1.0]);
+                let _colormap = push_colormap(plot_ui, Colormap::Paired);
+                assert_eq!(get_style().line_weight, 7.0);
+                assert_eq!(
+                    get_style()[PlotColorElement::Line],
+                    [1.0, 0.0, 0.0, 1.0].into()
+                );
+                assert_eq!(get_colormap_size(), 12);
+            }
+            assert_eq!(get_style().line_weight, style.line_weight);
+            assert_eq!(
+                get_style()[PlotColorElement::Line],
+                style[PlotColorElement::Line]
+            );
+            assert_eq!(get_colormap_size(), colormap_size);
+            assert_eq!(stack_check::outstanding_push_count(), 0);
+        });
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_popped_tokens_are_not_popped_again_on_drop() {
+        crate::test_support::with_test_frame(|plot_ui, _| {
+            let style = get_style();
+            let colormap_size = get_colormap_size();
+            let outer_var = push_style_var(plot_ui, StyleVarValue::LineWeight(2.0));
+            let outer_color = push_style_color(plot_ui, PlotColorElement::Fill, [0.0; 4]);
+            let outer_colormap = push_colormap(plot_ui, Colormap::Dark);
+
+            // Popping the inner tokens must leave the outer pushes in effect, which would be
+            // popped as well if dropping the popped tokens popped again
+            push_style_var(plot_ui, StyleVarValue::LineWeight(3.0)).pop();
+            push_style_color(plot_ui, PlotColorElement::Fill, [1.0; 4]).pop();
+            push_colormap(plot_ui, Colormap::Paired).pop();
+            push_style(plot_ui, &[(PlotColorElement::Fill, [1.0; 4].into())], &[]).pop();
+            assert_eq!(get_style().line_weight, 2.0);
+            assert_eq!(get_style()[PlotColorElement::Fill], [0.0; 4].into());
+            assert_eq!(get_colormap_size(), 9);
+
+            outer_var.pop();
+            outer_color.pop();
+            outer_colormap.pop();
+            assert_eq!(get_style().line_weight, style.line_weight);
+            assert_eq!(
+                get_style()[PlotColorElement::Fill],
+                style[PlotColorElement::Fill]
+            );
+            assert_eq!(get_colormap_size(), colormap_size);
+        });
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_tokens_pop_during_unwind() {
+        crate::test_support::with_test_frame(|plot_ui, _| {
+            let style = get_style();
+            let colormap_size = get_colormap_size();
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                let _var = push_style_var(plot_ui, StyleVarValue::LineWeight(7.0));
+                let _color = push_style_color(plot_ui, PlotColorElement::Line, [1.0; 4]);
+                let _colormap = push_colormap(plot_ui, Colormap::Paired);
+                panic!("panic with pushed tokens");
+            }));
+            assert!(result.is_err());
+            assert_eq!(get_style().line_weight, style.line_weight);
+            assert_eq!(
+                get_style()[PlotColorElement::Line],
+                style[PlotColorElement::Line]
+            );
+            assert_eq!(get_colormap_size(), colormap_size);
+            assert_eq!(stack_check::outstanding_push_count(), 0);
+        });
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_style_token_pops_its_colors_and_vars() {
+        crate::test_support::with_test_frame(|plot_ui, _| {
+            let style = get_style();
+            let _outer_var = push_style_var(plot_ui, StyleVarValue::MarkerSize(8.0));
+            let _outer_color = push_style_color(plot_ui, PlotColorElement::Line, [0.0; 4]);
+
+            let token = push_style(
+                plot_ui,
+                &[
+                    (PlotColorElement::Line, [1.0; 4].into()),
+                    (PlotColorElement::Fill, [1.0; 4].into()),
+                    (PlotColorElement::Line, [0.5; 4].into()),
+                ],
+                &[
+                    StyleVarValue::MarkerSize(9.0),
+                    StyleVarValue::LineWeight(3.0),
+                ],
+            );
+            assert_eq!(get_style()[PlotColorElement::Line], [0.5; 4].into());
+            assert_eq!(get_style().marker_size, 9.0);
+
+            // Three colors and two variables are popped, so the outer pushes are back in effect
+            token.pop();
+            assert_eq!(get_style()[PlotColorElement::Line], [0.0; 4].into());
+            assert_eq!(
+                get_style()[PlotColorElement::Fill],
+                style[PlotColorElement::Fill]
+            );
+            assert_eq!(get_style().marker_size, 8.0);
+            assert_eq!(get_style().line_weight, style.line_weight);
+            assert_eq!(stack_check::outstanding_push_count(), 2);
+        });
+    }
+
+    #[test]
+    fn test_marker_name_round_trip() {
+        for marker in Marker::all() {
+            let parsed: Marker = marker.name().parse().unwrap();
+            assert_eq!(parsed.name(), marker.name());
+        }
+        assert!("circle".parse::<Marker>().is_err());
+    }
+
+    #[test]
+    fn test_y_axis_choice_conversions() {
+        for (index, axis) in YAxisChoice::all().iter().enumerate() {
+            assert_eq!(axis.index(), index);
+            assert_eq!(YAxisChoice::try_from(index as i32), Ok(*axis));
+        }
+        for value in &[IMPLOT_AUTO, -2, 3, i32::MAX] {
+            assert_eq!(
+                YAxisChoice::try_from(*value),
+                Err(InvalidYAxisError { value: *value })
+            );
+        }
+    }
+}
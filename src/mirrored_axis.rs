@@ -0,0 +1,255 @@
+//! # Mirrored axis module
+//!
+//! This module contains [`MirroredAxis`], which shows a secondary Y axis in units derived from
+//! the first Y axis, such as degrees Fahrenheit next to degrees Celsius.
+use crate::{
+    compute_ticks, current_plot_id, get_plot_limits, get_plot_size, AxisFlags, Condition,
+    ImPlotRange, Plot, PlotStateMap, YAxisChoice, Y_TICK_SPACING,
+};
+
+/// A secondary Y axis that mirrors the first Y axis through a transform, so both axes stay
+/// locked together while the user pans and zooms. The transform has to be strictly monotonic
+/// over the shown range, and the first Y axis has to use a linear scale. The data is plotted on
+/// the first Y axis as usual; the mirrored axis only shows the transformed values:
+/// ```no_run
+/// # use implot::{MirroredAxis, Plot, PlotLine, PlotUi};
+/// # fn example(plot_ui: &PlotUi, fahrenheit: &mut MirroredAxis, x: &[f64], celsius: &[f64]) {
+/// // Created once, for example in the application state:
+/// // let fahrenheit = MirroredAxis::new(|c| c * 1.8 + 32.0, |f| (f - 32.0) / 1.8);
+/// fahrenheit
+///     .apply(Plot::new("Temperature").y_label("°C"))
+///     .build(plot_ui, || {
+///         PlotLine::new("sensor").plot(x, celsius);
+///         fahrenheit.update();
+///     });
+/// # }
+/// ```
+/// [`MirroredAxis::update`] records the limits of the first Y axis at the end of each frame, and
+/// [`MirroredAxis::apply`] sets the limits and ticks of the mirrored axis from them in the next
+/// frame, with `Condition::Always`. The limits of the mirrored axis are never read back, so
+/// they can't feed back into the first axis, and the mirrored axis is locked against panning
+/// on its own. This means the mirrored axis lags one frame behind, and shows no custom ticks in
+/// the first frame a plot is shown.
+///
+/// For affine transforms, the mirrored axis shows exactly the transformed limits, so data in
+/// the derived units can also be plotted on it. For other transforms, such as decibels, the
+/// ticks are placed where their value lines up with the first axis, and the mirrored axis
+/// should only be used for reading values. Decreasing transforms invert the mirrored axis.
+///
+/// Like [`MeasureTool`](struct.MeasureTool.html), one mirrored axis can be used for several
+/// plots, the recorded limits are kept separately for each plot.
+pub struct MirroredAxis {
+    /// Transform from the units of the first Y axis to the mirrored units
+    forward: Box<dyn Fn(f64) -> f64>,
+    /// Inverse of `forward`
+    inverse: Box<dyn Fn(f64) -> f64>,
+    /// Y axis that shows the mirrored units
+    axis: YAxisChoice,
+    /// Custom formatting for the tick labels, if any
+    formatter: Option<Box<dyn Fn(f64) -> String>>,
+    /// Recorded limits of the first Y axis and the plot height of each plot
+    states: PlotStateMap<MirrorState>,
+}
+
+/// Recorded state of a plot with a mirrored axis.
+#[derive(Default)]
+struct MirrorState {
+    /// Limits of the first Y axis in the most recent frame, if recorded yet
+    source: Option<ImPlotRange>,
+    /// Height of the plot area in pixels in the most recent frame
+    pixels: f32,
+}
+
+impl MirroredAxis {
+    /// Create a mirrored axis on the second Y axis, with the transform from the units of the
+    /// first Y axis to the mirrored units and its inverse.
+    pub fn new<F, G>(forward: F, inverse: G) -> Self
+    where
+        F: Fn(f64) -> f64 + 'static,
+        G: Fn(f64) -> f64 + 'static,
+    {
+        Self {
+            forward: Box::new(forward),
+            inverse: Box::new(inverse),
+            axis: YAxisChoice::Second,
+            formatter: None,
+            states: PlotStateMap::default(),
+        }
+    }
+
+    /// Set the Y axis that shows the mirrored units, the second one by default.
+    ///
+    /// # Panics
+    /// Will panic if the axis is the first Y axis, which is the one being mirrored.
+    pub fn with_axis(mut self, y_axis_choice: YAxisChoice) -> Self {
+        assert!(
+            y_axis_choice != YAxisChoice::First,
+            "The first Y axis can't mirror itself"
+        );
+        self.axis = y_axis_choice;
+        self
+    }
+
+    /// Set a custom formatter for the tick labels of the mirrored axis. By default, the values
+    /// are shown with as many decimals as the tick interval needs.
+    pub fn with_formatter<F: Fn(f64) -> String + 'static>(mut self, formatter: F) -> Self {
+        self.formatter = Some(Box::new(formatter));
+        self
+    }
+
+    /// Enable the mirrored axis on the plot, and set its limits and ticks from the limits of
+    /// the first Y axis recorded in the previous frame. Call this every frame on the finished
+    /// plot configuration, since setting the flags of the mirrored axis afterwards would remove
+    /// the ones added here.
+    pub fn apply(&mut self, plot: Plot) -> Plot {
+        let state = self.states.entry(plot.id());
+        let (source, pixels) = (state.source, state.pixels);
+        let mut flags = AxisFlags::LOCK_MIN | AxisFlags::LOCK_MAX;
+        let mut plot = plot;
+        if let Some(source) = source {
+            if let Some(target) = self.target_limits(&source) {
+                if (self.forward)(source.Max) < (self.forward)(source.Min) {
+                    flags |= AxisFlags::INVERT;
+                }
+                plot = plot
+                    .y_limits(target, self.axis, Condition::Always)
+                    .y_ticks_with_labels(self.axis, &self.ticks(&source, pixels), false);
+            }
+        }
+        plot.enable_y_axis_with_flags(self.axis, flags)
+    }
+
+    /// Record the limits of the first Y axis and the size of the current plot for the next
+    /// frame. Use this in the closure passed to [`Plot::build()`](struct.Plot.html#method.build)
+    /// of a plot that [`MirroredAxis::apply`] was used on, outside of plots this does nothing.
+    pub fn update(&mut self) {
+        let plot_id = match current_plot_id() {
+            Some(plot_id) => plot_id,
+            None => return,
+        };
+        let state = self.states.entry(&plot_id);
+        state.source = Some(get_plot_limits(Some(YAxisChoice::First)).y.into());
+        state.pixels = get_plot_size().y;
+    }
+
+    /// Returns the limits of the mirrored axis for the given limits of the first Y axis, in
+    /// increasing order. Returns `None` if the transform gives values that are not finite, or
+    /// the same value for both limits.
+    pub fn target_limits(&self, source: &ImPlotRange) -> Option<ImPlotRange> {
+        let target = ImPlotRange {
+            Min: (self.forward)(source.Min),
+            Max: (self.forward)(source.Max),
+        }
+        .normalized();
+        if target.Min.is_finite() && target.Max.is_finite() && target.Min < target.Max {
+            Some(target)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the ticks of the mirrored axis for the given limits of the first Y axis and the
+    /// height of the plot in pixels, as positions on the mirrored axis with their labels. The
+    /// tick values are nice numbers in the mirrored units, see [`compute_ticks`].
+    pub fn ticks(&self, source: &ImPlotRange, pixels: f32) -> Vec<(f64, String)> {
+        let target = match self.target_limits(source) {
+            Some(target) => target,
+            None => return Vec::new(),
+        };
+        let (target_min, target_max) = ((self.forward)(source.Min), (self.forward)(source.Max));
+        let values = compute_ticks(&target, pixels, Y_TICK_SPACING);
+        let decimals = label_decimals(&values);
+        values
+            .iter()
+            .map(|value| {
+                // Place the tick at the height of the corresponding value of the first axis,
+                // which is the value itself for affine transforms
+                let fraction = ((self.inverse)(*value) - source.Min) / (source.Max - source.Min);
+                let position = target_min + fraction * (target_max - target_min);
+                let label = match &self.formatter {
+                    Some(formatter) => formatter(*value),
+                    None => format!("{:.*}", decimals, value),
+                };
+                (position, label)
+            })
+            .collect()
+    }
+}
+
+/// Internal helper returning the number of decimals needed to tell the tick values apart.
+fn label_decimals(values: &[f64]) -> usize {
+    match values {
+        [first, second, ..] => (-(second - first).log10().floor()).max(0.0) as usize,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(min: f64, max: f64) -> ImPlotRange {
+        ImPlotRange { Min: min, Max: max }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_affine_round_trip() {
+        let fahrenheit = MirroredAxis::new(|c| c * 1.8 + 32.0, |f| (f - 32.0) / 1.8);
+        let target = fahrenheit.target_limits(&range(0.0, 100.0)).unwrap();
+        assert_close(target.Min, 32.0);
+        assert_close(target.Max, 212.0);
+
+        let ticks = fahrenheit.ticks(&range(0.0, 100.0), 900.0);
+        let labels: Vec<&str> = ticks.iter().map(|(_, label)| label.as_str()).collect();
+        assert_eq!(labels, ["100", "200"]);
+        for (position, label) in &ticks {
+            // Affine transforms put each tick at its own value
+            assert_close(*position, label.parse().unwrap());
+            let celsius = (fahrenheit.inverse)(*position);
+            assert_close((fahrenheit.forward)(celsius), *position);
+        }
+    }
+
+    #[test]
+    fn test_decreasing_transform() {
+        let negated = MirroredAxis::new(|x| -x, |x| -x);
+        let target = negated.target_limits(&range(0.0, 10.0)).unwrap();
+        assert_eq!((target.Min, target.Max), (-10.0, 0.0));
+        let ticks = negated.ticks(&range(0.0, 10.0), 900.0);
+        let positions: Vec<f64> = ticks.iter().map(|(position, _)| *position).collect();
+        assert_eq!(positions, [-10.0, -5.0, 0.0]);
+    }
+
+    #[test]
+    fn test_nonlinear_transform() {
+        let decibels = MirroredAxis::new(|x| 20.0 * x.log10(), |db| 10f64.powf(db / 20.0));
+        let ticks = decibels.ticks(&range(1.0, 100.0), 900.0);
+        let labels: Vec<&str> = ticks.iter().map(|(_, label)| label.as_str()).collect();
+        assert_eq!(labels, ["0", "20", "40"]);
+        // 20 dB is a factor of 10, which is 9/99 of the way up the first axis
+        assert_close(ticks[0].0, 0.0);
+        assert_close(ticks[1].0, 40.0 * 9.0 / 99.0);
+        assert_close(ticks[2].0, 40.0);
+
+        // Limits where the transform is not finite give nothing
+        assert!(decibels.target_limits(&range(0.0, 100.0)).is_none());
+        assert!(decibels.ticks(&range(0.0, 100.0), 900.0).is_empty());
+    }
+
+    #[test]
+    fn test_label_decimals() {
+        assert_eq!(label_decimals(&[0.0, 5.0, 10.0]), 0);
+        assert_eq!(label_decimals(&[0.1, 0.2, 0.30000000000000004]), 1);
+        assert_eq!(label_decimals(&[0.0, 0.05]), 2);
+        assert_eq!(label_decimals(&[1.0]), 0);
+    }
+}
@@ -0,0 +1,554 @@
+//! # Overlays module
+//!
+//! This module contains helpers that draw things on top of a plot to help with inspecting the
+//! data in it, such as highlighting the data point closest to the mouse. Like the plot elements,
+//! these are meant to be used in closures passed to
+//! [`Plot::build()`](struct.Plot.html#method.build).
+use crate::context::debug_check_thread;
+pub(crate) use crate::imgui_compat::show_tooltip;
+use crate::imgui_compat::{calc_text_size, mouse_clicked, mouse_down, MouseButton, PlotDrawList};
+use crate::plot::check_inside_plot;
+use crate::sys;
+use crate::{
+    current_plot_id, get_plot_limits, get_plot_mouse_position, get_plot_pos, get_plot_size,
+    is_plot_hovered, nearest_point_index, plot_to_pixels_vec2, set_next_marker_style, ImVec2,
+    ImVec4, KeyModifiers, Marker, PlotLocation, PlotPoint, PlotScatter, PlotStateMap,
+};
+
+/// Style of the marker drawn by [`highlight_nearest_point`].
+#[derive(Clone, Debug)]
+pub struct HighlightStyle {
+    /// Marker used for the highlighted point
+    marker: Marker,
+    /// Size of the marker in pixels
+    marker_size: f32,
+    /// Outline weight of the marker in pixels
+    outline_weight: f32,
+    /// Outline color of the marker
+    outline_color: ImVec4,
+    /// Fill color of the marker
+    fill_color: ImVec4,
+    /// Whether to show a tooltip with the coordinates of the highlighted point
+    show_tooltip: bool,
+}
+
+impl HighlightStyle {
+    /// Create a new highlight style with defaults that stand out against the usual plot markers.
+    pub fn new() -> Self {
+        Self {
+            marker: Marker::Circle,
+            marker_size: 8.0,
+            outline_weight: 2.0,
+            outline_color: ImVec4 {
+                x: 1.0,
+                y: 1.0,
+                z: 1.0,
+                w: 1.0,
+            },
+            fill_color: ImVec4 {
+                x: 1.0,
+                y: 1.0,
+                z: 1.0,
+                w: 0.25,
+            },
+            show_tooltip: true,
+        }
+    }
+
+    /// Set the marker used for the highlighted point
+    pub fn with_marker(mut self, marker: Marker) -> Self {
+        self.marker = marker;
+        self
+    }
+
+    /// Set the size of the marker, in pixels
+    pub fn with_marker_size(mut self, marker_size: f32) -> Self {
+        self.marker_size = marker_size;
+        self
+    }
+
+    /// Set the outline weight of the marker, in pixels
+    pub fn with_outline_weight(mut self, outline_weight: f32) -> Self {
+        self.outline_weight = outline_weight;
+        self
+    }
+
+    /// Set the outline and fill colors of the marker
+    pub fn with_colors<C: Into<ImVec4>, D: Into<ImVec4>>(
+        mut self,
+        outline_color: C,
+        fill_color: D,
+    ) -> Self {
+        self.outline_color = outline_color.into();
+        self.fill_color = fill_color.into();
+        self
+    }
+
+    /// Set whether a tooltip with the coordinates of the highlighted point is shown
+    pub fn with_tooltip(mut self, show_tooltip: bool) -> Self {
+        self.show_tooltip = show_tooltip;
+        self
+    }
+}
+
+impl Default for HighlightStyle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Highlight the point given by `x` and `y` that is closest to the mouse (as seen on screen) by
+/// drawing an emphasized marker on it, and optionally show a tooltip with its coordinates.
+/// Returns the index of the highlighted point, or `None` if the plot is not hovered or there is
+/// no data. Nothing is computed if the plot is not hovered, so this is cheap to call every frame.
+///
+/// The marker does not get a legend entry. Use this in closures passed to
+/// [`Plot::build()`](struct.Plot.html#method.build), after the data itself has been plotted so
+/// the marker is drawn on top of it.
+#[track_caller]
+pub fn highlight_nearest_point(x: &[f64], y: &[f64], style: &HighlightStyle) -> Option<usize> {
+    if !is_plot_hovered() {
+        return None;
+    }
+
+    // Compare distances in pixels rather than in plot units, since the axes can have vastly
+    // different scales and what matters is what looks closest to the user.
+    let limits = get_plot_limits(None);
+    let size = get_plot_size();
+    let x_scale = (size.x as f64 / limits.x.size()).abs();
+    let y_scale = (size.y as f64 / limits.y.size()).abs();
+    let index = nearest_point_index(x, y, get_plot_mouse_position(None), x_scale, y_scale)?;
+
+    set_next_marker_style(
+        Some(style.marker),
+        Some(style.marker_size),
+        Some(style.fill_color),
+        Some(style.outline_weight),
+        Some(style.outline_color),
+    );
+    // Labels starting with "##" don't get a legend entry in ImPlot
+    PlotScatter::new("##highlighted point").plot(&[x[index]], &[y[index]]);
+
+    if style.show_tooltip {
+        show_tooltip(&format!("x = {:.3}, y = {:.3}", x[index], y[index]));
+    }
+
+    Some(index)
+}
+
+/// Draw text inside the plot area at the given location, on top of a translucent background
+/// box. The text can span multiple lines. The box keeps the same distance from the plot edges as
+/// the mouse position text (see `StyleVar::MousePosPadding`), and the text is padded within the
+/// box by the annotation padding (see `StyleVar::AnnotationPadding`).
+///
+/// This is useful for readouts with custom formatting, e.g. in combination with
+/// [`PlotFlags::NO_MOUSE_POSITION`](struct.PlotFlags.html) and
+/// [`get_plot_mouse_position`](fn.get_plot_mouse_position.html). Use this in closures passed to
+/// [`Plot::build()`](struct.Plot.html#method.build).
+#[track_caller]
+pub fn plot_overlay_text(location: PlotLocation, text: &str) {
+    plot_overlay_text_colored(location, text, [1.0, 1.0, 1.0, 1.0], [0.0, 0.0, 0.0, 0.5]);
+}
+
+/// Like [`plot_overlay_text`], but with custom colors for the text and the background box. The
+/// colors can be anything that converts into an `ImVec4`, such as `[f32; 4]`.
+#[track_caller]
+pub fn plot_overlay_text_colored(
+    location: PlotLocation,
+    text: &str,
+    text_color: impl Into<ImVec4>,
+    background_color: impl Into<ImVec4>,
+) {
+    debug_check_thread();
+    if !check_inside_plot("plot_overlay_text") {
+        return;
+    }
+    let edge_padding = unsafe {
+        let style = sys::ImPlot_GetStyle();
+        assert_ne!(style, std::ptr::null_mut());
+        (*style).MousePosPadding
+    };
+    let box_size = text_box_size(text);
+
+    // PlotLocation is a combination of direction bits, with Center being none of them.
+    let plot_pos = get_plot_pos();
+    let plot_size = get_plot_size();
+    let location_bits = location as u32;
+    let box_x = if location_bits & sys::ImPlotLocation__ImPlotLocation_West != 0 {
+        plot_pos.x + edge_padding.x
+    } else if location_bits & sys::ImPlotLocation__ImPlotLocation_East != 0 {
+        plot_pos.x + plot_size.x - edge_padding.x - box_size.x
+    } else {
+        plot_pos.x + 0.5 * (plot_size.x - box_size.x)
+    };
+    let box_y = if location_bits & sys::ImPlotLocation__ImPlotLocation_North != 0 {
+        plot_pos.y + edge_padding.y
+    } else if location_bits & sys::ImPlotLocation__ImPlotLocation_South != 0 {
+        plot_pos.y + plot_size.y - edge_padding.y - box_size.y
+    } else {
+        plot_pos.y + 0.5 * (plot_size.y - box_size.y)
+    };
+
+    draw_text_box(
+        ImVec2 { x: box_x, y: box_y },
+        text,
+        text_color.into(),
+        background_color.into(),
+    );
+}
+
+/// Internal helper returning the annotation padding of the current style.
+fn text_padding() -> ImVec2 {
+    unsafe {
+        let style = sys::ImPlot_GetStyle();
+        assert_ne!(style, std::ptr::null_mut());
+        (*style).AnnotationPadding
+    }
+}
+
+/// Internal helper computing the size of a text box as drawn by [`draw_text_box`].
+fn text_box_size(text: &str) -> ImVec2 {
+    let text_size = calc_text_size(text);
+    let padding = text_padding();
+    ImVec2 {
+        x: text_size.x + 2.0 * padding.x,
+        y: text_size.y + 2.0 * padding.y,
+    }
+}
+
+/// Internal helper drawing text on a background box with its top left corner at the given pixel
+/// position, clipped to the plot area.
+fn draw_text_box(top_left: ImVec2, text: &str, text_color: ImVec4, background_color: ImVec4) {
+    let box_size = text_box_size(text);
+    let padding = text_padding();
+    let draw_list = PlotDrawList::current();
+    unsafe {
+        sys::ImPlot_PushPlotClipRect();
+    }
+    draw_list.add_rect_filled(
+        top_left,
+        ImVec2 {
+            x: top_left.x + box_size.x,
+            y: top_left.y + box_size.y,
+        },
+        background_color,
+    );
+    draw_list.add_text(
+        ImVec2 {
+            x: top_left.x + padding.x,
+            y: top_left.y + padding.y,
+        },
+        text_color,
+        text,
+    );
+    unsafe {
+        sys::ImPlot_PopPlotClipRect();
+    }
+}
+
+/// Format a duration given in seconds as e.g. `1d 02:03:04.500`, leaving out the days if there
+/// are none. Negative durations get a leading minus sign. This is meant as a formatter for
+/// differences along time axes, see [`MeasureTool::with_x_formatter`].
+pub fn format_duration(seconds: f64) -> String {
+    let sign = if seconds < 0.0 { "-" } else { "" };
+    let total_milliseconds = (seconds.abs() * 1000.0).round() as u64;
+    let milliseconds = total_milliseconds % 1000;
+    let total_seconds = total_milliseconds / 1000;
+    let (days, hours, minutes, seconds) = (
+        total_seconds / 86400,
+        (total_seconds / 3600) % 24,
+        (total_seconds / 60) % 60,
+        total_seconds % 60,
+    );
+    if days > 0 {
+        format!(
+            "{}{}d {:02}:{:02}:{:02}.{:03}",
+            sign, days, hours, minutes, seconds, milliseconds
+        )
+    } else {
+        format!(
+            "{}{:02}:{:02}:{:02}.{:03}",
+            sign, hours, minutes, seconds, milliseconds
+        )
+    }
+}
+
+/// The result of a measurement made with a [`MeasureTool`].
+#[derive(Copy, Clone, Debug)]
+pub struct Measurement {
+    /// Point where the measurement was started
+    pub start: PlotPoint,
+    /// Point where the measurement ends
+    pub end: PlotPoint,
+    /// Difference in X between the end and the start point
+    pub delta_x: f64,
+    /// Difference in Y between the end and the start point
+    pub delta_y: f64,
+    /// Slope of the line between the two points. This is infinite or NaN if `delta_x` is zero.
+    pub slope: f64,
+}
+
+/// Per-plot state of a [`MeasureTool`].
+#[derive(Default)]
+struct MeasureState {
+    /// Point where the measurement was started, if any
+    start: Option<PlotPoint>,
+    /// Point where the measurement ends, if any
+    end: Option<PlotPoint>,
+    /// Whether the end point is currently being dragged
+    dragging: bool,
+}
+
+impl MeasureState {
+    /// Returns the measurement described by this state, if there is one.
+    fn measurement(&self) -> Option<Measurement> {
+        let (start, end) = (self.start?, self.end?);
+        let delta_x = end.x - start.x;
+        let delta_y = end.y - start.y;
+        Some(Measurement {
+            start,
+            end,
+            delta_x,
+            delta_y,
+            slope: delta_y / delta_x,
+        })
+    }
+}
+
+/// Oscilloscope-style measurement tool. While the plot is hovered, dragging with the configured
+/// mouse button and modifier keys held (left mouse button with shift by default) measures the
+/// distance between two points of the plot. The measurement is shown as a line with an overlay
+/// of the differences and the slope, and persists until the next measurement is started, until
+/// the right mouse button is clicked with the modifier keys held, or until [`MeasureTool::clear`]
+/// is called.
+///
+/// Note that ImPlot's own mouse interactions (such as panning with the left mouse button) still
+/// apply while measuring, so it can be useful to pick a button and modifier combination that does
+/// not clash with them.
+///
+/// The tool has to be stored somewhere that persists across frames, and [`MeasureTool::show`]
+/// has to be called in the closure passed to [`Plot::build()`](struct.Plot.html#method.build).
+/// One tool can be used for several plots: the measurements are kept separately for each plot in
+/// a [`PlotStateMap`], keyed by the plot ID, and are dropped once a plot has not been shown for a
+/// while.
+/// ```no_run
+/// # use implot::{format_duration, MeasureTool, Plot, PlotUi};
+/// # fn example(plot_ui: &PlotUi, measure_tool: &mut MeasureTool) {
+/// // Created once, for example in the application state:
+/// // let measure_tool = MeasureTool::new().with_x_formatter(format_duration);
+/// Plot::new("Measured plot").build(plot_ui, || {
+///     // Plot some things
+///     measure_tool.show();
+/// });
+/// let measurement = measure_tool.measurement("Measured plot");
+/// # }
+/// ```
+pub struct MeasureTool {
+    /// Measurement state of each plot the tool is used in
+    states: PlotStateMap<MeasureState>,
+    /// Mouse button used for measuring
+    button: MouseButton,
+    /// Modifier keys that have to be held for measuring
+    modifiers: KeyModifiers,
+    /// Custom formatting for the X difference, if any
+    x_formatter: Option<Box<dyn Fn(f64) -> String>>,
+    /// Color of the measurement line and the anchor markers
+    color: ImVec4,
+}
+
+impl MeasureTool {
+    /// Create a new measurement tool, measuring with the left mouse button while shift is held.
+    pub fn new() -> Self {
+        Self {
+            states: PlotStateMap::default(),
+            button: MouseButton::Left,
+            modifiers: KeyModifiers::SHIFT,
+            x_formatter: None,
+            color: ImVec4 {
+                x: 1.0,
+                y: 1.0,
+                z: 0.0,
+                w: 1.0,
+            },
+        }
+    }
+
+    /// Set the mouse button and modifier keys used for measuring.
+    pub fn with_gesture(mut self, button: MouseButton, modifiers: KeyModifiers) -> Self {
+        self.button = button;
+        self.modifiers = modifiers;
+        self
+    }
+
+    /// Set a custom formatter for the X difference, for example [`format_duration`] for time
+    /// axes. By default, the difference is shown as a plain number.
+    pub fn with_x_formatter<F: Fn(f64) -> String + 'static>(mut self, formatter: F) -> Self {
+        self.x_formatter = Some(Box::new(formatter));
+        self
+    }
+
+    /// Set the color of the measurement line and the anchor markers.
+    pub fn with_color<C: Into<ImVec4>>(mut self, color: C) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Returns the current measurement in the plot with the given ID, if there is one.
+    pub fn measurement(&self, plot_id: &str) -> Option<Measurement> {
+        self.states.get(plot_id)?.measurement()
+    }
+
+    /// Returns true while a measurement is being dragged in the plot with the given ID.
+    pub fn is_measuring(&self, plot_id: &str) -> bool {
+        matches!(self.states.get(plot_id), Some(state) if state.dragging)
+    }
+
+    /// Remove the measurement in the plot with the given ID.
+    pub fn clear(&mut self, plot_id: &str) {
+        self.states.remove(plot_id);
+    }
+
+    /// Remove the measurements in all plots.
+    pub fn clear_all(&mut self) {
+        self.states.clear();
+    }
+
+    /// Handle the mouse input for measuring and draw the current measurement, if any. Use this in
+    /// closures passed to [`Plot::build()`](struct.Plot.html#method.build), outside of plots
+    /// this does nothing.
+    pub fn show(&mut self) {
+        let plot_id = match current_plot_id() {
+            Some(plot_id) => plot_id,
+            None => return,
+        };
+        let state = self.states.entry(&plot_id);
+        Self::handle_input(state, self.button, self.modifiers);
+        if let Some(measurement) = state.measurement() {
+            self.draw(&measurement);
+        }
+    }
+
+    /// Internal helper that updates the measurement points from the imgui mouse state.
+    fn handle_input(state: &mut MeasureState, button: MouseButton, modifiers: KeyModifiers) {
+        let modifiers_held = KeyModifiers::current().contains(modifiers);
+
+        if state.dragging {
+            state.end = Some(get_plot_mouse_position(None));
+            if !mouse_down(button) {
+                state.dragging = false;
+            }
+        } else if is_plot_hovered() && modifiers_held {
+            if mouse_clicked(button) {
+                let position = get_plot_mouse_position(None);
+                state.start = Some(position);
+                state.end = Some(position);
+                state.dragging = true;
+            } else if mouse_clicked(MouseButton::Right) {
+                *state = MeasureState::default();
+            }
+        }
+    }
+
+    /// Internal helper that draws the measurement line and the overlay with the values.
+    fn draw(&self, measurement: &Measurement) {
+        let start = plot_to_pixels_vec2(measurement.start, None);
+        let end = plot_to_pixels_vec2(measurement.end, None);
+        let draw_list = PlotDrawList::current();
+        unsafe {
+            sys::ImPlot_PushPlotClipRect();
+        }
+        draw_list.add_line(start, end, self.color, 1.5);
+        draw_list.add_circle_filled(start, 3.0, self.color);
+        draw_list.add_circle_filled(end, 3.0, self.color);
+        unsafe {
+            sys::ImPlot_PopPlotClipRect();
+        }
+
+        let delta_x = match &self.x_formatter {
+            Some(formatter) => formatter(measurement.delta_x),
+            None => format!("{:.4}", measurement.delta_x),
+        };
+        let text = format!(
+            "dx = {}\ndy = {:.4}\nslope = {:.4}",
+            delta_x, measurement.delta_y, measurement.slope
+        );
+        draw_text_box(
+            ImVec2 {
+                x: end.x + 8.0,
+                y: end.y + 8.0,
+            },
+            &text,
+            ImVec4 {
+                x: 1.0,
+                y: 1.0,
+                z: 1.0,
+                w: 1.0,
+            },
+            ImVec4 {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+                w: 0.5,
+            },
+        );
+    }
+}
+
+impl Default for MeasureTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0.0), "00:00:00.000");
+        assert_eq!(format_duration(1.5), "00:00:01.500");
+        assert_eq!(format_duration(3723.25), "01:02:03.250");
+        assert_eq!(format_duration(93784.5), "1d 02:03:04.500");
+        assert_eq!(format_duration(-61.0), "-00:01:01.000");
+        // Milliseconds are rounded, which can carry over into the seconds
+        assert_eq!(format_duration(59.9996), "00:01:00.000");
+        assert_eq!(format_duration(0.0004), "00:00:00.000");
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_measure_tool_measurements() {
+        crate::test_support::with_test_frame(|_, _| {
+            let mut tool = MeasureTool::new();
+            assert!(tool.measurement("plot").is_none());
+
+            // A started measurement with no end point yet has no result
+            tool.states.entry("plot").start = Some(PlotPoint::new(1.0, 2.0));
+            assert!(tool.measurement("plot").is_none());
+
+            let state = tool.states.entry("plot");
+            state.end = Some(PlotPoint::new(3.0, 8.0));
+            state.dragging = true;
+            let measurement = tool.measurement("plot").unwrap();
+            assert_eq!((measurement.delta_x, measurement.delta_y), (2.0, 6.0));
+            assert_eq!(measurement.slope, 3.0);
+            assert!(tool.is_measuring("plot"));
+            assert!(!tool.is_measuring("other plot"));
+
+            // Vertical measurements have an infinite slope
+            let state = tool.states.entry("other plot");
+            state.start = Some(PlotPoint::new(1.0, 2.0));
+            state.end = Some(PlotPoint::new(1.0, 0.0));
+            let slope = tool.measurement("other plot").unwrap().slope;
+            assert_eq!(slope, f64::NEG_INFINITY);
+
+            tool.clear("plot");
+            assert!(tool.measurement("plot").is_none());
+            assert!(!tool.is_measuring("plot"));
+            assert!(tool.measurement("other plot").is_some());
+            tool.clear_all();
+            assert!(tool.measurement("other plot").is_none());
+        });
+    }
+}
@@ -2,9 +2,14 @@
 //!
 //! This module defines the `Plot` struct, which is used to create a 2D plot that will
 //! contain all other objects that can be created using this library.
-use crate::{Context, PlotLocation, PlotOrientation, PlotUi, YAxisChoice, NUMBER_OF_Y_AXES};
+use crate::frame_stats::{record_plot_begun, record_style_pushes};
+pub use crate::imgui_compat::Condition;
+use crate::stack_check::{outstanding_push_count, pop_pushes_since};
+use crate::{
+    push_style_var_value, Context, PlotColorElement, PlotLocation, PlotOrientation, PlotUi,
+    StyleVarValue, YAxisChoice, NUMBER_OF_Y_AXES,
+};
 use bitflags::bitflags;
-pub use imgui::Condition;
 use implot_sys as sys;
 use std::ffi::CString;
 use std::os::raw::c_char;
@@ -14,6 +19,43 @@ pub use sys::{ImPlotLimits, ImPlotPoint, ImPlotRange, ImVec2, ImVec4};
 const DEFAULT_PLOT_SIZE_X: f32 = 400.0;
 const DEFAULT_PLOT_SIZE_Y: f32 = 400.0;
 
+thread_local! {
+    /// ID of the plot that is currently being built on this thread, if any. ImPlot does not
+    /// expose this through its public API, so it is tracked by `Plot::begin` and `PlotToken::end`.
+    static CURRENT_PLOT_ID: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// Returns the ID of the plot that is currently being built, which is the full title string it
+/// was created with. Returns `None` outside of plots. This is the ID used by the stateful helpers
+/// of this crate to look up their state in a [`PlotStateMap`](struct.PlotStateMap.html).
+pub fn current_plot_id() -> Option<String> {
+    CURRENT_PLOT_ID.with(|id| id.borrow().clone())
+}
+
+/// Returns true if a plot is currently being built on this thread, which is when the functions
+/// that query the current plot return meaningful values.
+pub(crate) fn is_inside_plot() -> bool {
+    CURRENT_PLOT_ID.with(|id| id.borrow().is_some())
+}
+
+/// Internal helper for functions that ImPlot only allows between `BeginPlot` and `EndPlot`,
+/// which is where the closure passed to [`Plot::build`] runs. Returns true inside of a plot.
+/// Outside of plots, ImPlot would fail an assertion and abort the process, so this fails a
+/// debug assertion naming the function and the location it was called from instead, and
+/// returns false so that release builds can skip the call. The callers are `#[track_caller]`,
+/// so the location is the one in user code.
+#[track_caller]
+pub(crate) fn check_inside_plot(function: &str) -> bool {
+    let inside_plot = is_inside_plot();
+    debug_assert!(
+        inside_plot,
+        "{} was called outside of a plot at {}, use it in the closure passed to Plot::build()",
+        function,
+        std::panic::Location::caller()
+    );
+    inside_plot
+}
+
 #[rustversion::attr(since(1.48), doc(alias = "ImPlotFlags"))]
 bitflags! {
     /// Flags for customizing plot behavior and interaction. Documentation copied from implot.h for
@@ -79,6 +121,85 @@ bitflags! {
     }
 }
 
+/// Choice of any axis of a plot, for settings that work the same way on all axes, such as
+/// [`Plot::with_axis`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PlotAxis {
+    /// The X axis
+    X,
+    /// One of the Y axes
+    Y(YAxisChoice),
+}
+
+impl From<YAxisChoice> for PlotAxis {
+    fn from(y_axis_choice: YAxisChoice) -> Self {
+        PlotAxis::Y(y_axis_choice)
+    }
+}
+
+/// Settings for one axis of a plot, applied with [`Plot::with_axis`]. This allows building
+/// axis configurations from data, for example from a table of signals with their units and
+/// preferred limits, and reusing them for several plots:
+/// ```no_run
+/// # use implot::{AxisFlags, AxisSettings, Condition, Plot, PlotAxis, PlotUi, YAxisChoice};
+/// # fn example(plot_ui: &PlotUi) {
+/// let pressure = AxisSettings::new()
+///     .with_label("pressure [Pa]")
+///     .with_limits((1e3, 1e6), Condition::FirstUseEver)
+///     .with_flags(AxisFlags::LOG_SCALE);
+/// Plot::new("Pressure over time")
+///     .with_axis(PlotAxis::Y(YAxisChoice::First), pressure.clone())
+///     .build(plot_ui, || {
+///         // Plot the pressure
+///     });
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct AxisSettings {
+    /// Label of the axis. The version of ImPlot bundled with this crate only shows labels for
+    /// the X axis and the first Y axis, so labels of the other Y axes are not shown.
+    pub label: Option<String>,
+    /// Limits of the axis and the condition for setting them
+    pub limits: Option<(ImPlotRange, Condition)>,
+    /// Flags of the axis
+    pub flags: AxisFlags,
+}
+
+impl AxisSettings {
+    /// Create settings without a label or limits, and without any flags set.
+    pub fn new() -> Self {
+        Self {
+            label: None,
+            limits: None,
+            flags: AxisFlags::NONE,
+        }
+    }
+
+    /// Set the label of the axis, see [`AxisSettings::label`].
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_owned());
+        self
+    }
+
+    /// Set the limits of the axis, which are set with the given condition.
+    pub fn with_limits<L: Into<ImPlotRange>>(mut self, limits: L, condition: Condition) -> Self {
+        self.limits = Some((limits.into(), condition));
+        self
+    }
+
+    /// Set the flags of the axis.
+    pub fn with_flags(mut self, flags: AxisFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+}
+
+impl Default for AxisSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Internally-used struct for storing axis limits
 #[derive(Clone)]
 enum AxisLimitSpecification {
@@ -93,14 +214,15 @@ enum AxisLimitSpecification {
 /// `Plot` is to be used (within an imgui window) with the following pattern:
 /// ```no_run
 /// # use implot;
+/// # fn example(ui: &imgui::Ui) {
 /// let plotting_context = implot::Context::create();
-/// let plot_ui = plotting_context.get_plot_ui();
+/// let plot_ui = plotting_context.get_plot_ui(ui);
 /// implot::Plot::new("my title")
 ///     .size([300.0, 200.0]) // other things such as .x_label("some_label") can be added too
 ///     .build(&plot_ui, || {
 ///         // Do things such as plotting lines
 ///     });
-///
+/// # }
 /// ```
 /// (If you are coming from the C++ implementation or the C bindings: build() calls both
 /// begin() and end() internally)
@@ -154,6 +276,10 @@ pub struct Plot {
     x_flags: sys::ImPlotAxisFlags,
     /// Flags relating to the each of the Y axes of the plot TODO(4bb4) make those into bitflags
     y_flags: [sys::ImPlotAxisFlags; NUMBER_OF_Y_AXES],
+    /// Style colors that are pushed for the duration of this plot only
+    color_overrides: Vec<(PlotColorElement, ImVec4)>,
+    /// Style variables that are pushed for the duration of this plot only
+    style_overrides: Vec<StyleVarValue>,
 }
 
 impl Plot {
@@ -187,17 +313,26 @@ impl Plot {
             plot_flags: PlotFlags::ANTIALIASED.bits() as sys::ImPlotFlags,
             x_flags: AxisFlags::NONE.bits() as sys::ImPlotAxisFlags,
             y_flags: [AxisFlags::NONE.bits() as sys::ImPlotAxisFlags; NUMBER_OF_Y_AXES],
+            color_overrides: Vec::new(),
+            style_overrides: Vec::new(),
         }
     }
 
-    /// Sets the plot size, given as [size_x, size_y]. Units are the same as
-    /// what imgui uses. TODO(4bb4) ... which is? I'm not sure it's pixels
+    /// Sets the plot size, given as [size_x, size_y] or anything else that converts into an
+    /// `ImVec2`. Units are the same as what imgui uses. TODO(4bb4) ... which is? I'm not sure
+    /// it's pixels
     #[inline]
-    pub fn size(mut self, size: [f32; 2]) -> Self {
-        self.size = size;
+    pub fn size<S: Into<ImVec2>>(mut self, size: S) -> Self {
+        let size = size.into();
+        self.size = [size.x, size.y];
         self
     }
 
+    /// Internal helper that returns the size set with [`Plot::size`], or the default size.
+    pub(crate) fn configured_size(&self) -> [f32; 2] {
+        self.size
+    }
+
     /// Set the x label of the plot
     ///
     /// # Panics
@@ -254,7 +389,7 @@ impl Plot {
         y_axis_choice: YAxisChoice,
         condition: Condition,
     ) -> Self {
-        let axis_index = y_axis_choice as usize;
+        let axis_index = y_axis_choice.index();
         self.y_limits[axis_index] = Some(AxisLimitSpecification::Single(limits.into(), condition));
         self
     }
@@ -293,7 +428,7 @@ impl Plot {
         limits: Rc<RefCell<ImPlotRange>>,
         y_axis_choice: YAxisChoice,
     ) -> Self {
-        let axis_index = y_axis_choice as usize;
+        let axis_index = y_axis_choice.index();
         self.y_limits[axis_index] = Some(AxisLimitSpecification::Linked(limits));
         self
     }
@@ -342,7 +477,7 @@ impl Plot {
         ticks: &[f64],
         show_default: bool,
     ) -> Self {
-        let axis_index = y_axis_choice as usize;
+        let axis_index = y_axis_choice.index();
         self.y_tick_positions[axis_index] = Some(ticks.into());
         self.show_y_default_ticks[axis_index] = show_default;
         self
@@ -387,7 +522,7 @@ impl Plot {
         tick_labels: &[(f64, String)],
         show_default: bool,
     ) -> Self {
-        let axis_index = y_axis_choice as usize;
+        let axis_index = y_axis_choice.index();
         self.y_tick_positions[axis_index] = Some(tick_labels.iter().map(|x| x.0).collect());
         self.y_tick_labels[axis_index] = Some(
             tick_labels
@@ -419,11 +554,66 @@ impl Plot {
     /// Set the axis flags for the selected Y axis in this plot
     #[inline]
     pub fn with_y_axis_flags(mut self, y_axis_choice: YAxisChoice, flags: &AxisFlags) -> Self {
-        let axis_index = y_axis_choice as usize;
+        let axis_index = y_axis_choice.index();
         self.y_flags[axis_index] = flags.bits() as sys::ImPlotAxisFlags;
         self
     }
 
+    /// Internal helper returning the ID of the plot, which is its full title.
+    pub(crate) fn id(&self) -> &str {
+        // The title was created from a &str, so it is valid UTF-8
+        self.title.to_str().unwrap_or_default()
+    }
+
+    /// Internal helper that enables the given Y axis and adds the flags to it, keeping the flags
+    /// that are already set.
+    pub(crate) fn enable_y_axis_with_flags(
+        mut self,
+        y_axis_choice: YAxisChoice,
+        flags: AxisFlags,
+    ) -> Self {
+        let plot_flag = match y_axis_choice {
+            YAxisChoice::First => PlotFlags::NONE,
+            YAxisChoice::Second => PlotFlags::Y_AXIS_2,
+            YAxisChoice::Third => PlotFlags::Y_AXIS_3,
+        };
+        self.plot_flags |= plot_flag.bits() as sys::ImPlotFlags;
+        self.y_flags[y_axis_choice.index()] |= flags.bits() as sys::ImPlotAxisFlags;
+        self
+    }
+
+    /// Apply the settings to the given axis. The flags always replace those of the axis, while
+    /// the label and the limits are only set if they are present in the settings - so linked
+    /// limits set with [`Plot::linked_x_limits`] or [`Plot::linked_y_limits`] are kept if the
+    /// settings have no limits. Like with the other limit setters, whichever limits are set last
+    /// take effect. Ticks are not part of the settings, set them with the tick functions.
+    ///
+    /// # Panics
+    /// Will panic if the label of the X axis or the first Y axis contains internal null bytes.
+    pub fn with_axis(mut self, axis: PlotAxis, settings: AxisSettings) -> Self {
+        match axis {
+            PlotAxis::X => {
+                if let Some(label) = &settings.label {
+                    self = self.x_label(label);
+                }
+                if let Some((limits, condition)) = settings.limits {
+                    self = self.x_limits(limits, condition);
+                }
+                self.with_x_axis_flags(&settings.flags)
+            }
+            PlotAxis::Y(y_axis_choice) => {
+                // The bundled ImPlot has no labels for the other Y axes
+                if let (Some(label), YAxisChoice::First) = (&settings.label, y_axis_choice) {
+                    self = self.y_label(label);
+                }
+                if let Some((limits, condition)) = settings.limits {
+                    self = self.y_limits(limits, y_axis_choice, condition);
+                }
+                self.with_y_axis_flags(y_axis_choice, &settings.flags)
+            }
+        }
+    }
+
     /// Set the legend location, orientation and whether it is to be drawn outside the plot
     #[rustversion::attr(since(1.48), doc(alias = "SetLegendLocation"))]
     #[inline]
@@ -437,6 +627,55 @@ impl Plot {
         self
     }
 
+    /// Override a style color for this plot only, for example to give it a transparent
+    /// background. Can be called several times for overriding several colors. The color is
+    /// pushed before the plot begins and popped after it ends, so it applies to the frame and
+    /// legend as well as to the items of the plot, and never leaks out of the plot.
+    #[rustversion::attr(since(1.48), doc(alias = "PushStyleColor"))]
+    #[inline]
+    pub fn with_color_override<C: Into<ImVec4>>(
+        mut self,
+        element: PlotColorElement,
+        color: C,
+    ) -> Self {
+        self.color_overrides.push((element, color.into()));
+        self
+    }
+
+    /// Override a style variable for this plot only, for example to remove the border. Can be
+    /// called several times for overriding several variables. Like with
+    /// [`Plot::with_color_override`], the variable is pushed before the plot begins and popped
+    /// after it ends.
+    #[rustversion::attr(since(1.48), doc(alias = "PushStyleVar"))]
+    #[inline]
+    pub fn with_style_override(mut self, value: StyleVarValue) -> Self {
+        self.style_overrides.push(value);
+        self
+    }
+
+    /// Internal helper that pushes the style overrides of this plot. ImPlot reads the frame
+    /// colors and paddings in BeginPlot, and draws the legend and the border in EndPlot, so this
+    /// has to happen before BeginPlot, and [`Plot::pop_style_overrides`] after EndPlot.
+    fn push_style_overrides(&self) {
+        record_style_pushes(self.color_overrides.len() + self.style_overrides.len());
+        for (element, color) in &self.color_overrides {
+            unsafe {
+                sys::ImPlot_PushStyleColorVec4(*element as sys::ImPlotCol, *color);
+            }
+        }
+        for value in &self.style_overrides {
+            push_style_var_value(*value);
+        }
+    }
+
+    /// Internal helper that pops the style overrides pushed by [`Plot::push_style_overrides`].
+    fn pop_style_overrides(color_count: usize, var_count: usize) {
+        unsafe {
+            sys::ImPlot_PopStyleColor(color_count as i32);
+            sys::ImPlot_PopStyleVar(var_count as i32);
+        }
+    }
+
     /// Internal helper function to set axis limits in case they are specified.
     fn maybe_set_axis_limits(&self) {
         // Limit-setting can either happen via direct limits or through linked limits. The version
@@ -457,15 +696,15 @@ impl Plot {
 
         self.y_limits
             .iter()
-            .enumerate()
-            .for_each(|(k, limit_spec)| {
+            .zip(YAxisChoice::all())
+            .for_each(|(limit_spec, axis)| {
                 if let Some(AxisLimitSpecification::Single(limits, condition)) = limit_spec {
                     unsafe {
                         sys::ImPlot_SetNextPlotLimitsY(
                             limits.Min,
                             limits.Max,
                             *condition as sys::ImGuiCond,
-                            k as i32,
+                            *axis as i32,
                         );
                     }
                 }
@@ -547,8 +786,8 @@ impl Plot {
             .iter()
             .zip(self.y_tick_labels.iter())
             .zip(self.show_y_default_ticks.iter())
-            .enumerate()
-            .for_each(|(k, ((positions, labels), show_defaults))| {
+            .zip(YAxisChoice::all())
+            .for_each(|(((positions, labels), show_defaults), axis)| {
                 if positions.is_some() && !positions.as_ref().unwrap().is_empty() {
                     // The vector of pointers we create has to have a longer lifetime
                     let mut pointer_vec;
@@ -568,7 +807,7 @@ impl Plot {
                             positions.as_ref().unwrap().len() as i32,
                             labels_pointer,
                             *show_defaults,
-                            k as i32,
+                            *axis as i32,
                         )
                     }
                 }
@@ -580,12 +819,29 @@ impl Plot {
     /// plot, and then call `end()` on the token when done with the plot.
     /// If none was returned, that means the plot is not rendered.
     ///
+    /// Plots can't be nested: ImPlot only handles one plot at a time. If this is called while
+    /// another plot is being built, for example from within the closure passed to
+    /// [`build()`](struct.Plot.html#method.build), the plot is skipped and `None` is returned,
+    /// and debug builds print a message naming both plots. The outer plot is not affected.
+    ///
     /// For a convenient implementation of all this, use [`build()`](struct.Plot.html#method.build)
     /// instead.
     #[rustversion::attr(since(1.48), doc(alias = "BeginPlot"))]
     pub fn begin(&self, plot_ui: &PlotUi) -> Option<PlotToken> {
+        if let Some(outer_plot_id) = current_plot_id() {
+            if cfg!(debug_assertions) {
+                eprintln!(
+                    "Plot \"{}\" was begun inside of plot \"{}\", but plots can't be nested, \
+                     skipping it",
+                    self.title.to_string_lossy(),
+                    outer_plot_id
+                );
+            }
+            return None;
+        }
         self.maybe_set_axis_limits();
         self.maybe_set_tick_labels();
+        self.push_style_overrides();
 
         let should_render = unsafe {
             let size_vec: ImVec2 = ImVec2 {
@@ -599,13 +855,15 @@ impl Plot {
                 size_vec,
                 self.plot_flags,
                 self.x_flags,
-                self.y_flags[0],
-                self.y_flags[1],
-                self.y_flags[2],
+                self.y_flags[YAxisChoice::First.index()],
+                self.y_flags[YAxisChoice::Second.index()],
+                self.y_flags[YAxisChoice::Third.index()],
             )
         };
 
         if should_render {
+            record_plot_begun();
+
             // Configure legend location, if one was set. This has to be called between begin() and
             // end(), but since only the last call to it actually affects the outcome, I'm adding
             // it here instead of as a freestanding function. If this is too restrictive (for
@@ -622,13 +880,20 @@ impl Plot {
                 }
             }
 
+            CURRENT_PLOT_ID.with(|id| {
+                *id.borrow_mut() = Some(self.title.to_string_lossy().into_owned());
+            });
             Some(PlotToken {
                 context: plot_ui.context,
                 plot_title: self.title.clone(),
+                color_override_count: self.color_overrides.len(),
+                style_override_count: self.style_overrides.len(),
             })
         } else {
             // In contrast with imgui windows, end() does not have to be
             // called if we don't render. This is more like an imgui popup modal.
+            // The style overrides still have to be popped though.
+            Plot::pop_style_overrides(self.color_overrides.len(), self.style_overrides.len());
             None
         }
     }
@@ -638,35 +903,89 @@ impl Plot {
     ///
     /// Note: the closure is not called if ImPlot::BeginPlot() returned
     /// false - TODO(4bb4) figure out if this is if things are not rendered
+    ///
+    /// If the closure panics, the plot is still ended and its style overrides are popped while
+    /// unwinding, so later plots keep working if the panic is caught.
+    ///
+    /// Style and colormap pushes made in the closure are popped at the end of the plot if their
+    /// tokens haven't popped them by then, for example because a token was stored outside of
+    /// the closure, so they can't change the style of later plots. Debug builds print these
+    /// pushes along with the places they were made at.
     #[rustversion::attr(since(1.48), doc(alias = "BeginPlot"))]
     #[rustversion::attr(since(1.48), doc(alias = "EndPlot"))]
     pub fn build<F: FnOnce()>(self, plot_ui: &PlotUi, f: F) {
-        if let Some(token) = self.begin(plot_ui) {
-            f();
-            token.end()
-        }
+        self.run_in_plot(plot_ui, f);
+    }
+
+    /// Internal helper that begins the plot, runs the closure in it and ends it again, popping
+    /// the pushes the closure leaked. Returns the result of the closure, or `None` if the plot
+    /// is not shown.
+    pub(crate) fn run_in_plot<R, F: FnOnce() -> R>(&self, plot_ui: &PlotUi, f: F) -> Option<R> {
+        let token = self.begin(plot_ui)?;
+        let pushes_before = outstanding_push_count();
+        let result = f();
+        self.pop_leaked_pushes(pushes_before);
+        token.end();
+        Some(result)
+    }
+
+    /// Internal helper that pops the style and colormap pushes the closure passed to the plot
+    /// did not pop, given the number of outstanding pushes from before the closure ran, so they
+    /// don't leak into later plots. Debug builds report these pushes.
+    fn pop_leaked_pushes(&self, pushes_before: usize) {
+        pop_pushes_since(pushes_before, || {
+            format!("within plot \"{}\"", self.title.to_string_lossy())
+        });
     }
 }
 
 /// Tracks a plot that must be ended by calling `.end()`
+///
+/// The plot can only be ended on the thread it was begun on, so the token can't be sent to
+/// other threads:
+/// ```compile_fail
+/// fn check<T: Send>() {}
+/// check::<implot::PlotToken>();
+/// ```
 pub struct PlotToken {
+    /// The context the plot was begun in. Being a raw pointer, this also keeps the token from
+    /// being `Send` or `Sync`.
     context: *const Context,
     /// For better error messages
     plot_title: CString,
+    /// Number of style colors overridden for the plot, which are popped after ending it
+    color_override_count: usize,
+    /// Number of style variables overridden for the plot, which are popped after ending it
+    style_override_count: usize,
 }
 
 impl PlotToken {
     /// End a previously begin()'ed plot.
     #[rustversion::attr(since(1.48), doc(alias = "EndPlot"))]
     pub fn end(mut self) {
+        self.end_plot();
+    }
+
+    /// Internal helper that ends the plot and pops the style overrides of the plot.
+    fn end_plot(&mut self) {
         self.context = std::ptr::null();
+        CURRENT_PLOT_ID.with(|id| *id.borrow_mut() = None);
         unsafe { sys::ImPlot_EndPlot() };
+        Plot::pop_style_overrides(self.color_override_count, self.style_override_count);
     }
 }
 
 impl Drop for PlotToken {
     fn drop(&mut self) {
-        if !self.context.is_null() && !std::thread::panicking() {
+        if self.context.is_null() {
+            return;
+        }
+        if std::thread::panicking() {
+            // Unwinding out of the plot, for example from a panic in the closure passed to
+            // Plot::build. The plot is still ended, since ImPlot can't begin any further plots
+            // otherwise, which would break all later frames if the panic is caught.
+            self.end_plot();
+        } else {
             panic!(
                 "Warning: A PlotToken for plot \"{:?}\" was not called end() on",
                 self.plot_title
@@ -674,3 +993,313 @@ impl Drop for PlotToken {
         }
     }
 }
+
+/// Detects whether the limits of a plot differ from the ones requested with
+/// [`Plot::x_limits`], [`Plot::y_limits`] (or the linked variants of these), which happens when
+/// the user pans or zooms the plot. This is useful for temporarily pausing automatic scrolling
+/// that is done with `Condition::Always` while the user is looking at something:
+/// ```no_run
+/// # use implot::{Condition, LimitChangeDetector, Plot, PlotUi};
+/// # fn example(plot_ui: &PlotUi, auto_scroll: &mut bool, time: f64) {
+/// let mut plot = Plot::new("Scrolling plot");
+/// if *auto_scroll {
+///     plot = plot.x_limits([time - 10.0, time], Condition::Always);
+/// }
+/// let mut detector = LimitChangeDetector::new(&plot);
+/// plot.build(plot_ui, || {
+///     // Plot some things
+///     detector.update();
+/// });
+/// if detector.user_modified_x() {
+///     *auto_scroll = false;
+/// }
+/// # }
+/// ```
+pub struct LimitChangeDetector {
+    /// Requested X limits, if any
+    requested_x: Option<ImPlotRange>,
+    /// Requested Y limits, if any, by axis
+    requested_y: [Option<ImPlotRange>; NUMBER_OF_Y_AXES],
+    /// Tolerance for the comparison, relative to the width of the requested range
+    relative_epsilon: f64,
+    /// Whether the X limits differed from the requested ones in the last update
+    modified_x: bool,
+    /// Whether the Y limits differed from the requested ones in the last update, by axis
+    modified_y: [bool; NUMBER_OF_Y_AXES],
+}
+
+impl LimitChangeDetector {
+    /// Create a detector for the limits requested on the given plot. This has to be created
+    /// before the plot is built, since building consumes the plot.
+    pub fn new(plot: &Plot) -> Self {
+        let requested_range = |specification: &Option<AxisLimitSpecification>| {
+            specification
+                .as_ref()
+                .map(|specification| match specification {
+                    AxisLimitSpecification::Single(range, _) => *range,
+                    AxisLimitSpecification::Linked(range) => *range.borrow(),
+                })
+        };
+        let mut requested_y = [None; NUMBER_OF_Y_AXES];
+        for (requested, specification) in requested_y.iter_mut().zip(plot.y_limits.iter()) {
+            *requested = requested_range(specification);
+        }
+        Self {
+            requested_x: requested_range(&plot.x_limits),
+            requested_y,
+            relative_epsilon: 1e-6,
+            modified_x: false,
+            modified_y: [false; NUMBER_OF_Y_AXES],
+        }
+    }
+
+    /// Set the tolerance for comparing the limits, relative to the width of the requested range.
+    /// Differences smaller than this are not counted as modifications. The default is `1e-6`.
+    pub fn with_epsilon(mut self, relative_epsilon: f64) -> Self {
+        self.relative_epsilon = relative_epsilon;
+        self
+    }
+
+    /// Compare the current limits of the plot with the requested ones. Use this in the closure
+    /// passed to [`Plot::build()`](struct.Plot.html#method.build) of the plot the detector was
+    /// created for.
+    pub fn update(&mut self) {
+        if let Some(requested) = &self.requested_x {
+            let actual = crate::get_plot_limits_raw(None).X;
+            self.modified_x = range_differs(requested, &actual, self.relative_epsilon);
+        }
+        for axis in YAxisChoice::all() {
+            if let Some(requested) = &self.requested_y[axis.index()] {
+                let actual = crate::get_plot_limits_raw(Some(*axis)).Y;
+                self.modified_y[axis.index()] =
+                    range_differs(requested, &actual, self.relative_epsilon);
+            }
+        }
+    }
+
+    /// Returns true if the X limits differed from the requested ones in the last update. This is
+    /// always false if no X limits were requested.
+    pub fn user_modified_x(&self) -> bool {
+        self.modified_x
+    }
+
+    /// Returns true if the limits of the given Y axis differed from the requested ones in the
+    /// last update. This is always false if no limits were requested for the axis.
+    pub fn user_modified_y(&self, y_axis_choice: YAxisChoice) -> bool {
+        self.modified_y[y_axis_choice.index()]
+    }
+
+    /// Returns true if the limits of any axis differed from the requested ones in the last update.
+    pub fn user_modified(&self) -> bool {
+        self.modified_x || self.modified_y.iter().any(|modified| *modified)
+    }
+}
+
+/// Internal helper that checks whether two ranges differ by more than the given tolerance,
+/// relative to the width of the `requested` range.
+fn range_differs(requested: &ImPlotRange, actual: &ImPlotRange, relative_epsilon: f64) -> bool {
+    let tolerance = (requested.Max - requested.Min).abs() * relative_epsilon;
+    (requested.Min - actual.Min).abs() > tolerance || (requested.Max - actual.Max).abs() > tolerance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "imgui-rs")]
+    use crate::test_support::with_test_frames;
+
+    #[test]
+    fn test_check_inside_plot() {
+        CURRENT_PLOT_ID.with(|id| *id.borrow_mut() = Some("plot".to_owned()));
+        assert!(check_inside_plot("test"));
+        CURRENT_PLOT_ID.with(|id| *id.borrow_mut() = None);
+        // Only debug builds fail the assertion, release builds just skip the call
+        let outside_plot = std::panic::catch_unwind(|| check_inside_plot("test"));
+        assert_eq!(outside_plot.is_err(), cfg!(debug_assertions));
+        assert_eq!(
+            outside_plot.ok(),
+            Some(false).filter(|_| !cfg!(debug_assertions))
+        );
+    }
+
+    fn single_limits(spec: &Option<AxisLimitSpecification>) -> Option<(f64, f64, Condition)> {
+        match spec {
+            Some(AxisLimitSpecification::Single(range, condition)) => {
+                Some((range.Min, range.Max, *condition))
+            }
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_with_axis_targets_the_right_axis() {
+        let time = AxisSettings::new()
+            .with_label("time [s]")
+            .with_limits((0.0, 10.0), Condition::Always)
+            .with_flags(AxisFlags::TIME);
+        let pressure = AxisSettings::new()
+            .with_limits((1e3, 1e6), Condition::FirstUseEver)
+            .with_flags(AxisFlags::LOG_SCALE);
+        let plot = Plot::new("Axes")
+            .with_axis(PlotAxis::X, time)
+            .with_axis(YAxisChoice::Third.into(), pressure.clone());
+
+        assert_eq!(plot.x_label.to_str().unwrap(), "time [s]");
+        assert_eq!(plot.x_flags, AxisFlags::TIME.bits() as sys::ImPlotAxisFlags);
+        assert_eq!(
+            single_limits(&plot.x_limits),
+            Some((0.0, 10.0, Condition::Always))
+        );
+        // The flags are passed to BeginPlot in the order of the Y axis indices
+        let log_scale = AxisFlags::LOG_SCALE.bits() as sys::ImPlotAxisFlags;
+        let none = AxisFlags::NONE.bits() as sys::ImPlotAxisFlags;
+        assert_eq!(plot.y_flags, [none, none, log_scale]);
+        assert!(plot.y_limits[0].is_none() && plot.y_limits[1].is_none());
+        assert_eq!(
+            single_limits(&plot.y_limits[2]),
+            Some((1e3, 1e6, Condition::FirstUseEver))
+        );
+
+        // Settings without limits keep linked limits, and the label of the first Y axis is set
+        let linked = Rc::new(RefCell::new(ImPlotRange { Min: 0.0, Max: 1.0 }));
+        let plot = Plot::new("Linked")
+            .linked_y2_limits(linked)
+            .with_axis(
+                PlotAxis::Y(YAxisChoice::Second),
+                AxisSettings::new().with_flags(AxisFlags::INVERT),
+            )
+            .with_axis(
+                PlotAxis::Y(YAxisChoice::First),
+                AxisSettings::new().with_label("value"),
+            );
+        assert!(matches!(
+            plot.y_limits[1],
+            Some(AxisLimitSpecification::Linked(_))
+        ));
+        assert_eq!(
+            plot.y_flags[1],
+            AxisFlags::INVERT.bits() as sys::ImPlotAxisFlags
+        );
+        assert_eq!(plot.y_label.to_str().unwrap(), "value");
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_panicking_plot_closure_ends_the_plot() {
+        let mut panics = 0;
+        with_test_frames(3, |plot_ui, _| {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                Plot::new("panicking")
+                    .with_style_override(StyleVarValue::LineWeight(2.0))
+                    .build(plot_ui, || panic!("panic in plot closure"));
+            }));
+            panics += result.is_err() as usize;
+            assert_eq!(current_plot_id(), None);
+            // ImPlot fails an assertion when beginning a plot while another one was not ended
+            Plot::new("after panic").build(plot_ui, || {
+                assert_eq!(current_plot_id().as_deref(), Some("after panic"));
+            });
+        });
+        // The plot may be skipped in the first frame of the window, but not in the later ones
+        assert!(panics >= 2);
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_pushes_left_over_in_plot_are_popped() {
+        with_test_frames(2, |plot_ui, _| {
+            let mut kept_token = None;
+            let pushes_before = outstanding_push_count();
+            Plot::new("leaking").build(plot_ui, || {
+                kept_token = Some(crate::push_style_var(
+                    plot_ui,
+                    StyleVarValue::LineWeight(3.0),
+                ));
+                std::mem::forget(crate::push_colormap(plot_ui, crate::Colormap::Jet));
+            });
+            assert_eq!(outstanding_push_count(), pushes_before);
+            // ImPlot fails an assertion when popping more than was pushed, so this must not pop
+            // the variable a second time
+            drop(kept_token);
+        });
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_nested_plot_is_skipped() {
+        with_test_frames(2, |plot_ui, _| {
+            let mut inner_ran = false;
+            // ImPlot fails an assertion when beginning a plot inside of another one
+            Plot::new("outer").build(plot_ui, || {
+                Plot::new("inner")
+                    .with_style_override(StyleVarValue::LineWeight(2.0))
+                    .build(plot_ui, || inner_ran = true);
+                assert_eq!(current_plot_id().as_deref(), Some("outer"));
+            });
+            assert!(!inner_ran);
+            assert_eq!(current_plot_id(), None);
+            Plot::new("after outer").build(plot_ui, || {});
+        });
+    }
+
+    #[test]
+    fn test_limit_change_detector_requested_limits() {
+        let linked = Rc::new(RefCell::new(ImPlotRange { Min: 2.0, Max: 4.0 }));
+        let plot = Plot::new("Detected")
+            .x_limits([0.0, 10.0], Condition::Always)
+            .linked_y_limits(linked, YAxisChoice::Second);
+        let detector = LimitChangeDetector::new(&plot);
+        assert_eq!(
+            detector.requested_x.map(|x| (x.Min, x.Max)),
+            Some((0.0, 10.0))
+        );
+        assert!(detector.requested_y[0].is_none());
+        assert_eq!(
+            detector.requested_y[1].map(|y| (y.Min, y.Max)),
+            Some((2.0, 4.0))
+        );
+        assert!(!detector.user_modified());
+
+        // The tolerance is relative to the width of the requested range
+        let requested = ImPlotRange {
+            Min: 0.0,
+            Max: 10.0,
+        };
+        let nudged = ImPlotRange {
+            Min: 5e-6,
+            Max: 10.0,
+        };
+        assert!(!range_differs(&requested, &nudged, 1e-6));
+        assert!(range_differs(&requested, &nudged, 1e-7));
+        let panned = ImPlotRange {
+            Min: 1.0,
+            Max: 11.0,
+        };
+        assert!(range_differs(&requested, &panned, 1e-6));
+        assert!(!range_differs(&requested, &requested, 0.0));
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_limit_change_detector_update() {
+        let mut frame = 0;
+        with_test_frames(2, |plot_ui, _| {
+            // Limits set once are only applied in the first frame, so the different limits
+            // requested in the second frame look like the user changed them
+            let limits = if frame == 0 { [0.0, 1.0] } else { [5.0, 6.0] };
+            let plot = Plot::new("detected")
+                .x_limits(limits, Condition::Once)
+                .y_limits(limits, YAxisChoice::First, Condition::Once);
+            let mut detector = LimitChangeDetector::new(&plot);
+            plot.build(plot_ui, || detector.update());
+
+            let modified = frame > 0;
+            assert_eq!(detector.user_modified_x(), modified);
+            assert_eq!(detector.user_modified_y(YAxisChoice::First), modified);
+            // Axes without requested limits are never modified
+            assert!(!detector.user_modified_y(YAxisChoice::Second));
+            assert_eq!(detector.user_modified(), modified);
+            frame += 1;
+        });
+    }
+}
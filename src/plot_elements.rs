@@ -3,10 +3,113 @@
 //! This module defines the various structs that can be used for drawing different things such
 //! as lines, bars, scatter plots and text in a plot. For the module to create plots themselves,
 //! see `plot`.
-use crate::sys;
-use std::ffi::CString;
+use crate::frame_stats::{record_item_plotted, record_style_pushes};
+use crate::imgui_compat::{content_region_avail, item_spacing, same_line, PlotDrawList};
+use crate::plot::check_inside_plot;
+use crate::{
+    contrasting_text_color, get_plot_pos, get_plot_size, hide_next_item, plot_to_pixels_vec2,
+    show_colormap_scale, sys, Condition, ImVec2, ImVec4, Plot, PlotColorElement, PlotPoint, PlotUi,
+};
+use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
 
+/// Largest number of points that can be plotted by one plot element, since ImPlot takes the
+/// number of points as an `int`. Elements with more points than this are not plotted, and fail
+/// a debug assertion naming the element. Renderers without support for large draw lists can
+/// draw far fewer points than this in one plot, see [`max_safe_points`](fn.max_safe_points.html).
+pub const MAX_PLOT_POINTS: usize = i32::MAX as usize;
+
+/// Size of bars along the axis of their positions, default value taken from C++ implot
+const DEFAULT_BAR_SIZE: f64 = 0.67;
+
+/// Errors of the `try_*` methods of the plot elements that check their data before plotting it,
+/// such as [`PlotHeatmap::try_plot`]. Data that fails these checks would otherwise make ImPlot
+/// read out of bounds or draw garbage.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PlotError {
+    /// The data doesn't have the length its dimensions call for, such as a heatmap's values
+    /// for its number of rows and columns, or the edges of histogram bins for their counts.
+    DimensionMismatch {
+        /// Length the dimensions call for
+        expected: usize,
+        /// Length of the data
+        actual: usize,
+    },
+    /// There is no data to plot.
+    EmptyData,
+    /// A range the data is shown in, such as a heatmap's scale or drawing area, or a bin edge
+    /// of a histogram, is infinite or NaN.
+    NonFiniteBounds,
+    /// A label string contains an internal nul byte, which C strings can't represent.
+    LabelContainsNul,
+    /// There are more points than ImPlot can plot in one call, see [`MAX_PLOT_POINTS`].
+    CountOverflow {
+        /// Number of points, or of values of a heatmap
+        count: usize,
+    },
+    /// The values of a pie chart add up to zero, so there are no slices to draw, and normalizing
+    /// them would divide by zero.
+    ZeroSum,
+}
+
+impl std::fmt::Display for PlotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlotError::DimensionMismatch { expected, actual } => write!(
+                f,
+                "the data has a length of {}, but its dimensions call for {}",
+                actual, expected
+            ),
+            PlotError::EmptyData => write!(f, "there is no data to plot"),
+            PlotError::NonFiniteBounds => write!(f, "the bounds of the data are not finite"),
+            PlotError::LabelContainsNul => write!(f, "the label contains an internal nul byte"),
+            PlotError::CountOverflow { count } => write!(
+                f,
+                "{} points are more than the {} that can be plotted at once",
+                count, MAX_PLOT_POINTS
+            ),
+            PlotError::ZeroSum => write!(f, "the values add up to zero"),
+        }
+    }
+}
+
+impl std::error::Error for PlotError {}
+
+/// Convert a number of points (or rows or columns) to the `int` ImPlot takes, which fails if it
+/// is larger than [`MAX_PLOT_POINTS`].
+pub(crate) fn checked_point_count(count: usize) -> Result<i32, PlotError> {
+    if count > MAX_PLOT_POINTS {
+        Err(PlotError::CountOverflow { count })
+    } else {
+        Ok(count as i32)
+    }
+}
+
+/// Internal helper for plot elements that can't be plotted, which fails a debug assertion with
+/// the label of the element, the error and the location of the call. Release builds skip the
+/// element instead of passing inconsistent data to ImPlot. The callers are `#[track_caller]`,
+/// so the location is the one in user code.
+#[track_caller]
+pub(crate) fn report_plot_error(label: &CStr, error: &PlotError) {
+    if cfg!(debug_assertions) {
+        panic!(
+            "plot element \"{}\" can't be plotted: {}, at {}",
+            label.to_string_lossy(),
+            error,
+            std::panic::Location::caller()
+        );
+    }
+}
+
+/// Internal helper for the plot elements that converts a number of points with
+/// [`checked_point_count`], and reports the error with [`report_plot_error`] if it doesn't fit.
+#[track_caller]
+pub(crate) fn point_count_or_skip(count: usize, label: &CStr) -> Option<i32> {
+    checked_point_count(count)
+        .map_err(|error| report_plot_error(label, &error))
+        .ok()
+}
+
 pub use crate::sys::ImPlotPoint;
 
 // --- Actual plotting functionality -------------------------------------------------------------
@@ -29,25 +132,69 @@ impl PlotLine {
     }
 
     /// Plot a line. Use this in closures passed to [`Plot::build()`](struct.Plot.html#method.build)
+    #[track_caller]
     pub fn plot(&self, x: &[f64], y: &[f64]) {
+        if !check_inside_plot("PlotLine::plot") {
+            return;
+        }
         // If there is no data to plot, we stop here
         if x.len().min(y.len()) == 0 {
             return;
         }
+        let number_of_points = match point_count_or_skip(x.len().min(y.len()), &self.label) {
+            Some(number_of_points) => number_of_points,
+            None => return,
+        };
+        record_item_plotted(number_of_points as usize);
         unsafe {
             sys::ImPlot_PlotLinedoublePtrdoublePtr(
                 self.label.as_ptr() as *const c_char,
                 x.as_ptr(),
                 y.as_ptr(),
-                x.len().min(y.len()) as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
-                0,                           // No offset
+                number_of_points,
+                0,                                 // No offset
                 std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
             );
         }
     }
+
+    /// Plot a line through points given as `[x, y]` pairs, instead of as separate slices of
+    /// X and Y values. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    #[track_caller]
+    pub fn plot_points(&self, points: &[[f64; 2]]) {
+        if !check_inside_plot("PlotLine::plot_points") {
+            return;
+        }
+        // If there is no data to plot, we stop here
+        if points.is_empty() {
+            return;
+        }
+        let number_of_points = match point_count_or_skip(points.len(), &self.label) {
+            Some(number_of_points) => number_of_points,
+            None => return,
+        };
+        record_item_plotted(number_of_points as usize);
+        unsafe {
+            // The X and Y values are interleaved, so both start at the first point and skip
+            // over a whole point from one value to the next
+            let values = points.as_ptr() as *const f64;
+            sys::ImPlot_PlotLinedoublePtrdoublePtr(
+                self.label.as_ptr() as *const c_char,
+                values,
+                values.add(1),
+                number_of_points,
+                0,                                      // No offset
+                std::mem::size_of::<[f64; 2]>() as i32, // Stride, one point of two f64 values
+            );
+        }
+    }
 }
 
-/// Struct to provide functionality for plotting a line in a plot with stairs style.
+/// Struct to provide functionality for plotting a line in a plot with stairs style, for values
+/// that hold until the next point, such as settings that change over time. It has the same
+/// methods as [`PlotLine`] and is styled the same way (line color and weight, markers), so
+/// switching a plot between the two only takes changing the type.
 pub struct PlotStairs {
     /// Label to show in the legend for this line
     label: CString,
@@ -67,18 +214,256 @@ impl PlotStairs {
 
     /// Plot a stairs style line. Use this in closures passed to
     /// [`Plot::build()`](struct.Plot.html#method.build)
+    #[track_caller]
     pub fn plot(&self, x: &[f64], y: &[f64]) {
+        if !check_inside_plot("PlotStairs::plot") {
+            return;
+        }
         // If there is no data to plot, we stop here
         if x.len().min(y.len()) == 0 {
             return;
         }
+        let number_of_points = match point_count_or_skip(x.len().min(y.len()), &self.label) {
+            Some(number_of_points) => number_of_points,
+            None => return,
+        };
+        record_item_plotted(number_of_points as usize);
         unsafe {
             sys::ImPlot_PlotStairsdoublePtrdoublePtr(
                 self.label.as_ptr() as *const c_char,
                 x.as_ptr(),
                 y.as_ptr(),
-                x.len().min(y.len()) as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
-                0,                           // No offset
+                number_of_points,
+                0,                                 // No offset
+                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+            );
+        }
+    }
+
+    /// Plot a stairs style line through points given as `[x, y]` pairs, instead of as separate
+    /// slices of X and Y values. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    #[track_caller]
+    pub fn plot_points(&self, points: &[[f64; 2]]) {
+        if !check_inside_plot("PlotStairs::plot_points") {
+            return;
+        }
+        // If there is no data to plot, we stop here
+        if points.is_empty() {
+            return;
+        }
+        let number_of_points = match point_count_or_skip(points.len(), &self.label) {
+            Some(number_of_points) => number_of_points,
+            None => return,
+        };
+        record_item_plotted(number_of_points as usize);
+        unsafe {
+            // The X and Y values are interleaved, so both start at the first point and skip
+            // over a whole point from one value to the next
+            let values = points.as_ptr() as *const f64;
+            sys::ImPlot_PlotStairsdoublePtrdoublePtr(
+                self.label.as_ptr() as *const c_char,
+                values,
+                values.add(1),
+                number_of_points,
+                0,                                      // No offset
+                std::mem::size_of::<[f64; 2]>() as i32, // Stride, one point of two f64 values
+            );
+        }
+    }
+}
+
+/// Internal helper that draws a line across the whole plot area at each of the given positions,
+/// vertical lines for positions on the X axis and horizontal lines for positions on the Y axis.
+///
+/// The ImPlot version wrapped here has no infinite lines. Plotting lines between the current
+/// limits would make fitting the plot to its data keep those limits, so the lines are drawn with
+/// the draw list of the plot instead. A dummy item gives them their legend entry and color, and
+/// doesn't take part in fitting. It is always shown, since the lines are drawn whether it is
+/// hidden or not, and ImPlot doesn't tell.
+#[track_caller]
+fn plot_lines_across(label: &CStr, positions: &[f64], vertical: bool) {
+    // Non-finite positions have no line to draw
+    let mut positions = positions
+        .iter()
+        .filter(|position| position.is_finite())
+        .peekable();
+    if positions.peek().is_none() {
+        return;
+    }
+    let mut color = ImVec4 {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+        w: 0.0,
+    };
+    hide_next_item(false, Condition::Always);
+    let weight = unsafe {
+        sys::ImPlot_PlotDummy(label.as_ptr());
+        sys::ImPlot_GetLastItemColor(&mut color as *mut ImVec4);
+        let style = sys::ImPlot_GetStyle();
+        assert_ne!(style, std::ptr::null_mut());
+        (*style).LineWeight
+    };
+    record_item_plotted(0);
+
+    let plot_pos = get_plot_pos();
+    let plot_size = get_plot_size();
+    let draw_list = PlotDrawList::current();
+    unsafe {
+        sys::ImPlot_PushPlotClipRect();
+    }
+    for position in positions {
+        // The other coordinate is 1 so the point is valid on logarithmic axes as well
+        let (start, end) = if vertical {
+            let x = plot_to_pixels_vec2([*position, 1.0], None).x;
+            (
+                ImVec2 { x, y: plot_pos.y },
+                ImVec2 {
+                    x,
+                    y: plot_pos.y + plot_size.y,
+                },
+            )
+        } else {
+            let y = plot_to_pixels_vec2([1.0, *position], None).y;
+            (
+                ImVec2 { x: plot_pos.x, y },
+                ImVec2 {
+                    x: plot_pos.x + plot_size.x,
+                    y,
+                },
+            )
+        };
+        draw_list.add_line(start, end, color, weight);
+    }
+    unsafe {
+        sys::ImPlot_PopPlotClipRect();
+    }
+}
+
+/// Struct to provide functionality for plotting vertical lines across the whole plot, such as
+/// thresholds or markers for events, which stay in place when the plot is panned or zoomed.
+/// The lines are styled like [`PlotLine`]s, with the line color and the line weight of the
+/// current style, and they share one legend entry.
+///
+/// The ImPlot version wrapped here doesn't have infinite lines, so the lines are drawn across
+/// the plot area directly, and don't take part in fitting the plot to its data. Because of this,
+/// their legend entry can't hide them: clicking it has no effect, the lines stay shown. And
+/// [`set_next_line_style`](fn.set_next_line_style.html) sets their color but not their weight.
+/// Push the [`StyleVar::LineWeight`](enum.StyleVar.html#variant.LineWeight) style variable to
+/// change the weight.
+pub struct PlotVLines {
+    /// Label to show in the legend for these lines
+    label: CString,
+}
+
+impl PlotVLines {
+    /// Create new vertical lines to be plotted. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+        }
+    }
+
+    /// Plot a vertical line at each of the given X positions. Positions that are infinite or
+    /// NaN are skipped. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    #[rustversion::attr(since(1.48), doc(alias = "PlotVLines"))]
+    #[track_caller]
+    pub fn plot(&self, x_positions: &[f64]) {
+        if !check_inside_plot("PlotVLines::plot") {
+            return;
+        }
+        plot_lines_across(&self.label, x_positions, true);
+    }
+}
+
+/// Struct to provide functionality for plotting horizontal lines across the whole plot. This is
+/// the horizontal version of [`PlotVLines`], see there for the details and limitations.
+pub struct PlotHLines {
+    /// Label to show in the legend for these lines
+    label: CString,
+}
+
+impl PlotHLines {
+    /// Create new horizontal lines to be plotted. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+        }
+    }
+
+    /// Plot a horizontal line at each of the given Y positions, in the units of the Y axis
+    /// selected with [`set_plot_y_axis`](fn.set_plot_y_axis.html). Positions that are infinite
+    /// or NaN are skipped. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    #[rustversion::attr(since(1.48), doc(alias = "PlotHLines"))]
+    #[track_caller]
+    pub fn plot(&self, y_positions: &[f64]) {
+        if !check_inside_plot("PlotHLines::plot") {
+            return;
+        }
+        plot_lines_across(&self.label, y_positions, false);
+    }
+}
+
+/// Struct to provide functionality for plotting digital signals, such as the channels of a
+/// logic analyzer. Digital signals are drawn as stacked bands at the bottom of the plot, in the
+/// order they are plotted in, and don't take part in fitting the Y axis to the data. Their
+/// height and the gap between them are set by the
+/// [`StyleVar::DigitalBitHeight`](enum.StyleVar.html#variant.DigitalBitHeight) and
+/// [`StyleVar::DigitalBitGap`](enum.StyleVar.html#variant.DigitalBitGap) style variables.
+pub struct PlotDigital {
+    /// Label to show in the legend for this signal
+    label: CString,
+}
+
+impl PlotDigital {
+    /// Create a new digital signal to be plotted. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+        }
+    }
+
+    /// Plot a digital signal, which is high where the value in `y` is not zero. The `x` values
+    /// are the times at which the signal takes the value at the same index, and it holds that
+    /// value until the next one. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    #[rustversion::attr(since(1.48), doc(alias = "PlotDigital"))]
+    #[track_caller]
+    pub fn plot(&self, x: &[f64], y: &[f64]) {
+        if !check_inside_plot("PlotDigital::plot") {
+            return;
+        }
+        // If there is no data to plot, we stop here
+        if x.len().min(y.len()) == 0 {
+            return;
+        }
+        let number_of_points = match point_count_or_skip(x.len().min(y.len()), &self.label) {
+            Some(number_of_points) => number_of_points,
+            None => return,
+        };
+        record_item_plotted(number_of_points as usize);
+        unsafe {
+            sys::ImPlot_PlotDigitaldoublePtr(
+                self.label.as_ptr() as *const c_char,
+                x.as_ptr(),
+                y.as_ptr(),
+                number_of_points,
+                0,                                 // No offset
                 std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
             );
         }
@@ -105,18 +490,27 @@ impl PlotScatter {
 
     /// Draw a previously-created scatter plot. Use this in closures passed to
     /// [`Plot::build()`](struct.Plot.html#method.build)
+    #[track_caller]
     pub fn plot(&self, x: &[f64], y: &[f64]) {
+        if !check_inside_plot("PlotScatter::plot") {
+            return;
+        }
         // If there is no data to plot, we stop here
         if x.len().min(y.len()) == 0 {
             return;
         }
+        let number_of_points = match point_count_or_skip(x.len().min(y.len()), &self.label) {
+            Some(number_of_points) => number_of_points,
+            None => return,
+        };
+        record_item_plotted(number_of_points as usize);
         unsafe {
             sys::ImPlot_PlotScatterdoublePtrdoublePtr(
                 self.label.as_ptr() as *const c_char,
                 x.as_ptr(),
                 y.as_ptr(),
-                x.len().min(y.len()) as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
-                0,                           // No offset
+                number_of_points,
+                0,                                 // No offset
                 std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
             );
         }
@@ -128,8 +522,11 @@ pub struct PlotBars {
     /// Label to show in the legend for this line
     label: CString,
 
-    /// Width of the bars, in plot coordinate terms
-    bar_width: f64,
+    /// Width of vertical bars, in X axis units, if one was set
+    bar_width: Option<f64>,
+
+    /// Height of horizontal bars, in Y axis units, if one was set
+    bar_height: Option<f64>,
 
     /// Horizontal bar mode
     horizontal_bars: bool,
@@ -145,18 +542,32 @@ impl PlotBars {
         Self {
             label: CString::new(label)
                 .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
-            bar_width: 0.67, // Default value taken from C++ implot
+            bar_width: None,
+            bar_height: None,
             horizontal_bars: false,
         }
     }
 
-    /// Set the width of the bars
+    /// Set the width of vertical bars, in X axis units. The default is 0.67. Horizontal bars
+    /// don't use this, their size along the Y axis is set with [`PlotBars::with_bar_height`],
+    /// and debug builds print a message when they are plotted with a width set.
     pub fn with_bar_width(mut self, bar_width: f64) -> Self {
-        self.bar_width = bar_width;
+        self.bar_width = Some(bar_width);
         self
     }
 
-    /// Set the bars to be horizontal (default is vertical)
+    /// Set the height of horizontal bars, in Y axis units. The default is 0.67. Vertical bars
+    /// don't use this, their size along the X axis is set with [`PlotBars::with_bar_width`],
+    /// and debug builds print a message when they are plotted with a height set.
+    pub fn with_bar_height(mut self, bar_height: f64) -> Self {
+        self.bar_height = Some(bar_height);
+        self
+    }
+
+    /// Set the bars to be horizontal (default is vertical). Horizontal bars are positioned
+    /// along the Y axis and extend along the X axis, for example for rankings with one category
+    /// per bar on the Y axis.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotBarsH"))]
     pub fn with_horizontal_bars(mut self) -> Self {
         self.horizontal_bars = true;
         self
@@ -166,18 +577,27 @@ impl PlotBars {
     /// [`Plot::build()`](struct.Plot.html#method.build). The `axis_positions`
     /// specify where on the corresponding axis (X for vertical mode, Y for horizontal mode) the
     /// bar is drawn, and the `bar_values` specify what values the bars have.
+    #[track_caller]
     pub fn plot(&self, axis_positions: &[f64], bar_values: &[f64]) {
+        if !check_inside_plot("PlotBars::plot") {
+            return;
+        }
         let number_of_points = axis_positions.len().min(bar_values.len());
         // If there is no data to plot, we stop here
         if number_of_points == 0 {
             return;
         }
+        let number_of_points = match point_count_or_skip(number_of_points, &self.label) {
+            Some(number_of_points) => number_of_points,
+            None => return,
+        };
+        record_item_plotted(number_of_points as usize);
         unsafe {
             // C++ implot has separate functions for the two variants, but the interfaces
             // are the same, so they are unified here. The x and y values have different
             // meanings though, hence the swapping around before they are passed to the
             // plotting function.
-            let (plot_function, x, y);
+            let (plot_function, x, y, bar_size);
             if self.horizontal_bars {
                 plot_function = sys::ImPlot_PlotBarsHdoublePtrdoublePtr
                     as unsafe extern "C" fn(
@@ -205,18 +625,81 @@ impl PlotBars {
                 x = axis_positions;
                 y = bar_values;
             };
+            bar_size = self.bar_size();
 
             plot_function(
                 self.label.as_ptr() as *const c_char,
                 x.as_ptr(),
                 y.as_ptr(),
-                number_of_points as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
-                self.bar_width,
+                number_of_points,
+                bar_size,
                 0,                                 // No offset
                 std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
             );
         }
     }
+
+    /// Draw a previously-created bar plot with the bars at the positions 0, 1, 2 and so on,
+    /// along the X axis for vertical bars and along the Y axis for horizontal bars. Use this in
+    /// closures passed to [`Plot::build()`](struct.Plot.html#method.build).
+    #[track_caller]
+    pub fn plot_values(&self, bar_values: &[f64]) {
+        if !check_inside_plot("PlotBars::plot_values") {
+            return;
+        }
+        // If there is no data to plot, we stop here
+        if bar_values.is_empty() {
+            return;
+        }
+        let number_of_points = match point_count_or_skip(bar_values.len(), &self.label) {
+            Some(number_of_points) => number_of_points,
+            None => return,
+        };
+        record_item_plotted(number_of_points as usize);
+        // Unlike for the other variant, the two C++ functions take their arguments in the same
+        // order and with the same meaning here, the bar size along the axis of the positions
+        let plot_function = if self.horizontal_bars {
+            sys::ImPlot_PlotBarsHdoublePtrInt
+        } else {
+            sys::ImPlot_PlotBarsdoublePtrInt
+        };
+        unsafe {
+            plot_function(
+                self.label.as_ptr() as *const c_char,
+                bar_values.as_ptr(),
+                number_of_points,
+                self.bar_size(),
+                0.0,                               // No shift of the positions
+                0,                                 // No offset
+                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+            );
+        }
+    }
+
+    /// Internal helper that returns the size of the bars along the axis of their positions,
+    /// which is what ImPlot takes: the width of vertical bars and the height of horizontal ones.
+    /// Debug builds print a message if the size for the other orientation was set, since it
+    /// has no effect.
+    fn bar_size(&self) -> f64 {
+        let (size, unused_size, unused_setter) = if self.horizontal_bars {
+            (self.bar_height, self.bar_width, "with_bar_width")
+        } else {
+            (self.bar_width, self.bar_height, "with_bar_height")
+        };
+        if cfg!(debug_assertions) && unused_size.is_some() {
+            eprintln!(
+                "The bars \"{}\" are {}, so the size set with PlotBars::{} has no effect",
+                self.label.to_string_lossy(),
+                if self.horizontal_bars {
+                    "horizontal"
+                } else {
+                    "vertical"
+                },
+                unused_setter
+            );
+        }
+        size.unwrap_or(DEFAULT_BAR_SIZE)
+    }
 }
 
 /// Struct to provide functionality for adding text within a plot
@@ -231,6 +714,10 @@ pub struct PlotText {
     /// Y component of the pixel offset to be used. Will be used independently of the actual plot
     /// scaling. Defaults to 0.
     pixel_offset_y: f32,
+
+    /// Color of the text. Defaults to `None`, which uses the style color
+    /// [`PlotColorElement::InlayText`].
+    text_color: Option<ImVec4>,
 }
 
 impl PlotText {
@@ -244,6 +731,7 @@ impl PlotText {
                 .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
             pixel_offset_x: 0.0,
             pixel_offset_y: 0.0,
+            text_color: None,
         }
     }
 
@@ -255,14 +743,43 @@ impl PlotText {
         self
     }
 
+    /// Set the color of the text, instead of using the style color
+    /// [`PlotColorElement::InlayText`].
+    pub fn with_text_color<C: Into<ImVec4>>(mut self, color: C) -> Self {
+        self.text_color = Some(color.into());
+        self
+    }
+
+    /// Set the color of the text to black or white, whichever is more readable on top of the
+    /// given background color, see [`contrasting_text_color`](fn.contrasting_text_color.html).
+    /// This is useful for labels on top of colored areas, such as heatmap cells.
+    pub fn with_auto_text_color<C: Into<ImVec4>>(self, background: C) -> Self {
+        self.with_text_color(contrasting_text_color(background.into()))
+    }
+
     /// Draw the text label in the plot at the given position, optionally vertically. Use this in
     /// closures passed to [`Plot::build()`](struct.Plot.html#method.build)
+    #[track_caller]
     pub fn plot(&self, x: f64, y: f64, vertical: bool) {
+        if !check_inside_plot("PlotText::plot") {
+            return;
+        }
         // If there is nothing to show, don't do anything
         if self.label.as_bytes().is_empty() {
             return;
         }
 
+        // Pushed and popped right here, so this doesn't need a PlotUi for tying a token to a frame
+        if let Some(color) = self.text_color {
+            record_style_pushes(1);
+            unsafe {
+                sys::ImPlot_PushStyleColorVec4(
+                    PlotColorElement::InlayText as sys::ImPlotCol,
+                    color,
+                );
+            }
+        }
+        record_item_plotted(1);
         unsafe {
             sys::ImPlot_PlotText(
                 self.label.as_ptr() as *const c_char,
@@ -275,10 +792,15 @@ impl PlotText {
                 },
             );
         }
+        if self.text_color.is_some() {
+            unsafe {
+                sys::ImPlot_PopStyleColor(1);
+            }
+        }
     }
 }
 
-/// Struct to provide functionality for creating headmaps.
+/// Struct to provide functionality for creating heatmaps.
 pub struct PlotHeatmap {
     /// Label to show in plot
     label: CString,
@@ -288,8 +810,8 @@ pub struct PlotHeatmap {
     /// is a value, the tuple is interpreted as `(minimum, maximum)`.
     scale_range: Option<(f64, f64)>,
 
-    /// Label C style format string, this is shown when a a value point is hovered.
-    /// None means don't show a label. The label is stored directly as an ImString because
+    /// Label C style format string for the values, which are drawn in the center of each cell.
+    /// None means don't show the values. The label is stored directly as an ImString because
     /// that is what's needed for the plot call anyway. Conversion is done in the setter.
     label_format: Option<CString>,
 
@@ -305,15 +827,25 @@ impl PlotHeatmap {
     /// what those are), aside from the `scale_min` and `scale_max` values, which default to
     /// `None`, which is interpreted as "automatically make the scale fit the data". Does not draw
     /// anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes, see
+    /// [`PlotHeatmap::try_new`] for a version that returns an error instead.
     pub fn new(label: &str) -> Self {
-        Self {
-            label: CString::new(label)
-                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+        Self::try_new(label)
+            .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label))
+    }
+
+    /// Like [`PlotHeatmap::new`], but returns [`PlotError::LabelContainsNul`] if the label
+    /// string contains internal null bytes.
+    pub fn try_new(label: &str) -> Result<Self, PlotError> {
+        Ok(Self {
+            label: CString::new(label).map_err(|_| PlotError::LabelContainsNul)?,
             scale_range: None,
             label_format: Some(CString::new("%.1f").unwrap()),
             drawarea_lower_left: ImPlotPoint { x: 0.0, y: 0.0 },
             drawarea_upper_right: ImPlotPoint { x: 1.0, y: 1.0 },
-        }
+        })
     }
 
     /// Specify the scale for the shown colors by minimum and maximum value.
@@ -322,7 +854,9 @@ impl PlotHeatmap {
         self
     }
 
-    /// Specify the label format for hovered data points.. `None` means no label is shown.
+    /// Specify the C style format string for the values drawn in each cell, such as `"%.3f"`.
+    /// The default is `"%.1f"`, and `None` turns the value labels off, which is worth doing for
+    /// heatmaps with cells too small to fit them.
     ///
     /// # Panics
     /// Will panic if the label format string contains internal null bytes.
@@ -334,33 +868,58 @@ impl PlotHeatmap {
         self
     }
 
-    /// Specify the drawing area as the lower left and upper right point
-    pub fn with_drawing_area(mut self, lower_left: ImPlotPoint, upper_right: ImPlotPoint) -> Self {
-        self.drawarea_lower_left = lower_left;
-        self.drawarea_upper_right = upper_right;
+    /// Specify the drawing area as the lower left and upper right point, in plot coordinates.
+    /// These are `bounds_min` and `bounds_max` in the C++ code, and default to `(0, 0)` and
+    /// `(1, 1)`.
+    pub fn with_drawing_area(
+        mut self,
+        lower_left: impl Into<PlotPoint>,
+        upper_right: impl Into<PlotPoint>,
+    ) -> Self {
+        self.drawarea_lower_left = lower_left.into().into();
+        self.drawarea_upper_right = upper_right.into().into();
         self
     }
 
     /// Plot the heatmap, with the given values (assumed to be in row-major order),
     /// number of rows and number of columns.
+    ///
+    /// Nothing is drawn without values. If the heatmap can't be plotted for any of the other
+    /// reasons [`PlotHeatmap::try_plot`] returns an error for, such as a number of values that
+    /// doesn't match the number of rows and columns, this fails a debug assertion, and release
+    /// builds skip the heatmap.
+    #[track_caller]
     pub fn plot(&self, values: &[f64], number_of_rows: u32, number_of_cols: u32) {
-        // If no range was given, determine that range
-        let scale_range = self.scale_range.unwrap_or_else(|| {
-            let mut min_seen = values[0];
-            let mut max_seen = values[0];
-            values.iter().for_each(|value| {
-                min_seen = min_seen.min(*value);
-                max_seen = max_seen.max(*value);
-            });
-            (min_seen, max_seen)
-        });
+        match self.try_plot(values, number_of_rows, number_of_cols) {
+            Ok(()) | Err(PlotError::EmptyData) => {}
+            Err(error) => report_plot_error(&self.label, &error),
+        }
+    }
 
+    /// Like [`PlotHeatmap::plot`], but returns an error instead of plotting the heatmap if there
+    /// are no values, if the number of values isn't `number_of_rows * number_of_cols`, if there
+    /// are too many of them, or if the scale range or the drawing area is not finite. The scale
+    /// range is not finite when it is computed from values that include infinities or NaN.
+    #[track_caller]
+    pub fn try_plot(
+        &self,
+        values: &[f64],
+        number_of_rows: u32,
+        number_of_cols: u32,
+    ) -> Result<(), PlotError> {
+        if !check_inside_plot("PlotHeatmap::plot") {
+            return Ok(());
+        }
+        let (number_of_rows, number_of_cols, scale_range) =
+            self.checked_plot_arguments(values, number_of_rows, number_of_cols)?;
+
+        record_item_plotted(values.len());
         unsafe {
             sys::ImPlot_PlotHeatmapdoublePtr(
                 self.label.as_ptr() as *const c_char,
                 values.as_ptr(),
-                number_of_rows as i32, // Not sure why C++ code uses a signed value here
-                number_of_cols as i32, // Not sure why C++ code uses a signed value here
+                number_of_rows,
+                number_of_cols,
                 scale_range.0,
                 scale_range.1,
                 // "no label" is taken as null pointer in the C++ code, but we're using
@@ -374,10 +933,235 @@ impl PlotHeatmap {
                 self.drawarea_upper_right,
             );
         }
+        Ok(())
+    }
+
+    /// Internal helper that checks the arguments of [`PlotHeatmap::try_plot`], and returns the
+    /// number of rows and columns and the scale range to pass to ImPlot. Nothing else is passed
+    /// to ImPlot, so it can't get values that are inconsistent with the dimensions.
+    fn checked_plot_arguments(
+        &self,
+        values: &[f64],
+        number_of_rows: u32,
+        number_of_cols: u32,
+    ) -> Result<(i32, i32, (f64, f64)), PlotError> {
+        let expected = (number_of_rows as usize)
+            .checked_mul(number_of_cols as usize)
+            .ok_or(PlotError::CountOverflow { count: usize::MAX })?;
+        if values.len() != expected {
+            return Err(PlotError::DimensionMismatch {
+                expected,
+                actual: values.len(),
+            });
+        }
+        if values.is_empty() {
+            return Err(PlotError::EmptyData);
+        }
+        // ImPlot multiplies the rows and columns as ints, so the product has to fit as well
+        checked_point_count(expected)?;
+        // Not sure why C++ code uses signed values for the rows and columns
+        let number_of_rows = checked_point_count(number_of_rows as usize)?;
+        let number_of_cols = checked_point_count(number_of_cols as usize)?;
+
+        let scale_range = self.scale_range_for(values);
+        let bounds = [
+            scale_range.0,
+            scale_range.1,
+            self.drawarea_lower_left.x,
+            self.drawarea_lower_left.y,
+            self.drawarea_upper_right.x,
+            self.drawarea_upper_right.y,
+        ];
+        if !bounds.iter().all(|bound| bound.is_finite()) {
+            return Err(PlotError::NonFiniteBounds);
+        }
+        Ok((number_of_rows, number_of_cols, scale_range))
+    }
+
+    /// Internal helper that returns the scale range set with [`PlotHeatmap::with_scale`], or
+    /// the range of the given values if none was set, which is `(0, 1)` without values.
+    fn scale_range_for(&self, values: &[f64]) -> (f64, f64) {
+        self.scale_range.unwrap_or_else(|| {
+            let (mut min_seen, mut max_seen) = match values.first() {
+                Some(first) => (*first, *first),
+                None => return (0.0, 1.0),
+            };
+            values.iter().for_each(|value| {
+                min_seen = min_seen.min(*value);
+                max_seen = max_seen.max(*value);
+            });
+            (min_seen, max_seen)
+        })
+    }
+
+    /// Returns the `(row, column)` of the heatmap cell at the given point in plot coordinates,
+    /// or `None` if the point is outside of the drawing area. Row 0 is the top row, the same way
+    /// [`PlotHeatmap::plot`] lays out its row-major values. Points exactly on the boundary between
+    /// two cells belong to the cell to the right of or below the boundary, points on the outer
+    /// edges of the drawing area belong to the cells along that edge.
+    pub fn cell_at(
+        &self,
+        point: impl Into<PlotPoint>,
+        number_of_rows: u32,
+        number_of_cols: u32,
+    ) -> Option<(u32, u32)> {
+        let point = point.into();
+        let (lower_left, upper_right) = (&self.drawarea_lower_left, &self.drawarea_upper_right);
+        // Fractions of the way through the drawing area, from the left and from the top
+        let fraction_x = (point.x - lower_left.x) / (upper_right.x - lower_left.x);
+        let fraction_y = (upper_right.y - point.y) / (upper_right.y - lower_left.y);
+        // This also rejects NaN, which results from empty drawing areas
+        if !(0.0..=1.0).contains(&fraction_x) || !(0.0..=1.0).contains(&fraction_y) {
+            return None;
+        }
+        if number_of_rows == 0 || number_of_cols == 0 {
+            return None;
+        }
+
+        let to_index =
+            |fraction: f64, count: u32| ((fraction * count as f64) as u32).min(count - 1);
+        Some((
+            to_index(fraction_y, number_of_rows),
+            to_index(fraction_x, number_of_cols),
+        ))
+    }
+
+    /// Returns the heatmap cell under the mouse cursor, along with its value, if the plot is
+    /// hovered and the mouse is over a cell. The arguments have to be the same as the ones
+    /// passed to [`PlotHeatmap::plot`]. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build).
+    #[track_caller]
+    pub fn hovered_cell(
+        &self,
+        values: &[f64],
+        number_of_rows: u32,
+        number_of_cols: u32,
+    ) -> Option<HeatmapCell> {
+        if !crate::is_plot_hovered() {
+            return None;
+        }
+        let mouse_position = crate::get_plot_mouse_position(None);
+        let (row, col) = self.cell_at(mouse_position, number_of_rows, number_of_cols)?;
+        let value = *values.get(row as usize * number_of_cols as usize + col as usize)?;
+        Some(HeatmapCell { row, col, value })
+    }
+
+    /// Like [`PlotHeatmap::hovered_cell`], but also shows the hovered cell in a tooltip, in the
+    /// form `row 12, col 7: 3.520`.
+    #[track_caller]
+    pub fn show_hovered_cell_tooltip(
+        &self,
+        values: &[f64],
+        number_of_rows: u32,
+        number_of_cols: u32,
+    ) -> Option<HeatmapCell> {
+        let cell = self.hovered_cell(values, number_of_rows, number_of_cols)?;
+        crate::overlays::show_tooltip(&format!(
+            "row {}, col {}: {:.3}",
+            cell.row, cell.col, cell.value
+        ));
+        Some(cell)
+    }
+}
+
+/// A cell of a heatmap, as returned by [`PlotHeatmap::hovered_cell`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct HeatmapCell {
+    /// Row of the cell, where row 0 is the top row
+    pub row: u32,
+    /// Column of the cell, where column 0 is the leftmost column
+    pub col: u32,
+    /// Value of the cell
+    pub value: f64,
+}
+
+/// Draws a heatmap in a plot with a colormap scale to the right of it, at the same height and
+/// with the same value range. The plot gets the available width minus the width reserved for the
+/// scale, so the two stay aligned when the window is resized:
+/// ```no_run
+/// # use implot::{HeatmapWithScale, Plot, PlotHeatmap, PlotUi};
+/// # fn example(plot_ui: &PlotUi, values: &[f64]) {
+/// HeatmapWithScale::new(
+///     Plot::new("Heatmap").size([0.0, 300.0]),
+///     PlotHeatmap::new("values"),
+/// )
+/// .build(plot_ui, values, 10, 10, |heatmap| {
+///     heatmap.show_hovered_cell_tooltip(values, 10, 10);
+/// });
+/// # }
+/// ```
+pub struct HeatmapWithScale {
+    /// Plot to draw the heatmap in. Its width is replaced, its height is used for the scale too.
+    plot: Plot,
+    /// Heatmap to draw
+    heatmap: PlotHeatmap,
+    /// Width reserved for the colormap scale, in pixels
+    scale_width: f32,
+}
+
+impl HeatmapWithScale {
+    /// Create a new heatmap with a scale. The width of the given plot is ignored, since the plot
+    /// fills the width that is not reserved for the scale. Does not draw anything yet.
+    pub fn new(plot: Plot, heatmap: PlotHeatmap) -> Self {
+        Self {
+            plot,
+            heatmap,
+            scale_width: 100.0,
+        }
+    }
+
+    /// Set the width reserved for the colormap scale, in pixels. The scale is as wide as its
+    /// labels need, so this should leave room for the longest label. Defaults to 100.
+    pub fn with_scale_width(mut self, scale_width: f32) -> Self {
+        self.scale_width = scale_width;
+        self
+    }
+
+    /// Draw the plot with the heatmap, using the values, number of rows and number of columns as
+    /// in [`PlotHeatmap::plot`], followed by the scale. If the heatmap has no scale set with
+    /// [`PlotHeatmap::with_scale`], the range of the values is used for both. The closure runs
+    /// inside the plot after the heatmap was drawn, its result is returned, or `None` if the
+    /// plot is not shown.
+    pub fn build<R, F: FnOnce(&PlotHeatmap) -> R>(
+        self,
+        plot_ui: &PlotUi,
+        values: &[f64],
+        number_of_rows: u32,
+        number_of_cols: u32,
+        f: F,
+    ) -> Option<R> {
+        let (scale_min, scale_max) = self.heatmap.scale_range_for(values);
+        let heatmap = self.heatmap.with_scale(scale_min, scale_max);
+
+        let available = content_region_avail();
+        let spacing = item_spacing().x;
+        // Resolve the height like imgui does for the plot, so the scale gets the same height:
+        // zero means the default plot height, negative heights are relative to the space left
+        let height = self.plot.configured_size()[1];
+        let height = if height == 0.0 {
+            plot_ui.context.default_plot_size().y
+        } else if height < 0.0 {
+            (available.y + height).max(1.0)
+        } else {
+            height
+        };
+        // A plot width of zero would make ImPlot use its default width instead
+        let plot_width = (available.x - self.scale_width - spacing).max(1.0);
+        let plot = self.plot.size([plot_width, height]);
+
+        let result = plot.run_in_plot(plot_ui, || {
+            heatmap.plot(values, number_of_rows, number_of_cols);
+            f(&heatmap)
+        });
+
+        same_line();
+        show_colormap_scale(scale_min, scale_max, height);
+        result
     }
 }
 
-/// Struct to provide stem plotting functionality.
+/// Struct to provide stem plotting functionality. Stems are drawn with the line weight, marker
+/// and colors of the current style, like lines are.
 pub struct PlotStems {
     /// Label to show in the legend for this line
     label: CString,
@@ -388,7 +1172,7 @@ pub struct PlotStems {
 
 impl PlotStems {
     /// Create a new stem plot to be shown. Does not draw anything by itself, call
-    /// [`PlotStems::plot`] on the struct for that.
+    /// [`PlotStems::plot`] or [`PlotStems::plot_values`] on the struct for that.
     pub fn new(label: &str) -> Self {
         Self {
             label: CString::new(label)
@@ -397,7 +1181,7 @@ impl PlotStems {
         }
     }
 
-    /// Set the reference y value for the stems
+    /// Set the reference y value for the stems, which they are drawn from. The default is 0.
     pub fn with_reference_y(mut self, reference_y: f64) -> Self {
         self.reference_y = reference_y;
         self
@@ -406,22 +1190,740 @@ impl PlotStems {
     /// Draw a previously-created stem plot. Use this in closures passed to
     /// [`Plot::build()`](struct.Plot.html#method.build). The `axis_positions` specify where on the
     /// X axis the stems are drawn, and the `stem_values` specify what values the stems have.
+    #[track_caller]
     pub fn plot(&self, axis_positions: &[f64], stem_values: &[f64]) {
+        if !check_inside_plot("PlotStems::plot") {
+            return;
+        }
         let number_of_points = axis_positions.len().min(stem_values.len());
         // If there is no data to plot, we stop here
         if number_of_points == 0 {
             return;
         }
+        let number_of_points = match point_count_or_skip(number_of_points, &self.label) {
+            Some(number_of_points) => number_of_points,
+            None => return,
+        };
+        record_item_plotted(number_of_points as usize);
         unsafe {
             sys::ImPlot_PlotStemsdoublePtrdoublePtr(
                 self.label.as_ptr() as *const c_char,
                 axis_positions.as_ptr(),
                 stem_values.as_ptr(),
-                number_of_points as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                number_of_points,
                 self.reference_y,
                 0,                                 // No offset
                 std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
             );
         }
     }
+
+    /// Draw a previously-created stem plot with the stems at the X positions 0, 1, 2 and so on,
+    /// for example for the samples of a discrete-time signal. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build).
+    #[track_caller]
+    pub fn plot_values(&self, stem_values: &[f64]) {
+        if !check_inside_plot("PlotStems::plot_values") {
+            return;
+        }
+        // If there is no data to plot, we stop here
+        if stem_values.is_empty() {
+            return;
+        }
+        let number_of_points = match point_count_or_skip(stem_values.len(), &self.label) {
+            Some(number_of_points) => number_of_points,
+            None => return,
+        };
+        record_item_plotted(number_of_points as usize);
+        unsafe {
+            sys::ImPlot_PlotStemsdoublePtrInt(
+                self.label.as_ptr() as *const c_char,
+                stem_values.as_ptr(),
+                number_of_points,
+                self.reference_y,
+                1.0,                               // X scale, one X unit per stem
+                0.0,                               // X position of the first stem
+                0,                                 // No offset
+                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+            );
+        }
+    }
+}
+
+/// Struct to provide functionality for shading the area between two curves, such as an
+/// uncertainty band around a line. The area is filled with the [`PlotColorElement::Fill`]
+/// color, with its alpha multiplied by the
+/// [`StyleVar::FillAlpha`](enum.StyleVar.html#variant.FillAlpha) style variable.
+pub struct PlotShaded {
+    /// Label to show in the legend for this shaded area
+    label: CString,
+}
+
+impl PlotShaded {
+    /// Create a new shaded area to be shown. Does not draw anything by itself, call
+    /// [`PlotShaded::plot_between`] or [`PlotShaded::plot_to_ref`] on the struct for that.
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+        }
+    }
+
+    /// Shade the area between the lower and the upper curve, which share the X positions in
+    /// `x`. Use this in closures passed to [`Plot::build()`](struct.Plot.html#method.build).
+    ///
+    /// Nothing is drawn without points. If the slices don't all have the same length, this
+    /// fails a debug assertion, and release builds skip the shaded area, see
+    /// [`PlotShaded::try_plot_between`].
+    #[rustversion::attr(since(1.48), doc(alias = "PlotShaded"))]
+    #[track_caller]
+    pub fn plot_between(&self, x: &[f64], y_lower: &[f64], y_upper: &[f64]) {
+        match self.try_plot_between(x, y_lower, y_upper) {
+            Ok(()) | Err(PlotError::EmptyData) => {}
+            Err(error) => report_plot_error(&self.label, &error),
+        }
+    }
+
+    /// Like [`PlotShaded::plot_between`], but returns an error instead of shading the area if
+    /// there are no points, if the slices don't all have the same length, or if there are too
+    /// many points.
+    #[track_caller]
+    pub fn try_plot_between(
+        &self,
+        x: &[f64],
+        y_lower: &[f64],
+        y_upper: &[f64],
+    ) -> Result<(), PlotError> {
+        if !check_inside_plot("PlotShaded::plot_between") {
+            return Ok(());
+        }
+        if let Some(mismatched) = [y_lower, y_upper].iter().find(|y| y.len() != x.len()) {
+            return Err(PlotError::DimensionMismatch {
+                expected: x.len(),
+                actual: mismatched.len(),
+            });
+        }
+        if x.is_empty() {
+            return Err(PlotError::EmptyData);
+        }
+        let number_of_points = checked_point_count(x.len())?;
+        record_item_plotted(x.len());
+        unsafe {
+            sys::ImPlot_PlotShadeddoublePtrdoublePtrdoublePtr(
+                self.label.as_ptr() as *const c_char,
+                x.as_ptr(),
+                y_lower.as_ptr(),
+                y_upper.as_ptr(),
+                number_of_points,
+                0,                                 // No offset
+                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+            );
+        }
+        Ok(())
+    }
+
+    /// Shade the area between a curve and the horizontal line at `y_ref`, such as the area
+    /// under an envelope down to zero. `f64::NEG_INFINITY` and `f64::INFINITY` are passed on
+    /// to ImPlot as they are, and fill to the bottom and the top of the plot area. Use this in
+    /// closures passed to [`Plot::build()`](struct.Plot.html#method.build).
+    ///
+    /// Nothing is drawn without points. If the slices don't have the same length, this fails
+    /// a debug assertion, and release builds skip the shaded area, see
+    /// [`PlotShaded::try_plot_to_ref`].
+    #[rustversion::attr(since(1.48), doc(alias = "PlotShaded"))]
+    #[track_caller]
+    pub fn plot_to_ref(&self, x: &[f64], y: &[f64], y_ref: f64) {
+        match self.try_plot_to_ref(x, y, y_ref) {
+            Ok(()) | Err(PlotError::EmptyData) => {}
+            Err(error) => report_plot_error(&self.label, &error),
+        }
+    }
+
+    /// Like [`PlotShaded::plot_to_ref`], but returns an error instead of shading the area if
+    /// there are no points, if the slices don't have the same length, or if there are too many
+    /// points.
+    #[track_caller]
+    pub fn try_plot_to_ref(&self, x: &[f64], y: &[f64], y_ref: f64) -> Result<(), PlotError> {
+        if !check_inside_plot("PlotShaded::plot_to_ref") {
+            return Ok(());
+        }
+        if y.len() != x.len() {
+            return Err(PlotError::DimensionMismatch {
+                expected: x.len(),
+                actual: y.len(),
+            });
+        }
+        if x.is_empty() {
+            return Err(PlotError::EmptyData);
+        }
+        let number_of_points = checked_point_count(x.len())?;
+        record_item_plotted(x.len());
+        unsafe {
+            sys::ImPlot_PlotShadeddoublePtrdoublePtrInt(
+                self.label.as_ptr() as *const c_char,
+                x.as_ptr(),
+                y.as_ptr(),
+                number_of_points,
+                y_ref,
+                0,                                 // No offset
+                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Struct to provide pie chart plotting functionality. Unlike the other plot elements, a pie
+/// chart has one label per slice, which are passed to [`PlotPieChart::plot`] with the values.
+pub struct PlotPieChart {
+    /// Center of the pie, in plot coordinates
+    center: PlotPoint,
+
+    /// Radius of the pie, in plot units
+    radius: f64,
+
+    /// Whether the values are scaled to fill the whole pie
+    normalize: bool,
+
+    /// Label C style format string for the values, which are drawn on the slices. None means
+    /// don't show the values.
+    label_format: Option<CString>,
+
+    /// Angle of the start of the first slice, in degrees
+    start_angle: f64,
+}
+
+impl PlotPieChart {
+    /// Create a new pie chart with the given center and radius, in plot coordinates. Uses the
+    /// same defaults as the C++ version for everything else. Does not draw anything by itself,
+    /// call [`PlotPieChart::plot`] on the struct for that.
+    ///
+    /// Since ImPlot doesn't make the pie round on its own, a plot with an equal axis ratio (see
+    /// [`PlotFlags::AXIS_EQUAL`](struct.PlotFlags.html#associatedconstant.AXIS_EQUAL)) is
+    /// usually what's wanted for pie charts.
+    pub fn new(center: impl Into<PlotPoint>, radius: f64) -> Self {
+        Self {
+            center: center.into(),
+            radius,
+            normalize: false, // Default values taken from C++ implot
+            label_format: Some(CString::new("%.1f").unwrap()),
+            start_angle: 90.0,
+        }
+    }
+
+    /// Set whether the values are scaled so that the slices fill the whole pie. Without this,
+    /// the values are taken as fractions of the pie, unless they add up to more than one, in
+    /// which case ImPlot scales them anyway.
+    pub fn with_normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Specify the C style format string for the values drawn on the slices, such as `"%.0f%%"`.
+    /// The default is `"%.1f"`, and `None` turns the value labels off.
+    ///
+    /// # Panics
+    /// Will panic if the label format string contains internal null bytes.
+    pub fn with_label_format(mut self, label_format: Option<&str>) -> Self {
+        self.label_format = label_format.map(|x| {
+            CString::new(x)
+                .unwrap_or_else(|_| panic!("Format label string has internal null bytes: {}", x))
+        });
+        self
+    }
+
+    /// Set the angle at which the first slice starts, in degrees counterclockwise from the
+    /// positive X axis. The default is 90, the top of the pie.
+    pub fn with_start_angle(mut self, degrees: f64) -> Self {
+        self.start_angle = degrees;
+        self
+    }
+
+    /// Draw the pie chart with one slice per value, labeled with the label at the same index.
+    /// The labels are shown in the legend. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build).
+    ///
+    /// Nothing is drawn without values, or if they add up to zero. If the pie chart can't be
+    /// plotted for any of the other reasons [`PlotPieChart::try_plot`] returns an error for,
+    /// such as a number of labels that doesn't match the number of values, this fails a debug
+    /// assertion, and release builds skip the pie chart.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotPieChart"))]
+    #[track_caller]
+    pub fn plot<S: AsRef<str>>(&self, labels: &[S], values: &[f64]) {
+        match self.try_plot(labels, values) {
+            Ok(()) | Err(PlotError::EmptyData) | Err(PlotError::ZeroSum) => {}
+            // Pie charts have no label of their own to name them by
+            Err(error) => report_plot_error(&CString::new("pie chart").unwrap(), &error),
+        }
+    }
+
+    /// Like [`PlotPieChart::plot`], but returns an error instead of plotting the pie chart if
+    /// there are no values, if the number of labels isn't the number of values, if a label
+    /// contains internal null bytes, if there are too many values, or if they add up to zero.
+    #[track_caller]
+    pub fn try_plot<S: AsRef<str>>(&self, labels: &[S], values: &[f64]) -> Result<(), PlotError> {
+        if !check_inside_plot("PlotPieChart::plot") {
+            return Ok(());
+        }
+        if values.is_empty() {
+            return Err(PlotError::EmptyData);
+        }
+        if labels.len() != values.len() {
+            return Err(PlotError::DimensionMismatch {
+                expected: values.len(),
+                actual: labels.len(),
+            });
+        }
+        let number_of_values = checked_point_count(values.len())?;
+        if values.iter().sum::<f64>() == 0.0 {
+            return Err(PlotError::ZeroSum);
+        }
+        // ImPlot takes the labels as an array of C string pointers, which point into the
+        // CStrings kept alive here until the call returns
+        let labels = labels
+            .iter()
+            .map(|label| CString::new(label.as_ref()).map_err(|_| PlotError::LabelContainsNul))
+            .collect::<Result<Vec<CString>, PlotError>>()?;
+        let label_pointers: Vec<*const c_char> =
+            labels.iter().map(|label| label.as_ptr()).collect();
+
+        record_item_plotted(values.len());
+        unsafe {
+            sys::ImPlot_PlotPieChartdoublePtr(
+                label_pointers.as_ptr(),
+                values.as_ptr(),
+                number_of_values,
+                self.center.x,
+                self.center.y,
+                self.radius,
+                self.normalize,
+                // "no label" is taken as null pointer in the C++ code
+                self.label_format
+                    .as_ref()
+                    .map_or(std::ptr::null(), |format| format.as_ptr()),
+                self.start_angle,
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "imgui-rs")]
+    use crate::test_support::{
+        panic_message_of_test_plot, stats_of_test_plot, with_test_frame, with_test_frames,
+    };
+    #[cfg(feature = "imgui-rs")]
+    use crate::Plot;
+
+    #[test]
+    fn test_checked_point_count() {
+        assert_eq!(checked_point_count(0), Ok(0));
+        assert_eq!(checked_point_count(MAX_PLOT_POINTS), Ok(i32::MAX));
+        let error = checked_point_count(MAX_PLOT_POINTS + 1).unwrap_err();
+        assert_eq!(
+            error,
+            PlotError::CountOverflow {
+                count: MAX_PLOT_POINTS + 1
+            }
+        );
+        let label = CString::new("points").unwrap();
+        let result = std::panic::catch_unwind(|| point_count_or_skip(usize::MAX, &label));
+        assert_eq!(result.is_err(), cfg!(debug_assertions));
+        match result {
+            Ok(count) => assert_eq!(count, None),
+            Err(panic) => {
+                let message = panic.downcast::<String>().unwrap();
+                assert!(message.starts_with("plot element \"points\" can't be plotted"));
+                assert!(message.contains(&usize::MAX.to_string()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_heatmap_arguments_match_the_values() {
+        let heatmap = PlotHeatmap::new("heatmap");
+        let values: Vec<f64> = (0..50u32).map(f64::from).collect();
+        for rows in 0..8 {
+            for cols in 0..8 {
+                for len in 0..values.len() {
+                    let arguments = heatmap.checked_plot_arguments(&values[..len], rows, cols);
+                    match arguments {
+                        Ok((checked_rows, checked_cols, _)) => {
+                            assert_eq!((checked_rows as u32, checked_cols as u32), (rows, cols));
+                            assert_eq!((checked_rows * checked_cols) as usize, len);
+                        }
+                        Err(PlotError::EmptyData) => assert_eq!(len, 0),
+                        Err(error) => assert_eq!(
+                            error,
+                            PlotError::DimensionMismatch {
+                                expected: (rows * cols) as usize,
+                                actual: len
+                            }
+                        ),
+                    }
+                }
+            }
+        }
+        assert_eq!(
+            heatmap.checked_plot_arguments(&values[..1], u32::MAX, 1),
+            Err(PlotError::DimensionMismatch {
+                expected: u32::MAX as usize,
+                actual: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_heatmap_bounds_must_be_finite() {
+        let values = [1.0, f64::INFINITY];
+        let heatmap = PlotHeatmap::new("heatmap");
+        assert_eq!(
+            heatmap.checked_plot_arguments(&values, 1, 2),
+            Err(PlotError::NonFiniteBounds)
+        );
+        let heatmap = heatmap.with_scale(0.0, 1.0);
+        assert!(heatmap.checked_plot_arguments(&values, 1, 2).is_ok());
+        let heatmap = heatmap.with_drawing_area([0.0, 0.0], [f64::NAN, 1.0]);
+        assert_eq!(
+            heatmap.checked_plot_arguments(&values, 1, 2),
+            Err(PlotError::NonFiniteBounds)
+        );
+        assert_eq!(
+            PlotHeatmap::try_new("nul\0").err(),
+            Some(PlotError::LabelContainsNul)
+        );
+    }
+
+    #[test]
+    fn test_plot_outside_plot_points_at_caller() {
+        // The elements are plotted outside of Plot::build, which ImPlot doesn't allow
+        let call_line = line!() + 1;
+        let result = std::panic::catch_unwind(|| PlotLine::new("line").plot(&[0.0], &[1.0]));
+        if cfg!(debug_assertions) {
+            let message = result.unwrap_err().downcast::<String>().unwrap();
+            assert!(message.contains("PlotLine::plot was called outside of a plot"));
+            assert!(message.contains(&format!("{}:{}:", file!(), call_line)));
+        } else {
+            assert!(result.is_ok());
+        }
+
+        let result = std::panic::catch_unwind(|| PlotText::new("text").plot(0.0, 0.0, false));
+        assert_eq!(result.is_err(), cfg!(debug_assertions));
+        let result = std::panic::catch_unwind(|| {
+            PlotHeatmap::new("heatmap").plot(&[1.0], 1, 1);
+        });
+        assert_eq!(result.is_err(), cfg!(debug_assertions));
+    }
+
+    #[test]
+    fn test_heatmap_cell_at_boundaries() {
+        // 2 rows and 4 columns on [0, 4] x [0, 2], so every cell is 1x1
+        let heatmap = PlotHeatmap::new("heatmap").with_drawing_area(
+            ImPlotPoint { x: 0.0, y: 0.0 },
+            ImPlotPoint { x: 4.0, y: 2.0 },
+        );
+        let cell_at = |x, y| heatmap.cell_at(ImPlotPoint { x, y }, 2, 4);
+
+        assert_eq!(cell_at(0.5, 1.5), Some((0, 0)));
+        assert_eq!(cell_at(3.5, 0.5), Some((1, 3)));
+        // Inner boundaries belong to the cell to the right and below
+        assert_eq!(cell_at(1.0, 1.5), Some((0, 1)));
+        assert_eq!(cell_at(0.5, 1.0), Some((1, 0)));
+        // Outer edges belong to the cells along them
+        assert_eq!(cell_at(0.0, 2.0), Some((0, 0)));
+        assert_eq!(cell_at(4.0, 0.0), Some((1, 3)));
+    }
+
+    #[test]
+    fn test_heatmap_cell_at_outside_of_bounds() {
+        let heatmap = PlotHeatmap::new("heatmap").with_drawing_area(
+            ImPlotPoint { x: 1.0, y: 1.0 },
+            ImPlotPoint { x: 2.0, y: 2.0 },
+        );
+        let cell_at = |x, y| heatmap.cell_at(ImPlotPoint { x, y }, 3, 3);
+
+        assert_eq!(cell_at(0.99, 1.5), None);
+        assert_eq!(cell_at(2.01, 1.5), None);
+        assert_eq!(cell_at(1.5, 0.99), None);
+        assert_eq!(cell_at(1.5, 2.01), None);
+        assert_eq!(cell_at(f64::NAN, 1.5), None);
+        assert_eq!(heatmap.cell_at(ImPlotPoint { x: 1.5, y: 1.5 }, 0, 3), None);
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_lines_and_stairs_from_points() {
+        let points = [[0.0, 1.0], [1.0, 3.0], [2.0, 2.0]];
+        let stats = stats_of_test_plot(|| {
+            let line = PlotLine::new("line");
+            line.plot(&[0.0, 1.0], &[1.0, 3.0]);
+            line.plot_points(&points);
+            line.plot_points(&[]);
+            let stairs = PlotStairs::new("stairs");
+            stairs.plot(&[0.0, 1.0], &[1.0, 3.0]);
+            stairs.plot_points(&points);
+            stairs.plot_points(&[]);
+        });
+        assert_eq!(stats.items_plotted, 4);
+        assert_eq!(stats.points_submitted, 10);
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_lines_across_the_plot() {
+        let stats = stats_of_test_plot(|| {
+            PlotVLines::new("events").plot(&[0.25, f64::NAN, 0.75]);
+            PlotHLines::new("threshold").plot(&[0.5]);
+            // Nothing is passed on without finite positions
+            PlotVLines::new("no events").plot(&[]);
+            PlotHLines::new("no threshold").plot(&[f64::INFINITY]);
+        });
+        // The lines are drawn directly, so no points reach ImPlot
+        assert_eq!(stats.items_plotted, 2);
+        assert_eq!(stats.points_submitted, 0);
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_lines_across_the_plot_do_not_affect_fitting() {
+        // This checks the limits ImPlot fits the plot to, so it needs a real ImPlot rather than
+        // one that only checks how it is called
+        let mut frame = 0;
+        let mut fitted_limits = None;
+        with_test_frames(4, |plot_ui, _| {
+            if frame == 1 {
+                unsafe {
+                    sys::ImPlot_FitNextPlotAxes(true, true, true, true);
+                }
+            }
+            Plot::new("fitted")
+                .x_limits([0.0, 10.0], crate::Condition::Once)
+                .y_limits(
+                    [0.0, 10.0],
+                    crate::YAxisChoice::First,
+                    crate::Condition::Once,
+                )
+                .build(plot_ui, || {
+                    PlotLine::new("line").plot(&[2.0, 3.0], &[2.0, 3.0]);
+                    PlotVLines::new("events").plot(&[2.5]);
+                    PlotHLines::new("threshold").plot(&[2.5]);
+                    fitted_limits = Some(crate::get_plot_limits(None));
+                });
+            frame += 1;
+        });
+        // Lines between the limits before the fit would have kept them at 0 to 10. The fit
+        // padding of the default style is zero, so the limits are the ones of the line.
+        let limits = fitted_limits.expect("The plot was not shown");
+        for range in &[limits.x, limits.y] {
+            assert!((range.min - 2.0).abs() < 1e-6, "{:?}", limits);
+            assert!((range.max - 3.0).abs() < 1e-6, "{:?}", limits);
+        }
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_digital_signals_with_analog_lines() {
+        let times = [0.0, 1.0, 2.0, 3.0];
+        let stats = stats_of_test_plot(|| {
+            PlotLine::new("analog").plot(&times, &[0.1, 0.5, 0.9, 0.4]);
+            PlotDigital::new("clock").plot(&times, &[0.0, 1.0, 0.0, 1.0]);
+            // The shorter slice decides the number of points, like for lines
+            PlotDigital::new("data").plot(&times, &[1.0, 1.0]);
+            PlotDigital::new("empty").plot(&times, &[]);
+        });
+        assert_eq!(stats.items_plotted, 3);
+        assert_eq!(stats.points_submitted, 10);
+    }
+
+    #[test]
+    fn test_bar_width_and_height_are_separate() {
+        // The size passed to ImPlot is the one for the orientation of the bars
+        let bars = PlotBars::new("bars").with_bar_height(0.25);
+        assert_eq!(bars.bar_size(), DEFAULT_BAR_SIZE);
+        let bars = bars.with_bar_width(0.5);
+        assert_eq!(bars.bar_size(), 0.5);
+        let bars = bars.with_horizontal_bars();
+        assert_eq!(bars.bar_size(), 0.25);
+        let bars = PlotBars::new("bars")
+            .with_bar_width(0.5)
+            .with_horizontal_bars();
+        assert_eq!(bars.bar_size(), DEFAULT_BAR_SIZE);
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_bars_without_positions() {
+        let stats = stats_of_test_plot(|| {
+            PlotBars::new("vertical").plot_values(&[3.0, 1.0, 2.0]);
+            PlotBars::new("horizontal")
+                .with_horizontal_bars()
+                .with_bar_height(0.5)
+                .plot_values(&[3.0, 1.0]);
+            // Nothing is passed on without values
+            PlotBars::new("empty").plot_values(&[]);
+        });
+        assert_eq!(stats.items_plotted, 2);
+        assert_eq!(stats.points_submitted, 5);
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_stems_with_and_without_positions() {
+        let stats = stats_of_test_plot(|| {
+            let stems = PlotStems::new("stems").with_reference_y(-1.0);
+            stems.plot(&[0.0, 0.5, 1.0], &[1.0, 2.0]);
+            stems.plot_values(&[1.0, 2.0, 3.0]);
+            // Nothing is passed on without values
+            stems.plot(&[], &[]);
+            stems.plot_values(&[]);
+        });
+        assert_eq!(stats.items_plotted, 2);
+        assert_eq!(stats.points_submitted, 5);
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_shaded_area_checks_lengths() {
+        let values = [0.0, 1.0, 2.0, 3.0];
+        with_test_frame(|plot_ui, _| {
+            Plot::new("shaded").build(plot_ui, || {
+                let shaded = PlotShaded::new("band");
+                shaded.plot_between(&values, &values, &values);
+                shaded.plot_between(&[], &[], &[]);
+                for lower in 0..values.len() {
+                    for upper in 0..values.len() {
+                        if lower == upper {
+                            continue;
+                        }
+                        let result = shaded.try_plot_between(
+                            &values[..3],
+                            &values[..lower],
+                            &values[..upper],
+                        );
+                        assert!(matches!(result, Err(PlotError::DimensionMismatch { .. })));
+                    }
+                }
+                assert_eq!(
+                    shaded.try_plot_between(&values, &values[..2], &values),
+                    Err(PlotError::DimensionMismatch {
+                        expected: 4,
+                        actual: 2
+                    })
+                );
+
+                // Infinite references fill to the edges of the plot area
+                for y_ref in [0.0, f64::NEG_INFINITY, f64::INFINITY].iter() {
+                    assert_eq!(shaded.try_plot_to_ref(&values, &values, *y_ref), Ok(()));
+                }
+                shaded.plot_to_ref(&[], &[], 0.0);
+                assert_eq!(
+                    shaded.try_plot_to_ref(&values, &values[..3], 0.0),
+                    Err(PlotError::DimensionMismatch {
+                        expected: 4,
+                        actual: 3
+                    })
+                );
+            });
+            if cfg!(debug_assertions) {
+                let message = panic_message_of_test_plot(plot_ui, || {
+                    PlotShaded::new("band").plot_between(&values, &values, &values[..1]);
+                });
+                assert!(message.contains("\"band\" can't be plotted: the data has a length of 1"));
+            }
+        });
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_pie_chart_checks_labels_and_values() {
+        with_test_frames(2, |plot_ui, _| {
+            Plot::new("pie").build(plot_ui, || {
+                let pie = PlotPieChart::new([0.5, 0.5], 0.4).with_normalize(true);
+                pie.plot(&["a", "b", "c"], &[1.0, 2.0, 3.0]);
+                pie.plot(&[String::from("a")], &[1.0]);
+                // Zero sums and missing values are skipped without an assertion
+                pie.plot(&["a", "b"], &[0.0, 0.0]);
+                pie.plot::<&str>(&[], &[]);
+                assert_eq!(
+                    pie.try_plot(&["a", "b"], &[1.0, -1.0]),
+                    Err(PlotError::ZeroSum)
+                );
+                assert_eq!(
+                    pie.try_plot(&["a"], &[1.0, 2.0]),
+                    Err(PlotError::DimensionMismatch {
+                        expected: 2,
+                        actual: 1
+                    })
+                );
+                assert_eq!(
+                    pie.try_plot(&["a", "b\0"], &[1.0, 2.0]),
+                    Err(PlotError::LabelContainsNul)
+                );
+            });
+            if cfg!(debug_assertions) {
+                let message = panic_message_of_test_plot(plot_ui, || {
+                    PlotPieChart::new([0.5, 0.5], 0.4).plot(&["a", "b"], &[1.0]);
+                });
+                assert!(message.contains("\"pie chart\" can't be plotted: the data has a length"));
+            }
+        });
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_inconsistent_heatmap_data_does_not_reach_implot() {
+        // Any of these reaching ImPlot would make it read past the data, which the test build
+        // turns into an abort
+        let values = vec![1.0; 30];
+        with_test_frames(2, |plot_ui, _| {
+            Plot::new("inconsistent data").build(plot_ui, || {
+                let heatmap = PlotHeatmap::new("heatmap");
+                for rows in 0..6 {
+                    for cols in 0..6 {
+                        for len in 0..values.len() {
+                            if len == (rows * cols) as usize && len > 0 {
+                                continue;
+                            }
+                            assert!(heatmap.try_plot(&values[..len], rows, cols).is_err());
+                        }
+                    }
+                }
+                assert!(heatmap.try_plot(&values[..1], u32::MAX, 1).is_err());
+            });
+            if cfg!(debug_assertions) {
+                let message = panic_message_of_test_plot(plot_ui, || {
+                    PlotHeatmap::new("heatmap").plot(&[1.0], u32::MAX, 1);
+                });
+                assert!(message.contains("\"heatmap\" can't be plotted: the data has a length"));
+            }
+            assert_eq!(crate::current_plot_id(), None);
+        });
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_heatmap_scale_gets_the_plot_height() {
+        let values = [0.0, 1.0, 2.0, 3.0];
+        with_test_frames(2, |plot_ui, context| {
+            // A height of zero makes ImPlot use the default plot height
+            let default_height = context.default_plot_size().y;
+            for &(plot_height, expected) in &[(250.0, 250.0), (0.0, default_height)] {
+                let shown = HeatmapWithScale::new(
+                    Plot::new(&format!("heatmap {}", plot_height)).size([0.0, plot_height]),
+                    PlotHeatmap::new("values"),
+                )
+                .build(plot_ui, &values, 2, 2, |_| ());
+                assert_eq!(shown, Some(()));
+
+                // The scale is the last item
+                let mut scale_size = imgui::sys::ImVec2::zero();
+                unsafe { imgui::sys::igGetItemRectSize(&mut scale_size) };
+                assert_eq!(scale_size.y, expected);
+            }
+            assert_eq!(crate::current_plot_id(), None);
+        });
+    }
 }
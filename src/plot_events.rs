@@ -0,0 +1,210 @@
+//! # Plot events module
+//!
+//! This module contains [`PlotEvents`], a set of callbacks for
+//! [`Plot::build_with_events`](struct.Plot.html#method.build_with_events). This is an
+//! alternative to calling the various `is_plot_*` and `get_plot_*` functions inside the closure
+//! passed to [`Plot::build()`](struct.Plot.html#method.build).
+use crate::{
+    current_plot_id, get_plot_limits, get_plot_mouse_position, get_plot_query_checked,
+    is_plot_hovered, Plot, PlotLimits, PlotPoint, PlotStateMap, PlotUi, YAxisChoice,
+};
+use std::cell::RefCell;
+
+thread_local! {
+    /// Plot limits seen in the previous frame, for detecting limit changes in
+    /// `Plot::build_with_events`.
+    static PREVIOUS_LIMITS: RefCell<PlotStateMap<PlotLimits>> =
+        RefCell::new(PlotStateMap::default());
+}
+
+/// Callbacks for events of a plot, used with
+/// [`Plot::build_with_events`](struct.Plot.html#method.build_with_events). Each of the
+/// callbacks is invoked at most once per frame, after the closure that plots the items has run
+/// and before the plot is ended. If several of them apply in a frame, they are invoked in the
+/// order `on_hover`, `on_query`, `on_limits_changed`.
+///
+/// All the callbacks are invoked inside the plot, so they can still use functions like
+/// [`plot_text`](fn.plot_text.html) or [`get_plot_limits`](fn.get_plot_limits.html).
+#[derive(Default)]
+pub struct PlotEvents<'a> {
+    /// Called with the mouse position in plot coordinates if the plot is hovered
+    on_hover: Option<Box<dyn FnOnce(PlotPoint) + 'a>>,
+    /// Called with the query limits if there is an active query
+    on_query: Option<Box<dyn FnOnce(PlotLimits) + 'a>>,
+    /// Called with the new limits if they changed since the previous frame
+    on_limits_changed: Option<Box<dyn FnOnce(PlotLimits) + 'a>>,
+    /// Y axis used for the positions and limits passed to the callbacks
+    y_axis_choice: Option<YAxisChoice>,
+}
+
+/// Internal summary of the state of a plot, as used to decide which events to invoke.
+struct PlotEventState {
+    /// Mouse position in plot coordinates, if the plot is hovered
+    hover_position: Option<PlotPoint>,
+    /// Query limits, if there is an active query
+    query: Option<PlotLimits>,
+    /// Current plot limits
+    limits: PlotLimits,
+    /// Whether the limits differ from the ones in the previous frame
+    limits_changed: bool,
+}
+
+impl<'a> PlotEvents<'a> {
+    /// Create a new set of callbacks with none of the callbacks set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the callback invoked with the mouse position in plot coordinates while the plot is
+    /// hovered.
+    pub fn with_on_hover<F: FnOnce(PlotPoint) + 'a>(mut self, callback: F) -> Self {
+        self.on_hover = Some(Box::new(callback));
+        self
+    }
+
+    /// Set the callback invoked with the query limits while there is an active query.
+    pub fn with_on_query<F: FnOnce(PlotLimits) + 'a>(mut self, callback: F) -> Self {
+        self.on_query = Some(Box::new(callback));
+        self
+    }
+
+    /// Set the callback invoked with the new plot limits in frames where they differ from the
+    /// ones of the previous frame, for example because the user panned or zoomed the plot. This
+    /// is not invoked in the first frame a plot is shown in.
+    pub fn with_on_limits_changed<F: FnOnce(PlotLimits) + 'a>(mut self, callback: F) -> Self {
+        self.on_limits_changed = Some(Box::new(callback));
+        self
+    }
+
+    /// Set the Y axis used for the positions and limits passed to the callbacks. By default,
+    /// the Y axis that was last selected with [`set_plot_y_axis`](fn.set_plot_y_axis.html) in
+    /// the plot closure is used.
+    pub fn with_y_axis(mut self, y_axis_choice: YAxisChoice) -> Self {
+        self.y_axis_choice = Some(y_axis_choice);
+        self
+    }
+
+    /// Internal helper that reads the state of the current plot, and records its limits for
+    /// comparing them in the next frame.
+    fn gather_state(&self) -> PlotEventState {
+        let limits = get_plot_limits(self.y_axis_choice);
+        let limits_changed = match current_plot_id() {
+            Some(plot_id) => PREVIOUS_LIMITS.with(|previous_limits| {
+                let mut previous_limits = previous_limits.borrow_mut();
+                let previous = previous_limits.entry_or_insert_with(&plot_id, || limits);
+                let limits_changed = *previous != limits;
+                *previous = limits;
+                limits_changed
+            }),
+            None => false,
+        };
+        PlotEventState {
+            hover_position: if is_plot_hovered() {
+                Some(get_plot_mouse_position(self.y_axis_choice))
+            } else {
+                None
+            },
+            query: get_plot_query_checked(self.y_axis_choice),
+            limits,
+            limits_changed,
+        }
+    }
+
+    /// Internal helper that runs the plot closure and then invokes the callbacks for the state
+    /// returned by `gather_state`. This defines the order in which things happen.
+    fn run<F: FnOnce(), G: FnOnce(&Self) -> PlotEventState>(self, f: F, gather_state: G) {
+        f();
+        let state = gather_state(&self);
+        if let (Some(on_hover), Some(position)) = (self.on_hover, state.hover_position) {
+            on_hover(position);
+        }
+        if let (Some(on_query), Some(query)) = (self.on_query, state.query) {
+            on_query(query);
+        }
+        if let Some(on_limits_changed) = self.on_limits_changed {
+            if state.limits_changed {
+                on_limits_changed(state.limits);
+            }
+        }
+    }
+}
+
+impl Plot {
+    /// Like [`Plot::build()`](struct.Plot.html#method.build), but also invokes the callbacks
+    /// given in `events` for the things that happened in the plot this frame. The closure `f`
+    /// runs first, then the callbacks are invoked in the order described in [`PlotEvents`], and
+    /// then the plot is ended. If the plot is not rendered, neither the closure nor any of the
+    /// callbacks are called.
+    /// ```no_run
+    /// # use implot::{Plot, PlotEvents, PlotUi};
+    /// # fn example(plot_ui: &PlotUi) {
+    /// Plot::new("Plot with events").build_with_events(
+    ///     plot_ui,
+    ///     PlotEvents::new()
+    ///         .with_on_hover(|position| println!("Hovered at {:?}", position))
+    ///         .with_on_limits_changed(|limits| println!("New limits: {:?}", limits)),
+    ///     || {
+    ///         // Plot some things
+    ///     },
+    /// );
+    /// # }
+    /// ```
+    #[rustversion::attr(since(1.48), doc(alias = "BeginPlot"))]
+    #[rustversion::attr(since(1.48), doc(alias = "EndPlot"))]
+    pub fn build_with_events<F: FnOnce()>(self, plot_ui: &PlotUi, events: PlotEvents, f: F) {
+        self.run_in_plot(plot_ui, || events.run(f, PlotEvents::gather_state));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(x_min: f64, x_max: f64) -> PlotLimits {
+        PlotLimits::new(x_min..x_max, 0.0..1.0)
+    }
+
+    #[test]
+    fn test_callbacks_run_after_plot_closure_in_order() {
+        let log = RefCell::new(Vec::new());
+        let events = PlotEvents::new()
+            .with_on_limits_changed(|_| log.borrow_mut().push("limits"))
+            .with_on_query(|_| log.borrow_mut().push("query"))
+            .with_on_hover(|_| log.borrow_mut().push("hover"));
+        events.run(
+            || log.borrow_mut().push("items"),
+            |_| {
+                log.borrow_mut().push("gather");
+                PlotEventState {
+                    hover_position: Some(PlotPoint { x: 0.5, y: 0.5 }),
+                    query: Some(limits(0.0, 0.5)),
+                    limits: limits(0.0, 1.0),
+                    limits_changed: true,
+                }
+            },
+        );
+        assert_eq!(
+            *log.borrow(),
+            vec!["items", "gather", "hover", "query", "limits"]
+        );
+    }
+
+    #[test]
+    fn test_callbacks_only_run_for_events_that_happened() {
+        let log = RefCell::new(Vec::new());
+        let events = PlotEvents::new()
+            .with_on_limits_changed(|_| log.borrow_mut().push("limits"))
+            .with_on_query(|_| log.borrow_mut().push("query"))
+            .with_on_hover(|_| log.borrow_mut().push("hover"));
+        events.run(
+            || {},
+            |_| PlotEventState {
+                hover_position: Some(PlotPoint { x: 0.5, y: 0.5 }),
+                query: None,
+                limits: limits(0.0, 1.0),
+                limits_changed: false,
+            },
+        );
+        assert_eq!(*log.borrow(), vec!["hover"]);
+    }
+}
@@ -0,0 +1,171 @@
+//! # Plot scope module
+//!
+//! This module contains [`PlotScope`], which is handed to the closure passed to
+//! [`Plot::build_with_scope`](struct.Plot.html#method.build_with_scope). Its methods query the
+//! plot that is being built, and since a `PlotScope` only exists while a plot is being built,
+//! they can't be called in places where the free `get_plot_*` functions would return
+//! meaningless values.
+use crate::{
+    get_plot_limits, get_plot_mouse_position, get_plot_pos, get_plot_query_checked, get_plot_size,
+    is_legend_entry_hovered, is_plot_hovered, is_plot_queried, is_plot_x_axis_hovered,
+    is_plot_y_axis_hovered, pixels_to_plot_vec2, plot_to_pixels_vec2, ImVec2, Plot, PlotLimits,
+    PlotPoint, PlotUi, YAxisChoice,
+};
+use std::marker::PhantomData;
+
+/// Access to the plot that is currently being built, passed to the closure of
+/// [`Plot::build_with_scope`](struct.Plot.html#method.build_with_scope). The closure only gets
+/// a reference to it, so it can't be kept around after the plot has ended.
+///
+/// For all the methods that take a Y axis choice, `None` means that the most recently selected
+/// Y axis is used.
+///
+/// The plot can only be queried on the thread it is built on, so the scope can't be shared
+/// with other threads:
+/// ```compile_fail
+/// fn check<T: Sync>() {}
+/// check::<implot::PlotScope>();
+/// ```
+pub struct PlotScope {
+    /// Keeps the scope from being created outside of this module, and from being sent to other
+    /// threads, which aren't building the plot.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl PlotScope {
+    /// Returns true if the plot area is hovered.
+    #[rustversion::attr(since(1.48), doc(alias = "IsPlotHovered"))]
+    pub fn is_hovered(&self) -> bool {
+        is_plot_hovered()
+    }
+
+    /// Returns true if the plot is queried.
+    #[rustversion::attr(since(1.48), doc(alias = "IsPlotQueried"))]
+    pub fn is_queried(&self) -> bool {
+        is_plot_queried()
+    }
+
+    /// Returns true if the X axis area of the plot is hovered.
+    #[rustversion::attr(since(1.48), doc(alias = "IsPlotXAxisHovered"))]
+    pub fn is_x_axis_hovered(&self) -> bool {
+        is_plot_x_axis_hovered()
+    }
+
+    /// Returns true if the area of the given Y axis is hovered.
+    #[rustversion::attr(since(1.48), doc(alias = "IsPlotYAxisHovered"))]
+    pub fn is_y_axis_hovered(&self, y_axis_choice: Option<YAxisChoice>) -> bool {
+        is_plot_y_axis_hovered(y_axis_choice)
+    }
+
+    /// Returns true if the given entry of the legend is hovered.
+    ///
+    /// # Panics
+    /// Will panic if the legend entry string contains internal null bytes.
+    pub fn is_legend_entry_hovered(&self, legend_entry: &str) -> bool {
+        is_legend_entry_hovered(legend_entry)
+    }
+
+    /// Returns the mouse position in plot coordinates.
+    #[rustversion::attr(since(1.48), doc(alias = "GetPlotMousePos"))]
+    pub fn mouse_position(&self, y_axis_choice: Option<YAxisChoice>) -> PlotPoint {
+        get_plot_mouse_position(y_axis_choice)
+    }
+
+    /// Returns the limits of the plot axes.
+    #[rustversion::attr(since(1.48), doc(alias = "GetPlotLimits"))]
+    pub fn limits(&self, y_axis_choice: Option<YAxisChoice>) -> PlotLimits {
+        get_plot_limits(y_axis_choice)
+    }
+
+    /// Returns the query limits if the plot is queried, and `None` otherwise.
+    #[rustversion::attr(since(1.48), doc(alias = "GetPlotQuery"))]
+    pub fn query(&self, y_axis_choice: Option<YAxisChoice>) -> Option<PlotLimits> {
+        get_plot_query_checked(y_axis_choice)
+    }
+
+    /// Convert a position in pixels to plot coordinates.
+    #[rustversion::attr(since(1.48), doc(alias = "PixelsToPlot"))]
+    pub fn pixels_to_plot(
+        &self,
+        pixel_position: impl Into<ImVec2>,
+        y_axis_choice: Option<YAxisChoice>,
+    ) -> PlotPoint {
+        pixels_to_plot_vec2(pixel_position, y_axis_choice)
+    }
+
+    /// Convert a position in plot coordinates to pixels.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotToPixels"))]
+    pub fn plot_to_pixels(
+        &self,
+        plot_position: impl Into<PlotPoint>,
+        y_axis_choice: Option<YAxisChoice>,
+    ) -> ImVec2 {
+        plot_to_pixels_vec2(plot_position, y_axis_choice)
+    }
+
+    /// Returns the top-left position of the plot area, in pixels.
+    #[rustversion::attr(since(1.48), doc(alias = "GetPlotPos"))]
+    pub fn pos(&self) -> ImVec2 {
+        get_plot_pos()
+    }
+
+    /// Returns the size of the plot area, in pixels.
+    #[rustversion::attr(since(1.48), doc(alias = "GetPlotSize"))]
+    pub fn size(&self) -> ImVec2 {
+        get_plot_size()
+    }
+}
+
+impl Plot {
+    /// Like [`Plot::build()`](struct.Plot.html#method.build), but passes a [`PlotScope`] to the
+    /// closure, which is the way to query the plot that doesn't compile when done outside of
+    /// it. The value returned by the closure is passed on, or `None` is returned if the plot is
+    /// not rendered and the closure didn't run.
+    /// ```no_run
+    /// # use implot::{Plot, PlotLine, PlotUi};
+    /// # fn example(plot_ui: &PlotUi) {
+    /// let hovered_at = Plot::new("Plot with scope").build_with_scope(plot_ui, |plot| {
+    ///     PlotLine::new("line").plot(&[0.0, 1.0], &[0.0, 1.0]);
+    ///     if plot.is_hovered() {
+    ///         Some(plot.mouse_position(None))
+    ///     } else {
+    ///         None
+    ///     }
+    /// });
+    /// # }
+    /// ```
+    #[rustversion::attr(since(1.48), doc(alias = "BeginPlot"))]
+    #[rustversion::attr(since(1.48), doc(alias = "EndPlot"))]
+    pub fn build_with_scope<R, F: FnOnce(&PlotScope) -> R>(
+        self,
+        plot_ui: &PlotUi,
+        f: F,
+    ) -> Option<R> {
+        self.run_in_plot(plot_ui, || {
+            f(&PlotScope {
+                _not_send: PhantomData,
+            })
+        })
+    }
+}
+
+#[cfg(all(test, feature = "imgui-rs"))]
+mod tests {
+    use crate::test_support::with_test_frames;
+    use crate::{get_plot_limits_checked, Plot, PlotLimits};
+
+    #[test]
+    fn test_plot_scope_queries_the_current_plot() {
+        with_test_frames(2, |plot_ui, _| {
+            let result = Plot::new("scoped").build_with_scope(plot_ui, |plot| {
+                assert!(get_plot_limits_checked(None).is_some());
+                (plot.is_hovered(), plot.limits(None))
+            });
+            if let Some((hovered, limits)) = result {
+                assert!(!hovered);
+                assert_eq!(limits, PlotLimits::new(0.0..1.0, 0.0..1.0));
+            }
+            assert_eq!(get_plot_limits_checked(None), None);
+        });
+    }
+}
@@ -0,0 +1,182 @@
+//! # Plot state module
+//!
+//! This module contains [`PlotStateMap`], a small container for state that has to persist across
+//! frames for each plot, such as the anchor points of a measurement. It is used by the stateful
+//! helpers of this crate, and can be used for custom helpers the same way.
+use std::collections::HashMap;
+
+/// A single entry of a [`PlotStateMap`], along with the frame it was last used in.
+struct PlotStateEntry<T> {
+    /// The stored state
+    value: T,
+    /// The imgui frame in which the entry was last accessed through `entry()`
+    last_used_frame: i32,
+}
+
+/// Map from plot IDs to per-plot state of type `T`. Entries are created on first access and
+/// removed again once they have not been accessed for a configurable number of frames, so state
+/// for plots that are no longer shown does not pile up.
+///
+/// The plot ID is usually the title of the plot (the full string passed to
+/// [`Plot::new`](struct.Plot.html#method.new), including any `##` suffix), but any string that
+/// identifies the state can be used. Inside of a plot, [`current_plot_id`](fn.current_plot_id.html)
+/// returns the ID of the current plot.
+/// ```no_run
+/// # use implot::{current_plot_id, Plot, PlotStateMap, PlotUi};
+/// # fn example(plot_ui: &PlotUi, click_counts: &mut PlotStateMap<u32>) {
+/// // Created once, for example in the application state:
+/// // let click_counts = PlotStateMap::<u32>::new(60);
+/// Plot::new("My plot").build(plot_ui, || {
+///     let count = click_counts.entry(&current_plot_id().unwrap());
+///     *count += 1;
+/// });
+/// # }
+/// ```
+pub struct PlotStateMap<T> {
+    /// The stored entries, by plot ID
+    entries: HashMap<String, PlotStateEntry<T>>,
+    /// Number of frames after which unused entries are removed
+    max_unused_frames: u32,
+    /// The most recent frame in which unused entries were removed
+    last_pruned_frame: i32,
+}
+
+impl<T> PlotStateMap<T> {
+    /// Create a new map. Entries that have not been accessed through `entry()` for more than
+    /// `max_unused_frames` frames are removed.
+    pub fn new(max_unused_frames: u32) -> Self {
+        Self {
+            entries: HashMap::new(),
+            max_unused_frames,
+            last_pruned_frame: i32::MIN,
+        }
+    }
+
+    /// Get the state for the given ID, inserting a default value if there is none yet. This marks
+    /// the entry as used in the current frame and removes entries that have been unused for too
+    /// long. This has to be called while an imgui frame is active, since it uses imgui's frame
+    /// counter.
+    pub fn entry(&mut self, id: &str) -> &mut T
+    where
+        T: Default,
+    {
+        self.entry_or_insert_with(id, T::default)
+    }
+
+    /// Like [`PlotStateMap::entry`], but uses the given function to create the state if there is
+    /// none yet.
+    pub fn entry_or_insert_with<F: FnOnce() -> T>(&mut self, id: &str, default: F) -> &mut T {
+        let frame = crate::imgui_compat::frame_count();
+        self.entry_at_frame(id, frame, default)
+    }
+
+    /// Get the state for the given ID without marking it as used, if there is any.
+    pub fn get(&self, id: &str) -> Option<&T> {
+        self.entries.get(id).map(|entry| &entry.value)
+    }
+
+    /// Get the state for the given ID mutably without marking it as used, if there is any.
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut T> {
+        self.entries.get_mut(id).map(|entry| &mut entry.value)
+    }
+
+    /// Remove the state for the given ID, returning it if there was any.
+    pub fn remove(&mut self, id: &str) -> Option<T> {
+        self.entries.remove(id).map(|entry| entry.value)
+    }
+
+    /// Remove all state.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Returns the number of stored entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if there are no stored entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterate over all the stored IDs and their state.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &T)> {
+        self.entries
+            .iter()
+            .map(|(id, entry)| (id.as_str(), &entry.value))
+    }
+
+    /// Internal implementation of `entry_or_insert_with`, taking the current frame explicitly.
+    fn entry_at_frame<F: FnOnce() -> T>(&mut self, id: &str, frame: i32, default: F) -> &mut T {
+        if frame != self.last_pruned_frame {
+            let max_unused_frames = i64::from(self.max_unused_frames);
+            self.entries.retain(|_, entry| {
+                i64::from(frame) - i64::from(entry.last_used_frame) <= max_unused_frames
+            });
+            self.last_pruned_frame = frame;
+        }
+
+        // Looking up first avoids allocating a new key string for entries that already exist
+        if !self.entries.contains_key(id) {
+            self.entries.insert(
+                id.to_owned(),
+                PlotStateEntry {
+                    value: default(),
+                    last_used_frame: frame,
+                },
+            );
+        }
+        let entry = self.entries.get_mut(id).unwrap();
+        entry.last_used_frame = frame;
+        &mut entry.value
+    }
+}
+
+impl<T> Default for PlotStateMap<T> {
+    /// Create a map that removes entries after one second at 60 frames per second.
+    fn default() -> Self {
+        Self::new(60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unused_entries_are_pruned() {
+        let mut map = PlotStateMap::<u32>::new(5);
+        *map.entry_at_frame("a", 10, Default::default) += 1;
+        // Entries are kept for max_unused_frames frames after their last use
+        map.entry_at_frame("b", 15, Default::default);
+        assert_eq!(map.get("a"), Some(&1));
+        map.entry_at_frame("b", 16, Default::default);
+        assert_eq!(map.get("a"), None);
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_entries_used_within_the_window_survive() {
+        let mut map = PlotStateMap::<u32>::new(5);
+        *map.entry_at_frame("a", 10, Default::default) += 1;
+        *map.entry_at_frame("a", 14, || unreachable!()) += 1;
+        map.entry_at_frame("b", 19, Default::default);
+        assert_eq!(map.get("a"), Some(&2));
+        map.entry_at_frame("b", 20, Default::default);
+        assert_eq!(map.get("a"), None);
+    }
+
+    #[test]
+    fn test_get_does_not_mark_entries_as_used() {
+        let mut map = PlotStateMap::<u32>::new(5);
+        map.entry_at_frame("a", 10, Default::default);
+        map.entry_at_frame("b", 14, Default::default);
+        // Neither of these counts as a use in frame 14
+        assert_eq!(map.get("a"), Some(&0));
+        *map.get_mut("a").unwrap() = 3;
+        map.entry_at_frame("b", 16, Default::default);
+        assert_eq!(map.get("a"), None);
+        assert_eq!(map.get_mut("a"), None);
+    }
+}
@@ -0,0 +1,128 @@
+//! # Quick plot module
+//!
+//! This module contains one-call plotting functions for debugging, in the spirit of
+//! matplotlib's `plt.plot`. Each call creates a plot that fills the available width, fits its
+//! axes to the data when it first appears, and shows a single series:
+//! ```no_run
+//! # use implot::PlotUi;
+//! # fn example(plot_ui: &PlotUi, x: &[f64], y: &[f64]) {
+//! implot::quick::line(plot_ui, "residuals", x, y);
+//! # }
+//! ```
+//! The ID of each plot is derived from its label. Calling a quick plot function several times
+//! with the same label in one frame, for example in a loop, numbers the plots by the order of
+//! the calls, so they don't share their state - as long as the calls happen in the same order
+//! every frame, each of them keeps its zoom and pan state. Use distinct labels where the order
+//! can change. The plots are numbered separately for each imgui window and for each ID pushed
+//! with imgui's `push_id`, so plots in one of these don't change the numbers in another.
+use crate::imgui_compat::{current_id_scope, frame_count};
+use crate::{BinMethod, Plot, PlotHistogram, PlotLine, PlotScatter, PlotStateMap, PlotUi};
+use implot_sys as sys;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Size of quick plots: the full available width, and ImPlot's default height.
+const QUICK_PLOT_SIZE: [f32; 2] = [-1.0, 0.0];
+
+thread_local! {
+    /// Number of quick plots shown per ID scope and label in the current frame.
+    static QUICK_PLOT_IDS: RefCell<QuickPlotIds> = RefCell::new(QuickPlotIds::new());
+    /// Quick plots that were shown recently, which are not fitted again. Plots that have not
+    /// been shown for a second at 60 frames per second are fitted again when they reappear.
+    static SHOWN_QUICK_PLOTS: RefCell<PlotStateMap<()>> = RefCell::new(PlotStateMap::new(60));
+}
+
+/// Show a line plot of the data.
+pub fn line(plot_ui: &PlotUi, label: &str, x: &[f64], y: &[f64]) {
+    show(plot_ui, label, || PlotLine::new(label).plot(x, y));
+}
+
+/// Show a scatter plot of the data.
+pub fn scatter(plot_ui: &PlotUi, label: &str, x: &[f64], y: &[f64]) {
+    show(plot_ui, label, || PlotScatter::new(label).plot(x, y));
+}
+
+/// Show a histogram of the values, with the given number of bins of equal width between the
+/// smallest and the largest value. Values that are not finite are left out, see
+/// [`histogram_bins`](../fn.histogram_bins.html) for the details.
+pub fn histogram(plot_ui: &PlotUi, label: &str, values: &[f64], bins: usize) {
+    show(plot_ui, label, || {
+        PlotHistogram::new(label)
+            .with_bin_method(BinMethod::Count(bins))
+            .plot(values)
+    });
+}
+
+/// Internal helper that shows a quick plot with an ID derived from the label, and fits its
+/// axes if it was not shown recently.
+fn show<F: FnOnce()>(plot_ui: &PlotUi, label: &str, plot_series: F) {
+    let scope = current_id_scope();
+    let id = QUICK_PLOT_IDS.with(|ids| ids.borrow_mut().next_id(scope, label, frame_count()));
+    // Plots with the same ID in different scopes are different plots
+    let key = format!("{:08x}/{}", scope, id);
+    let shown_recently = SHOWN_QUICK_PLOTS.with(|shown| {
+        let mut shown = shown.borrow_mut();
+        let shown_recently = shown.get(&key).is_some();
+        shown.entry(&key);
+        shown_recently
+    });
+    if !shown_recently {
+        unsafe {
+            sys::ImPlot_FitNextPlotAxes(true, true, true, true);
+        }
+    }
+    Plot::new(&id)
+        .size(QUICK_PLOT_SIZE)
+        .build(plot_ui, plot_series);
+}
+
+/// Counts the quick plots shown per label in one frame, to give plots with the same label
+/// distinct IDs.
+struct QuickPlotIds {
+    /// Frame the counts are for
+    frame: i32,
+    /// Number of plots shown so far in the frame, by imgui ID scope and label
+    counts: HashMap<(u32, String), usize>,
+}
+
+impl QuickPlotIds {
+    fn new() -> Self {
+        Self {
+            frame: i32::MIN,
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Returns the ID for the next plot with the given label in the given imgui ID scope and
+    /// frame. The label is kept visible as the title, and the part after `##` only goes into
+    /// the ID.
+    fn next_id(&mut self, scope: u32, label: &str, frame: i32) -> String {
+        if frame != self.frame {
+            self.counts.clear();
+            self.frame = frame;
+        }
+        let count = self.counts.entry((scope, label.to_owned())).or_insert(0);
+        let id = format!("{}##quick{}", label, count);
+        *count += 1;
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_are_stable_per_call_order() {
+        let mut ids = QuickPlotIds::new();
+        for frame in 0..3 {
+            let frame_ids: Vec<String> = (0..3).map(|_| ids.next_id(1, "loop", frame)).collect();
+            assert_eq!(frame_ids, ["loop##quick0", "loop##quick1", "loop##quick2"]);
+            assert_eq!(ids.next_id(1, "other", frame), "other##quick0");
+            // Plots in another window are numbered on their own
+            assert_eq!(ids.next_id(2, "loop", frame), "loop##quick0");
+        }
+        // Calls of an earlier frame don't count for a later one
+        assert_eq!(ids.next_id(1, "loop", 3), "loop##quick0");
+    }
+}
@@ -0,0 +1,223 @@
+//! # Selection module
+//!
+//! This module contains helpers for relating plot data to regions of a plot, such as finding
+//! out which data points lie within the current query rectangle. The functions here operate
+//! on plain slices of data, so they can be used with the same data that is passed to the
+//! plotting functions in `plot_elements`.
+use crate::context::debug_check_thread;
+use crate::{get_plot_query_checked, PlotLimits, PlotPoint, PlotRange, YAxisChoice};
+use std::ops::Range;
+
+/// Returns the range of indices of `x` whose values lie within `range` (inclusive on both ends).
+/// The values in `x` must be sorted in ascending order for the result to be meaningful, in which
+/// case this only needs a binary search instead of a scan over the whole slice. Ranges with a
+/// NaN bound contain no values.
+pub fn sorted_index_range(x: &[f64], range: impl Into<PlotRange>) -> Range<usize> {
+    let range = range.into();
+    if range.min.is_nan() || range.max.is_nan() {
+        return 0..0;
+    }
+    let PlotRange { min, max } = range.normalized();
+    let start = x.partition_point(|value| *value < min);
+    let end = x.partition_point(|value| *value <= max);
+    start..end.max(start)
+}
+
+/// Collects the indices of all points given by `x` and `y` that lie within `limits` into `out`.
+/// The output vector is cleared first, which allows reusing its allocation across frames. If
+/// the slices have different lengths, the extra values of the longer one are ignored, the same
+/// way the plotting functions do it. Limits with a NaN bound contain no points.
+///
+/// This scans all the points. If the `x` values are sorted, [`points_in_limits_sorted`] does the
+/// same thing while only looking at points within the X range of the limits.
+pub fn points_in_limits(x: &[f64], y: &[f64], limits: impl Into<PlotLimits>, out: &mut Vec<usize>) {
+    out.clear();
+    collect_points_in_limits(x, y, 0..x.len().min(y.len()), &limits.into(), out);
+}
+
+/// Like [`points_in_limits`], but assumes the `x` values are sorted in ascending order and uses
+/// this to only look at the points within the X range of the limits.
+pub fn points_in_limits_sorted(
+    x: &[f64],
+    y: &[f64],
+    limits: impl Into<PlotLimits>,
+    out: &mut Vec<usize>,
+) {
+    out.clear();
+    let limits = limits.into();
+    let number_of_points = x.len().min(y.len());
+    let index_range = sorted_index_range(&x[..number_of_points], limits.x);
+    collect_points_in_limits(x, y, index_range, &limits, out);
+}
+
+/// Collects the indices of all points in the current query rectangle into `out`, see
+/// [`points_in_limits`] for details. Returns false (and leaves `out` empty) if there is no active
+/// query. The `y_axis_choice` selects the Y axis the data was plotted on, `None` means the most
+/// recently selected Y axis. Use this in closures passed to
+/// [`Plot::build()`](struct.Plot.html#method.build).
+///
+/// This scans all the points. If the `x` values are sorted, use
+/// [`get_queried_point_indices_sorted`] instead.
+pub fn get_queried_point_indices(
+    x: &[f64],
+    y: &[f64],
+    y_axis_choice: Option<YAxisChoice>,
+    out: &mut Vec<usize>,
+) -> bool {
+    debug_check_thread();
+    out.clear();
+    match get_plot_query_checked(y_axis_choice) {
+        Some(limits) => {
+            points_in_limits(x, y, limits, out);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Like [`get_queried_point_indices`], but assumes the `x` values are sorted in ascending order
+/// and uses this to only look at the points within the X range of the query, see
+/// [`points_in_limits_sorted`].
+pub fn get_queried_point_indices_sorted(
+    x: &[f64],
+    y: &[f64],
+    y_axis_choice: Option<YAxisChoice>,
+    out: &mut Vec<usize>,
+) -> bool {
+    debug_check_thread();
+    out.clear();
+    match get_plot_query_checked(y_axis_choice) {
+        Some(limits) => {
+            points_in_limits_sorted(x, y, limits, out);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Returns the index of the point given by `x` and `y` that is closest to `target`, or `None` if
+/// there are no points. The distances along X and Y are multiplied by `x_scale` and `y_scale`
+/// respectively before they are compared - pass the number of pixels per plot unit of the two
+/// axes to find the point that is closest on screen. Points with NaN coordinates are skipped.
+pub fn nearest_point_index(
+    x: &[f64],
+    y: &[f64],
+    target: impl Into<PlotPoint>,
+    x_scale: f64,
+    y_scale: f64,
+) -> Option<usize> {
+    let target = target.into();
+    x.iter()
+        .zip(y.iter())
+        .enumerate()
+        .filter_map(|(index, (x_value, y_value))| {
+            let dx = (x_value - target.x) * x_scale;
+            let dy = (y_value - target.y) * y_scale;
+            let squared_distance = dx * dx + dy * dy;
+            if squared_distance.is_nan() {
+                None
+            } else {
+                Some((index, squared_distance))
+            }
+        })
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+        .map(|(index, _)| index)
+}
+
+/// Internal helper that appends the indices within `index_range` of points inside the limits.
+fn collect_points_in_limits(
+    x: &[f64],
+    y: &[f64],
+    index_range: Range<usize>,
+    limits: &PlotLimits,
+    out: &mut Vec<usize>,
+) {
+    let bounds = [limits.x.min, limits.x.max, limits.y.min, limits.y.max];
+    if bounds.iter().any(|bound| bound.is_nan()) {
+        return;
+    }
+    // PlotLimits::contains normalizes, so inverted limits still select the points between
+    // their bounds
+    out.extend(index_range.filter(|&index| limits.contains([x[index], y[index]])));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Internal helper that collects the points in the limits with both variants, and checks
+    /// that they agree.
+    fn points_in(x: &[f64], y: &[f64], limits: PlotLimits) -> Vec<usize> {
+        let mut scanned = Vec::new();
+        points_in_limits(x, y, limits, &mut scanned);
+        let mut sorted = vec![42];
+        points_in_limits_sorted(x, y, limits, &mut sorted);
+        assert_eq!(scanned, sorted);
+        scanned
+    }
+
+    #[test]
+    fn test_sorted_index_range() {
+        let x = [1.0, 2.0, 2.0, 3.0, 4.0];
+        // Both bounds are inclusive, also for repeated values
+        assert_eq!(sorted_index_range(&x, 2.0..=3.0), 1..4);
+        assert_eq!(sorted_index_range(&x, 1.5..2.5), 1..3);
+        assert_eq!(sorted_index_range(&x, 0.0..=10.0), 0..5);
+        // Inverted ranges select the same values as normalized ones
+        assert_eq!(sorted_index_range(&x, [3.0, 2.0]), 1..4);
+        // Ranges without values give empty ranges at the right place
+        assert_eq!(sorted_index_range(&x, 2.2..2.8), 3..3);
+        assert_eq!(sorted_index_range(&x, 5.0..6.0), 5..5);
+        assert_eq!(sorted_index_range(&[], 0.0..1.0), 0..0);
+        // NaN bounds select nothing, and trailing NaN values are not selected
+        assert!(sorted_index_range(&x, [f64::NAN, 3.0]).is_empty());
+        assert!(sorted_index_range(&x, [2.0, f64::NAN]).is_empty());
+        assert_eq!(sorted_index_range(&[1.0, 2.0, f64::NAN], 0.0..=10.0), 0..2);
+    }
+
+    #[test]
+    fn test_points_in_limits() {
+        let x = [0.0, 1.0, 2.0, 3.0, 4.0];
+        let y = [0.0, 2.0, 1.0, f64::NAN, 2.0];
+        let limits = PlotLimits::new(1.0..=4.0, 1.0..=2.0);
+        assert_eq!(points_in(&x, &y, limits), [1, 2, 4]);
+        // Inverted limits select the same points
+        let inverted = PlotLimits::new([4.0, 1.0], [2.0, 1.0]);
+        assert_eq!(points_in(&x, &y, inverted), [1, 2, 4]);
+        // Limits with a NaN bound select nothing
+        let nan_limits = PlotLimits::new([f64::NAN, 4.0], 0.0..=2.0);
+        assert!(points_in(&x, &y, nan_limits).is_empty());
+        let nan_limits = PlotLimits::new(0.0..=4.0, [0.0, f64::NAN]);
+        assert!(points_in(&x, &y, nan_limits).is_empty());
+        // The extra values of the longer slice are ignored
+        assert_eq!(points_in(&x, &y[..3], limits), [1, 2]);
+        assert_eq!(points_in(&x[..2], &y, limits), [1]);
+        assert!(points_in(&[], &y, limits).is_empty());
+    }
+
+    #[test]
+    fn test_nearest_point_index() {
+        let x = [0.0, 1.0, 2.0, 3.0];
+        let y = [0.0, 10.0, 0.0, f64::NAN];
+        assert_eq!(nearest_point_index(&x, &y, [0.9, 8.0], 1.0, 1.0), Some(1));
+        assert_eq!(nearest_point_index(&x, &y, [1.6, 0.0], 1.0, 1.0), Some(2));
+        // The scales weigh the distances along the axes, so with the distances along Y scaled
+        // down, the point at the same X as the target is the closest one
+        assert_eq!(nearest_point_index(&x, &y, [1.0, 4.0], 1.0, 1.0), Some(0));
+        assert_eq!(nearest_point_index(&x, &y, [1.0, 4.0], 1.0, 0.01), Some(1));
+        // Points with NaN coordinates are skipped, also when they are the closest ones
+        assert_eq!(nearest_point_index(&x, &y, [3.0, 0.0], 1.0, 1.0), Some(2));
+        // Ties go to the first point
+        assert_eq!(nearest_point_index(&x, &y, [1.0, 0.0], 1.0, 1.0), Some(0));
+        // The extra values of the longer slice are ignored
+        assert_eq!(
+            nearest_point_index(&x[..1], &y, [2.0, 0.0], 1.0, 1.0),
+            Some(0)
+        );
+        assert_eq!(nearest_point_index(&[], &y, [0.0, 0.0], 1.0, 1.0), None);
+        assert_eq!(
+            nearest_point_index(&[f64::NAN], &[0.0], [0.0, 0.0], 1.0, 1.0),
+            None
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! # Stack check module
+//!
+//! This module keeps track of the pushes to ImPlot's style color, style variable and colormap
+//! stacks, so that pushes which are never popped can be cleaned up at the end of the plot they
+//! were made in, and reported along with the place they were made at in debug builds.
+use crate::frame_stats::record_style_pushes;
+use crate::sys;
+use std::cell::{Cell, RefCell};
+use std::panic::Location;
+
+/// The ImPlot stacks that are checked for pushes that are not popped.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum StackKind {
+    StyleColor,
+    StyleVar,
+    Colormap,
+}
+
+/// Identifies the pushes made for one token, so the token can tell whether they were already
+/// popped when cleaning up after a plot.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) struct PushId(u64);
+
+/// A push that has not been popped yet.
+struct OutstandingPush {
+    /// The stack that was pushed to
+    kind: StackKind,
+    /// The token the push belongs to
+    id: PushId,
+    /// Where the push was made, as seen from outside of this crate
+    location: &'static Location<'static>,
+}
+
+thread_local! {
+    /// All pushes that have not been popped yet, in the order they were made in
+    static OUTSTANDING_PUSHES: RefCell<Vec<OutstandingPush>> = RefCell::new(Vec::new());
+    /// ID for the next token that records pushes
+    static NEXT_PUSH_ID: Cell<u64> = Cell::new(0);
+}
+
+/// Returns a new ID for the pushes of a token.
+pub(crate) fn new_push_id() -> PushId {
+    NEXT_PUSH_ID.with(|next_id| {
+        let id = next_id.get();
+        next_id.set(id + 1);
+        PushId(id)
+    })
+}
+
+/// Record `count` pushes to the given stack for the token with the given ID, made at the
+/// location of the caller. The public push functions are `#[track_caller]` as well, so this is
+/// the location in user code.
+#[track_caller]
+pub(crate) fn record_pushes(id: PushId, kind: StackKind, count: usize) {
+    record_style_pushes(count);
+    let location = Location::caller();
+    OUTSTANDING_PUSHES.with(|pushes| {
+        let mut pushes = pushes.borrow_mut();
+        for _ in 0..count {
+            pushes.push(OutstandingPush { kind, id, location });
+        }
+    });
+}
+
+/// Forget the pushes of the token with the given ID, and return how many of them are still on
+/// the style color, style variable and colormap stacks, which is how many the token has to pop.
+/// This is zero for pushes that were already popped when cleaning up after a plot.
+pub(crate) fn take_pushes(id: PushId) -> [usize; 3] {
+    let mut counts = [0; 3];
+    OUTSTANDING_PUSHES.with(|pushes| {
+        pushes.borrow_mut().retain(|push| {
+            if push.id == id {
+                counts[push.kind as usize] += 1;
+            }
+            push.id != id
+        })
+    });
+    counts
+}
+
+/// Returns true if any of the pushes that have not been popped yet are to the given stack.
+pub(crate) fn has_outstanding_pushes(kind: StackKind) -> bool {
+    OUTSTANDING_PUSHES.with(|pushes| pushes.borrow().iter().any(|push| push.kind == kind))
+}
+
+/// Returns the number of pushes that have not been popped yet.
+pub(crate) fn outstanding_push_count() -> usize {
+    OUTSTANDING_PUSHES.with(|pushes| pushes.borrow().len())
+}
+
+/// Pop all the pushes beyond the first `expected_count` outstanding pushes from ImPlot's
+/// stacks, so they don't affect anything after the current plot. The tokens of these pushes
+/// won't pop them again. In debug builds, the pushes are reported on stderr along with the
+/// places they were made at, and `scope` describes where they should have been popped.
+pub(crate) fn pop_pushes_since<S: FnOnce() -> String>(expected_count: usize, scope: S) {
+    let leaked = OUTSTANDING_PUSHES.with(|pushes| {
+        let mut pushes = pushes.borrow_mut();
+        let expected_count = expected_count.min(pushes.len());
+        pushes.split_off(expected_count)
+    });
+    if leaked.is_empty() {
+        return;
+    }
+
+    let mut counts = [0; 3];
+    for push in &leaked {
+        counts[push.kind as usize] += 1;
+    }
+    pop_from_stacks(counts);
+
+    if cfg!(debug_assertions) {
+        eprintln!(
+            "{} push(es) to the implot style stacks were not popped {}, popping them now: {}",
+            leaked.len(),
+            scope(),
+            describe_pushes(&leaked)
+        );
+    }
+}
+
+/// Pop the given numbers of entries from the style color, style variable and colormap stacks.
+pub(crate) fn pop_from_stacks([colors, vars, colormaps]: [usize; 3]) {
+    unsafe {
+        if colors > 0 {
+            sys::ImPlot_PopStyleColor(colors as i32);
+        }
+        if vars > 0 {
+            sys::ImPlot_PopStyleVar(vars as i32);
+        }
+        if colormaps > 0 {
+            sys::ImPlot_PopColormap(colormaps as i32);
+        }
+    }
+}
+
+/// Panic if there are more outstanding pushes than `expected_count`, listing the pushes beyond
+/// that count. `scope` describes where the pushes should have been popped. Does nothing in
+/// builds without debug assertions.
+pub(crate) fn check_outstanding_pushes(expected_count: usize, scope: &str) {
+    if !cfg!(debug_assertions) {
+        return;
+    }
+    OUTSTANDING_PUSHES.with(|pushes| {
+        let pushes = pushes.borrow();
+        if pushes.len() > expected_count && !std::thread::panicking() {
+            panic!(
+                "{} push(es) to the implot style stacks were not popped {}: {}",
+                pushes.len() - expected_count,
+                scope,
+                describe_pushes(&pushes[expected_count..])
+            );
+        }
+    });
+}
+
+/// Internal helper that lists the given pushes with the places they were made at.
+fn describe_pushes(pushes: &[OutstandingPush]) -> String {
+    pushes
+        .iter()
+        .map(|push| format!("{:?} pushed at {}", push.kind, push.location))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Check that all pushes to the style color, style variable and colormap stacks made through
+/// this crate have been popped again, and panic with the number of outstanding pushes and the
+/// places they were made at if not. This is meant to be called at the end of a frame, for
+/// example after rendering, to catch tokens that are kept around by accident.
+///
+/// This only panics in builds with debug assertions enabled, in other builds it does nothing.
+/// Plots built with [`Plot::build`](struct.Plot.html#method.build) pop the pushes their
+/// closure did not pop by themselves, so these don't affect later plots.
+pub fn debug_check_style_stacks() {
+    check_outstanding_pushes(0, "by the end of the frame");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_pushes_counts_the_pushes_of_one_token() {
+        let first = new_push_id();
+        let second = new_push_id();
+        record_pushes(first, StackKind::StyleColor, 3);
+        record_pushes(second, StackKind::Colormap, 1);
+        record_pushes(first, StackKind::StyleVar, 2);
+
+        assert_eq!(take_pushes(first), [3, 2, 0]);
+        // The pushes are forgotten, so a second pop of the token pops nothing
+        assert_eq!(take_pushes(first), [0, 0, 0]);
+        assert_eq!(outstanding_push_count(), 1);
+        assert!(has_outstanding_pushes(StackKind::Colormap));
+        assert_eq!(take_pushes(second), [0, 0, 1]);
+        assert_eq!(outstanding_push_count(), 0);
+    }
+}
@@ -0,0 +1,680 @@
+//! # Style module
+//!
+//! This module contains [`Style`], which gives access to the complete ImPlot style of a
+//! [`Context`](struct.Context.html). For temporary changes to the style, use the push/pop
+//! functions such as [`push_style_var`](fn.push_style_var.html) instead.
+use crate::context::{context_mut, debug_check_thread};
+use crate::imgui_compat::{style_color, ImguiColor};
+use crate::{sys, Context, ContextMut, ImVec2, ImVec4, Marker, PlotColorElement, StyleVarValue};
+use std::fmt::Write;
+use std::ops::{Index, IndexMut};
+
+/// The style of plots, mirroring ImPlot's `ImPlotStyle`. Get the current style of a context with
+/// [`Context::style`] and apply a changed style with [`Context::set_style`]:
+/// ```no_run
+/// # use implot::{Context, ImVec2};
+/// # fn example(context: &mut Context) {
+/// let mut style = context.style();
+/// style.marker_size = 6.0;
+/// style.plot_padding = ImVec2 { x: 4.0, y: 4.0 };
+/// context.set_style(&style);
+/// # }
+/// ```
+/// The colormap is not part of the style in ImPlot, see
+/// [`set_colormap_from_preset`](fn.set_colormap_from_preset.html) for setting it.
+#[rustversion::attr(since(1.48), doc(alias = "ImPlotStyle"))]
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct Style {
+    /// Item line weight in pixels
+    pub line_weight: f32,
+    /// Marker type, see [`Style::marker`]
+    pub(crate) marker: i32,
+    /// Marker size in pixels (roughly the marker's "radius")
+    pub marker_size: f32,
+    /// Outline weight of markers in pixels
+    pub marker_weight: f32,
+    /// Alpha modifier applied to all plot item fills
+    pub fill_alpha: f32,
+    /// Error bar whisker width in pixels
+    pub error_bar_size: f32,
+    /// Error bar whisker weight in pixels
+    pub error_bar_weight: f32,
+    /// Digital channels bit height (at 1) in pixels
+    pub digital_bit_height: f32,
+    /// Digital channels bit padding gap in pixels
+    pub digital_bit_gap: f32,
+    /// Thickness of the border around plot area
+    pub plot_border_size: f32,
+    /// Alpha multiplier applied to minor axis grid lines
+    pub minor_alpha: f32,
+    /// Major tick lengths for X and Y axes
+    pub major_tick_len: ImVec2,
+    /// Minor tick lengths for X and Y axes
+    pub minor_tick_len: ImVec2,
+    /// Line thickness of major ticks
+    pub major_tick_size: ImVec2,
+    /// Line thickness of minor ticks
+    pub minor_tick_size: ImVec2,
+    /// Line thickness of major grid lines
+    pub major_grid_size: ImVec2,
+    /// Line thickness of minor grid lines
+    pub minor_grid_size: ImVec2,
+    /// Padding between widget frame and plot area, labels, or outside legends (i.e. main padding)
+    pub plot_padding: ImVec2,
+    /// Padding between axes labels, tick labels, and plot edge
+    pub label_padding: ImVec2,
+    /// Legend padding from plot edges
+    pub legend_padding: ImVec2,
+    /// Legend inner padding from legend edges
+    pub legend_inner_padding: ImVec2,
+    /// Spacing between legend entries
+    pub legend_spacing: ImVec2,
+    /// Padding between plot edge and interior info text
+    pub mouse_pos_padding: ImVec2,
+    /// Text padding around annotation labels
+    pub annotation_padding: ImVec2,
+    /// Additional fit padding as a percentage of the fit extents (e.g. 0.1 = 10%)
+    pub fit_padding: ImVec2,
+    /// Default size used when the plot size is not given
+    pub plot_default_size: ImVec2,
+    /// Minimum size the plot frame can be when shrunk
+    pub plot_min_size: ImVec2,
+    /// Style colors, which can also be accessed by indexing the style with a
+    /// [`PlotColorElement`](enum.PlotColorElement.html)
+    pub colors: [ImVec4; 24],
+    /// Enable global anti-aliasing on plot lines (overrides the per-plot flag)
+    pub anti_aliased_lines: bool,
+    /// Axis labels will be formatted for your timezone when time axes are used
+    pub use_local_time: bool,
+    /// Dates will be formatted according to ISO 8601 where applicable (e.g. YYYY-MM-DD)
+    pub use_iso_8601: bool,
+    /// Times will be formatted using a 24 hour clock
+    pub use_24_hour_clock: bool,
+}
+
+// The style is copied to and from ImPlot's style as raw memory, so make sure at compile time that
+// the two have the same size and alignment.
+const _: [(); std::mem::size_of::<sys::ImPlotStyle>()] = [(); std::mem::size_of::<Style>()];
+const _: [(); std::mem::align_of::<sys::ImPlotStyle>()] = [(); std::mem::align_of::<Style>()];
+
+/// All colorable plot elements, in the order of [`Style::colors`].
+pub(crate) const COLOR_ELEMENTS: [PlotColorElement; 24] = [
+    PlotColorElement::Line,
+    PlotColorElement::Fill,
+    PlotColorElement::MarkerOutline,
+    PlotColorElement::MarkerFill,
+    PlotColorElement::ErrorBar,
+    PlotColorElement::FrameBg,
+    PlotColorElement::PlotBg,
+    PlotColorElement::PlotBorder,
+    PlotColorElement::LegendBackground,
+    PlotColorElement::LegendBorder,
+    PlotColorElement::LegendText,
+    PlotColorElement::TitleText,
+    PlotColorElement::InlayText,
+    PlotColorElement::XAxis,
+    PlotColorElement::XAxisGrid,
+    PlotColorElement::YAxis,
+    PlotColorElement::YAxisGrid,
+    PlotColorElement::YAxis2,
+    PlotColorElement::YAxisGrid2,
+    PlotColorElement::YAxis3,
+    PlotColorElement::YAxisGrid3,
+    PlotColorElement::Selection,
+    PlotColorElement::Crosshairs,
+    PlotColorElement::Query,
+];
+
+impl Style {
+    /// Returns the default marker of plot items, or `None` if ImPlot's style contains a value
+    /// that is not a known marker.
+    pub fn marker(&self) -> Option<Marker> {
+        Marker::ALL
+            .iter()
+            .copied()
+            .find(|marker| *marker as i32 == self.marker)
+    }
+
+    /// Set the default marker of plot items.
+    pub fn set_marker(&mut self, marker: Marker) {
+        self.marker = marker as i32;
+    }
+
+    /// Set the field of the style that corresponds to the style variable of the given value.
+    /// This is the permanent counterpart of [`push_style_var`](fn.push_style_var.html).
+    pub fn set_var(&mut self, value: StyleVarValue) {
+        match value {
+            StyleVarValue::Marker(marker) => self.set_marker(marker),
+            StyleVarValue::LineWeight(value) => self.line_weight = value,
+            StyleVarValue::MarkerSize(value) => self.marker_size = value,
+            StyleVarValue::MarkerWeight(value) => self.marker_weight = value,
+            StyleVarValue::FillAlpha(value) => self.fill_alpha = value,
+            StyleVarValue::ErrorBarSize(value) => self.error_bar_size = value,
+            StyleVarValue::ErrorBarWeight(value) => self.error_bar_weight = value,
+            StyleVarValue::DigitalBitHeight(value) => self.digital_bit_height = value,
+            StyleVarValue::DigitalBitGap(value) => self.digital_bit_gap = value,
+            StyleVarValue::PlotBorderSize(value) => self.plot_border_size = value,
+            StyleVarValue::MinorAlpha(value) => self.minor_alpha = value,
+            StyleVarValue::MajorTickLen(value) => self.major_tick_len = value,
+            StyleVarValue::MinorTickLen(value) => self.minor_tick_len = value,
+            StyleVarValue::MajorTickSize(value) => self.major_tick_size = value,
+            StyleVarValue::MinorTickSize(value) => self.minor_tick_size = value,
+            StyleVarValue::MajorGridSize(value) => self.major_grid_size = value,
+            StyleVarValue::MinorGridSize(value) => self.minor_grid_size = value,
+            StyleVarValue::PlotPadding(value) => self.plot_padding = value,
+            StyleVarValue::LabelPadding(value) => self.label_padding = value,
+            StyleVarValue::LegendPadding(value) => self.legend_padding = value,
+            StyleVarValue::LegendInnerPadding(value) => self.legend_inner_padding = value,
+            StyleVarValue::LegendSpacing(value) => self.legend_spacing = value,
+            StyleVarValue::MousePosPadding(value) => self.mouse_pos_padding = value,
+            StyleVarValue::AnnotationPadding(value) => self.annotation_padding = value,
+            StyleVarValue::FitPadding(value) => self.fit_padding = value,
+            StyleVarValue::PlotDefaultSize(value) => self.plot_default_size = value,
+            StyleVarValue::PlotMinSize(value) => self.plot_min_size = value,
+        }
+    }
+
+    /// Internal helper that sets the colors that have an override, indexed by element, and
+    /// leaves the other colors as they are.
+    fn apply_color_overrides(&mut self, overrides: &[Option<ImVec4>]) {
+        for (color, color_override) in self.colors.iter_mut().zip(overrides) {
+            if let Some(color_override) = color_override {
+                *color = *color_override;
+            }
+        }
+    }
+
+    /// Returns Rust code that recreates this style by assigning all of its values to a variable
+    /// named `style`. This is meant for style tweaking: adjust the style interactively with
+    /// [`show_style_editor`], then print this and paste it into your application.
+    /// ```no_run
+    /// # use implot::Context;
+    /// # fn example(context: &Context) {
+    /// println!("{}", context.style().to_rust_code());
+    /// // Prints lines such as
+    /// // style.line_weight = 1.0;
+    /// // style.plot_padding = ImVec2 { x: 10.0, y: 10.0 };
+    /// // style[PlotColorElement::PlotBg] = ImVec4 { x: 0.0, y: 0.0, z: 0.0, w: 0.5 };
+    /// # }
+    /// ```
+    pub fn to_rust_code(&self) -> String {
+        let mut code = String::new();
+        // Writing to a String can't fail, so the results are ignored below
+        let mut float = |name: &str, value: f32| {
+            let _ = writeln!(code, "style.{} = {:?};", name, value);
+        };
+        float("line_weight", self.line_weight);
+        float("marker_size", self.marker_size);
+        float("marker_weight", self.marker_weight);
+        float("fill_alpha", self.fill_alpha);
+        float("error_bar_size", self.error_bar_size);
+        float("error_bar_weight", self.error_bar_weight);
+        float("digital_bit_height", self.digital_bit_height);
+        float("digital_bit_gap", self.digital_bit_gap);
+        float("plot_border_size", self.plot_border_size);
+        float("minor_alpha", self.minor_alpha);
+
+        let mut vec2 = |name: &str, value: ImVec2| {
+            let _ = writeln!(
+                code,
+                "style.{} = ImVec2 {{ x: {:?}, y: {:?} }};",
+                name, value.x, value.y
+            );
+        };
+        vec2("major_tick_len", self.major_tick_len);
+        vec2("minor_tick_len", self.minor_tick_len);
+        vec2("major_tick_size", self.major_tick_size);
+        vec2("minor_tick_size", self.minor_tick_size);
+        vec2("major_grid_size", self.major_grid_size);
+        vec2("minor_grid_size", self.minor_grid_size);
+        vec2("plot_padding", self.plot_padding);
+        vec2("label_padding", self.label_padding);
+        vec2("legend_padding", self.legend_padding);
+        vec2("legend_inner_padding", self.legend_inner_padding);
+        vec2("legend_spacing", self.legend_spacing);
+        vec2("mouse_pos_padding", self.mouse_pos_padding);
+        vec2("annotation_padding", self.annotation_padding);
+        vec2("fit_padding", self.fit_padding);
+        vec2("plot_default_size", self.plot_default_size);
+        vec2("plot_min_size", self.plot_min_size);
+
+        for element in COLOR_ELEMENTS.iter() {
+            let color = self[*element];
+            let _ = writeln!(
+                code,
+                "style[PlotColorElement::{:?}] = ImVec4 {{ x: {:?}, y: {:?}, z: {:?}, w: {:?} }};",
+                element, color.x, color.y, color.z, color.w
+            );
+        }
+
+        if let Some(marker) = self.marker() {
+            let _ = writeln!(code, "style.set_marker(Marker::{:?});", marker);
+        }
+        let _ = writeln!(
+            code,
+            "style.anti_aliased_lines = {};",
+            self.anti_aliased_lines
+        );
+        let _ = writeln!(code, "style.use_local_time = {};", self.use_local_time);
+        let _ = writeln!(code, "style.use_iso_8601 = {};", self.use_iso_8601);
+        let _ = writeln!(
+            code,
+            "style.use_24_hour_clock = {};",
+            self.use_24_hour_clock
+        );
+        code
+    }
+}
+
+impl Default for Style {
+    /// Returns ImPlot's default style, as set up by the `ImPlotStyle` constructor. All colors
+    /// are automatic in it.
+    fn default() -> Self {
+        let vec2 = |x, y| ImVec2 { x, y };
+        Self {
+            line_weight: 1.0,
+            marker: Marker::None as i32,
+            marker_size: 4.0,
+            marker_weight: 1.0,
+            fill_alpha: 1.0,
+            error_bar_size: 5.0,
+            error_bar_weight: 1.5,
+            digital_bit_height: 8.0,
+            digital_bit_gap: 4.0,
+            plot_border_size: 1.0,
+            minor_alpha: 0.25,
+            major_tick_len: vec2(10.0, 10.0),
+            minor_tick_len: vec2(5.0, 5.0),
+            major_tick_size: vec2(1.0, 1.0),
+            minor_tick_size: vec2(1.0, 1.0),
+            major_grid_size: vec2(1.0, 1.0),
+            minor_grid_size: vec2(1.0, 1.0),
+            plot_padding: vec2(10.0, 10.0),
+            label_padding: vec2(5.0, 5.0),
+            legend_padding: vec2(10.0, 10.0),
+            legend_inner_padding: vec2(5.0, 5.0),
+            legend_spacing: vec2(5.0, 0.0),
+            mouse_pos_padding: vec2(10.0, 10.0),
+            annotation_padding: vec2(2.0, 2.0),
+            fit_padding: vec2(0.0, 0.0),
+            plot_default_size: vec2(400.0, 300.0),
+            plot_min_size: vec2(200.0, 150.0),
+            colors: [crate::IMPLOT_AUTO_COL; 24],
+            anti_aliased_lines: false,
+            use_local_time: false,
+            use_iso_8601: false,
+            use_24_hour_clock: false,
+        }
+    }
+}
+
+impl Index<PlotColorElement> for Style {
+    type Output = ImVec4;
+
+    fn index(&self, element: PlotColorElement) -> &ImVec4 {
+        &self.colors[element as usize]
+    }
+}
+
+impl IndexMut<PlotColorElement> for Style {
+    fn index_mut(&mut self, element: PlotColorElement) -> &mut ImVec4 {
+        &mut self.colors[element as usize]
+    }
+}
+
+impl Context {
+    /// Returns a copy of the current style of this context. This includes the effects of any
+    /// style changes that are currently pushed.
+    #[rustversion::attr(since(1.48), doc(alias = "GetStyle"))]
+    pub fn style(&self) -> Style {
+        self.with_current(|| unsafe { *(self.raw_style() as *const Style) })
+    }
+
+    /// Replace the style of this context. This takes `&mut self` so it can't be called while
+    /// plots are being built with a [`PlotUi`](struct.PlotUi.html) of this context. Style
+    /// changes that are pushed at the time are overwritten until they are popped, after which
+    /// the values from before the push are restored.
+    #[rustversion::attr(since(1.48), doc(alias = "GetStyle"))]
+    pub fn set_style(&mut self, style: &Style) {
+        self.with_current(|| unsafe {
+            *(self.raw_style() as *mut Style) = *style;
+        });
+    }
+
+    /// Set a style color of this context explicitly. Unlike colors set with
+    /// [`Context::set_style`], this color is remembered as an override, which
+    /// [`sync_style_with_imgui`] keeps when it derives the other colors from the imgui style
+    /// again. Use [`Context::clear_style_color_override`] to let the color follow the imgui style
+    /// again.
+    pub fn set_style_color<C: Into<ImVec4>>(&mut self, element: PlotColorElement, color: C) {
+        let color = color.into();
+        self.color_overrides[element as usize] = Some(color);
+        self.with_current(|| unsafe {
+            (*self.raw_style()).Colors[element as usize] = color;
+        });
+    }
+
+    /// Forget the override of the given style color that was set with
+    /// [`Context::set_style_color`]. The color itself is left as it is until the next call to
+    /// [`sync_style_with_imgui`], which derives it from the imgui style like the other colors.
+    pub fn clear_style_color_override(&mut self, element: PlotColorElement) {
+        self.color_overrides[element as usize] = None;
+    }
+
+    /// Returns the style color override of the given element, if one was set with
+    /// [`Context::set_style_color`].
+    pub fn style_color_override(&self, element: PlotColorElement) -> Option<ImVec4> {
+        self.color_overrides[element as usize]
+    }
+
+    /// Returns the size of plots that are built without an explicit size, see
+    /// [`Context::set_default_plot_size`].
+    pub fn default_plot_size(&self) -> ImVec2 {
+        self.style().plot_default_size
+    }
+
+    /// Set the size of plots that are built without an explicit size, for the whole lifetime of
+    /// the context. Unlike pushing `StyleVarValue::PlotDefaultSize`, this doesn't need a token
+    /// to be kept around.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotDefaultSize"))]
+    pub fn set_default_plot_size<S: Into<ImVec2>>(&mut self, size: S) {
+        let size = size.into();
+        self.with_current(|| unsafe {
+            (*self.raw_style()).PlotDefaultSize = size;
+        });
+    }
+
+    /// Returns the minimum size plots are shrunk to, see [`Context::set_min_plot_size`].
+    pub fn min_plot_size(&self) -> ImVec2 {
+        self.style().plot_min_size
+    }
+
+    /// Set the minimum size plots are shrunk to, for the whole lifetime of the context. Unlike
+    /// pushing `StyleVarValue::PlotMinSize`, this doesn't need a token to be kept around.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotMinSize"))]
+    pub fn set_min_plot_size<S: Into<ImVec2>>(&mut self, size: S) {
+        let size = size.into();
+        self.with_current(|| unsafe {
+            (*self.raw_style()).PlotMinSize = size;
+        });
+    }
+
+    /// Internal helper that returns a pointer to the ImPlot style of this context. ImPlot only
+    /// hands out the style of the current context, so this has to be called within
+    /// [`Context::with_current`].
+    fn raw_style(&self) -> *mut sys::ImPlotStyle {
+        debug_assert!(
+            self.is_current(),
+            "The style of a context that is not current"
+        );
+        let style = unsafe { sys::ImPlot_GetStyle() };
+        assert!(!style.is_null(), "ImPlot returned no style");
+        style
+    }
+}
+
+/// Internal helper that returns the style of the current context, panicking if there is none.
+fn current_style() -> *mut sys::ImPlotStyle {
+    debug_check_thread();
+    assert!(
+        !unsafe { sys::ImPlot_GetCurrentContext() }.is_null(),
+        "The style can only be accessed while an implot context exists"
+    );
+    unsafe { sys::ImPlot_GetStyle() }
+}
+
+/// Returns a copy of the style of the current context, see [`Context::style`]. This is useful
+/// where only a [`PlotUi`](struct.PlotUi.html) and not the context itself is at hand.
+///
+/// # Panics
+/// Will panic if there is no implot context.
+#[rustversion::attr(since(1.48), doc(alias = "GetStyle"))]
+pub fn get_style() -> Style {
+    unsafe { *(current_style() as *const Style) }
+}
+
+/// Set whether lines are anti-aliased in all plots of the current context. Anti-aliased lines
+/// look better, but are more expensive to render, which shows with large amounts of data. This
+/// changes the style itself, so it persists until it is set again.
+///
+/// # Panics
+/// Will panic if there is no implot context.
+pub fn set_anti_aliased_lines(anti_aliased_lines: bool) {
+    unsafe {
+        (*current_style()).AntiAliasedLines = anti_aliased_lines;
+    }
+}
+
+/// Set the alpha modifier that is applied to the fills of all plot items of the current context,
+/// between 0 (invisible) and 1 (unchanged). This changes the style itself, so it persists until
+/// it is set again - for a temporary change, push [`StyleVarValue::FillAlpha`] with
+/// [`push_style_var`](fn.push_style_var.html) instead.
+///
+/// # Panics
+/// Will panic if there is no implot context.
+pub fn set_fill_alpha(fill_alpha: f32) {
+    unsafe {
+        (*current_style()).FillAlpha = fill_alpha;
+    }
+}
+
+/// Returns whether the given style color of the current context is automatic, which means that
+/// ImPlot derives it from the imgui style or, for item colors such as
+/// [`PlotColorElement::Line`], from the current colormap.
+///
+/// # Panics
+/// Will panic if there is no implot context.
+pub fn is_style_color_auto(element: PlotColorElement) -> bool {
+    // IMPLOT_AUTO_COL is the only color with a negative alpha
+    get_style()[element].w < 0.0
+}
+
+/// Returns the style color of the current context for the given element. This includes the
+/// effects of any style colors that are currently pushed, so it is the color ImPlot would use
+/// right now. This is useful for deriving related colors:
+/// ```no_run
+/// # use implot::{get_style_color, PlotColorElement, ImVec4};
+/// let border = get_style_color(PlotColorElement::PlotBorder);
+/// let faint_border = ImVec4 { w: border.w * 0.5, ..border };
+/// ```
+///
+/// Automatic colors (see [`is_style_color_auto`]) are resolved the same way ImPlot does it,
+/// mostly from the current imgui style. The automatic colors of the item elements
+/// [`PlotColorElement::Line`], [`PlotColorElement::Fill`], [`PlotColorElement::MarkerOutline`]
+/// and [`PlotColorElement::MarkerFill`] depend on the item they are used for, so for these,
+/// opaque black is returned like in ImPlot.
+///
+/// # Panics
+/// Will panic if there is no implot context or no imgui context.
+#[rustversion::attr(since(1.48), doc(alias = "GetStyleColorVec4"))]
+pub fn get_style_color(element: PlotColorElement) -> ImVec4 {
+    debug_check_thread();
+    let color = get_style()[element];
+    if color.w >= 0.0 {
+        return color;
+    }
+
+    // This mirrors GetAutoColor from implot.cpp, which is not part of the bindings
+    match element {
+        PlotColorElement::Line
+        | PlotColorElement::Fill
+        | PlotColorElement::MarkerOutline
+        | PlotColorElement::MarkerFill => ImVec4 {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: 1.0,
+        },
+        PlotColorElement::ErrorBar
+        | PlotColorElement::TitleText
+        | PlotColorElement::InlayText
+        | PlotColorElement::XAxis => style_color(ImguiColor::Text),
+        PlotColorElement::FrameBg => style_color(ImguiColor::FrameBg),
+        PlotColorElement::PlotBg => style_color(ImguiColor::WindowBg),
+        PlotColorElement::PlotBorder => style_color(ImguiColor::Border),
+        PlotColorElement::LegendBackground => style_color(ImguiColor::PopupBg),
+        PlotColorElement::LegendBorder | PlotColorElement::Crosshairs => {
+            get_style_color(PlotColorElement::PlotBorder)
+        }
+        PlotColorElement::LegendText => get_style_color(PlotColorElement::InlayText),
+        PlotColorElement::XAxisGrid => {
+            let axis_color = get_style_color(PlotColorElement::XAxis);
+            ImVec4 {
+                w: axis_color.w * 0.25,
+                ..axis_color
+            }
+        }
+        PlotColorElement::YAxis => get_style_color(PlotColorElement::XAxis),
+        PlotColorElement::YAxisGrid => get_style_color(PlotColorElement::XAxisGrid),
+        PlotColorElement::YAxis2 | PlotColorElement::YAxis3 => {
+            get_style_color(PlotColorElement::YAxis)
+        }
+        PlotColorElement::YAxisGrid2 | PlotColorElement::YAxisGrid3 => {
+            get_style_color(PlotColorElement::YAxisGrid)
+        }
+        PlotColorElement::Selection => ImVec4 {
+            x: 1.0,
+            y: 1.0,
+            z: 0.0,
+            w: 1.0,
+        },
+        PlotColorElement::Query => ImVec4 {
+            x: 0.0,
+            y: 1.0,
+            z: 0.0,
+            w: 1.0,
+        },
+    }
+}
+
+/// Show ImPlot's style editor, which allows changing the style of the current context
+/// interactively. This is a developer tool like [`show_demo_window`](fn.show_demo_window.html),
+/// and draws its widgets into the current imgui window. The changes are made to the style
+/// directly, so they can be read back with [`get_style`] - [`Style::to_rust_code`] turns them
+/// into code for your application.
+///
+/// The editor offers to revert the style to a reference style, and to save the current style as
+/// the new reference. If `reference` is `None`, ImPlot uses a reference style of its own.
+///
+/// # Panics
+/// Will panic if there is no implot context.
+// Unlike the demo window, this is part of implot.cpp, so it's always available.
+#[rustversion::attr(since(1.48), doc(alias = "ShowStyleEditor"))]
+pub fn show_style_editor(reference: Option<&mut Style>) {
+    debug_check_thread();
+    current_style();
+    let reference = match reference {
+        Some(reference) => reference as *mut Style as *mut sys::ImPlotStyle,
+        None => std::ptr::null_mut(),
+    };
+    unsafe { sys::ImPlot_ShowStyleEditor(reference) }
+}
+
+/// Use the dark color preset for the style of the current context. This can be called at any
+/// time outside of plots.
+///
+/// # Panics
+/// Will panic if there is no implot context.
+#[rustversion::attr(since(1.48), doc(alias = "StyleColorsDark"))]
+pub fn style_colors_dark() {
+    debug_check_thread();
+    unsafe { sys::ImPlot_StyleColorsDark(current_style()) }
+}
+
+/// Use the light color preset for the style of the current context. This can be called at any
+/// time outside of plots.
+///
+/// # Panics
+/// Will panic if there is no implot context.
+#[rustversion::attr(since(1.48), doc(alias = "StyleColorsLight"))]
+pub fn style_colors_light() {
+    debug_check_thread();
+    unsafe { sys::ImPlot_StyleColorsLight(current_style()) }
+}
+
+/// Use the classic color preset for the style of the current context. This can be called at any
+/// time outside of plots.
+///
+/// # Panics
+/// Will panic if there is no implot context.
+#[rustversion::attr(since(1.48), doc(alias = "StyleColorsClassic"))]
+pub fn style_colors_classic() {
+    debug_check_thread();
+    unsafe { sys::ImPlot_StyleColorsClassic(current_style()) }
+}
+
+/// Derive the colors of the style of the current context from the current imgui style, so plots
+/// match the rest of the user interface. Call this again after changing the imgui style, for
+/// example when switching between light and dark themes. This can be called at any time outside
+/// of plots.
+///
+/// # Panics
+/// Will panic if there is no implot context.
+#[rustversion::attr(since(1.48), doc(alias = "StyleColorsAuto"))]
+pub fn style_colors_auto() {
+    debug_check_thread();
+    unsafe { sys::ImPlot_StyleColorsAuto(current_style()) }
+}
+
+/// Derive the colors of the style of the given context from the current imgui style, like
+/// [`style_colors_auto`], but keep the colors that were set with [`Context::set_style_color`].
+/// Call this after switching the imgui theme at runtime, so plots follow the new theme without
+/// losing the colors the application chose on purpose. This can be called at any time outside of
+/// plots.
+#[rustversion::attr(since(1.48), doc(alias = "StyleColorsAuto"))]
+pub fn sync_style_with_imgui<C: ContextMut + ?Sized>(context: &mut C) {
+    let context = context_mut(context);
+    context.with_current(|| {
+        let raw_style = context.raw_style();
+        unsafe {
+            sys::ImPlot_StyleColorsAuto(raw_style);
+            (*(raw_style as *mut Style)).apply_color_overrides(&context.color_overrides);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "imgui-rs")]
+    use crate::test_support::with_test_frame;
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_default_matches_a_fresh_context() {
+        with_test_frame(|_, _| {
+            let fresh = unsafe { *(sys::ImPlot_GetStyle() as *const Style) };
+            // Style has no PartialEq, since comparing floats exactly is rarely what is wanted,
+            // but here it is: the defaults are copied from ImPlot and must match exactly
+            assert_eq!(format!("{:?}", Style::default()), format!("{:?}", fresh));
+        });
+    }
+
+    #[test]
+    fn test_apply_color_overrides_keeps_other_colors() {
+        let override_color = ImVec4 {
+            x: 0.5,
+            y: 0.25,
+            z: 0.0,
+            w: 1.0,
+        };
+        let mut overrides = [None; COLOR_ELEMENTS.len()];
+        overrides[PlotColorElement::PlotBg as usize] = Some(override_color);
+        overrides[PlotColorElement::Query as usize] = Some(override_color);
+
+        let mut style = Style::default();
+        style.apply_color_overrides(&overrides);
+        for element in COLOR_ELEMENTS.iter() {
+            let expected = match element {
+                PlotColorElement::PlotBg | PlotColorElement::Query => override_color,
+                _ => crate::IMPLOT_AUTO_COL,
+            };
+            let color = style[*element];
+            assert_eq!(
+                [color.x, color.y, color.z, color.w],
+                [expected.x, expected.y, expected.z, expected.w]
+            );
+        }
+    }
+}
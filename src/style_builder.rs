@@ -0,0 +1,393 @@
+//! # Style builder module
+//!
+//! This module contains [`StyleBuilder`], for setting up the plot style declaratively, for
+//! example once at application startup.
+use crate::context::context_mut;
+use crate::{
+    ColormapChoice, ContextMut, ImVec2, ImVec4, Marker, PlotColorElement, Style, StyleVarValue,
+};
+use std::fmt;
+
+/// Error for style values that are outside of the range ImPlot can work with, such as negative
+/// sizes.
+#[derive(Copy, Clone, Debug)]
+pub struct StyleValueError {
+    /// The value that is out of range
+    pub value: StyleVarValue,
+}
+
+impl fmt::Display for StyleValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "style value {:?} is out of range", self.value)
+    }
+}
+
+impl std::error::Error for StyleValueError {}
+
+/// Builder for changing several settings of the plot style at once. Only the settings that are
+/// configured on the builder are changed when it is applied, all other settings keep their
+/// current values:
+/// ```no_run
+/// # use implot::{Colormap, Context, PlotColorElement, StyleBuilder};
+/// # fn example(context: &mut Context) {
+/// StyleBuilder::new()
+///     .line_weight(2.0)
+///     .marker_size(3.0)
+///     .plot_padding([8.0, 8.0])
+///     .colormap(Colormap::Viridis)
+///     .color(PlotColorElement::PlotBg, [0.1, 0.1, 0.1, 1.0])
+///     .apply(context)
+///     .expect("Style values are in range");
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct StyleBuilder {
+    /// Style variables to set, in the order they were configured in
+    vars: Vec<StyleVarValue>,
+    /// Style colors to set, in the order they were configured in
+    colors: Vec<(PlotColorElement, ImVec4)>,
+    /// Value for `Style::anti_aliased_lines`, if configured
+    anti_aliased_lines: Option<bool>,
+    /// Value for `Style::use_local_time`, if configured
+    use_local_time: Option<bool>,
+    /// Value for `Style::use_iso_8601`, if configured
+    use_iso_8601: Option<bool>,
+    /// Value for `Style::use_24_hour_clock`, if configured
+    use_24_hour_clock: Option<bool>,
+    /// Colormap to set, if configured
+    colormap: Option<ColormapChoice>,
+}
+
+impl StyleBuilder {
+    /// Create a new builder without any settings configured.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the style variable given by the value, see [`StyleVarValue`](enum.StyleVarValue.html).
+    /// The other setters of this builder are shorthands for this.
+    pub fn var(mut self, value: StyleVarValue) -> Self {
+        self.vars.push(value);
+        self
+    }
+
+    /// Set the default marker of items.
+    pub fn marker(self, marker: Marker) -> Self {
+        self.var(StyleVarValue::Marker(marker))
+    }
+
+    /// Set the line weight of items in pixels.
+    pub fn line_weight(self, value: f32) -> Self {
+        self.var(StyleVarValue::LineWeight(value))
+    }
+
+    /// Set the marker size in pixels (roughly the marker's "radius").
+    pub fn marker_size(self, value: f32) -> Self {
+        self.var(StyleVarValue::MarkerSize(value))
+    }
+
+    /// Set the outline weight of markers in pixels.
+    pub fn marker_weight(self, value: f32) -> Self {
+        self.var(StyleVarValue::MarkerWeight(value))
+    }
+
+    /// Set the alpha modifier applied to all plot item fills, between 0 and 1.
+    pub fn fill_alpha(self, value: f32) -> Self {
+        self.var(StyleVarValue::FillAlpha(value))
+    }
+
+    /// Set the error bar whisker width in pixels.
+    pub fn error_bar_size(self, value: f32) -> Self {
+        self.var(StyleVarValue::ErrorBarSize(value))
+    }
+
+    /// Set the error bar whisker weight in pixels.
+    pub fn error_bar_weight(self, value: f32) -> Self {
+        self.var(StyleVarValue::ErrorBarWeight(value))
+    }
+
+    /// Set the digital channels bit height (at 1) in pixels.
+    pub fn digital_bit_height(self, value: f32) -> Self {
+        self.var(StyleVarValue::DigitalBitHeight(value))
+    }
+
+    /// Set the digital channels bit padding gap in pixels.
+    pub fn digital_bit_gap(self, value: f32) -> Self {
+        self.var(StyleVarValue::DigitalBitGap(value))
+    }
+
+    /// Set the thickness of the border around the plot area.
+    pub fn plot_border_size(self, value: f32) -> Self {
+        self.var(StyleVarValue::PlotBorderSize(value))
+    }
+
+    /// Set the alpha multiplier applied to minor axis grid lines, between 0 and 1.
+    pub fn minor_alpha(self, value: f32) -> Self {
+        self.var(StyleVarValue::MinorAlpha(value))
+    }
+
+    /// Set the major tick lengths for X and Y axes.
+    pub fn major_tick_len<V: Into<ImVec2>>(self, value: V) -> Self {
+        self.var(StyleVarValue::MajorTickLen(value.into()))
+    }
+
+    /// Set the minor tick lengths for X and Y axes.
+    pub fn minor_tick_len<V: Into<ImVec2>>(self, value: V) -> Self {
+        self.var(StyleVarValue::MinorTickLen(value.into()))
+    }
+
+    /// Set the line thickness of major ticks.
+    pub fn major_tick_size<V: Into<ImVec2>>(self, value: V) -> Self {
+        self.var(StyleVarValue::MajorTickSize(value.into()))
+    }
+
+    /// Set the line thickness of minor ticks.
+    pub fn minor_tick_size<V: Into<ImVec2>>(self, value: V) -> Self {
+        self.var(StyleVarValue::MinorTickSize(value.into()))
+    }
+
+    /// Set the line thickness of major grid lines.
+    pub fn major_grid_size<V: Into<ImVec2>>(self, value: V) -> Self {
+        self.var(StyleVarValue::MajorGridSize(value.into()))
+    }
+
+    /// Set the line thickness of minor grid lines.
+    pub fn minor_grid_size<V: Into<ImVec2>>(self, value: V) -> Self {
+        self.var(StyleVarValue::MinorGridSize(value.into()))
+    }
+
+    /// Set the padding between widget frame and plot area, labels, or outside legends.
+    pub fn plot_padding<V: Into<ImVec2>>(self, value: V) -> Self {
+        self.var(StyleVarValue::PlotPadding(value.into()))
+    }
+
+    /// Set the padding between axes labels, tick labels, and plot edge.
+    pub fn label_padding<V: Into<ImVec2>>(self, value: V) -> Self {
+        self.var(StyleVarValue::LabelPadding(value.into()))
+    }
+
+    /// Set the legend padding from plot edges.
+    pub fn legend_padding<V: Into<ImVec2>>(self, value: V) -> Self {
+        self.var(StyleVarValue::LegendPadding(value.into()))
+    }
+
+    /// Set the legend inner padding from legend edges.
+    pub fn legend_inner_padding<V: Into<ImVec2>>(self, value: V) -> Self {
+        self.var(StyleVarValue::LegendInnerPadding(value.into()))
+    }
+
+    /// Set the spacing between legend entries.
+    pub fn legend_spacing<V: Into<ImVec2>>(self, value: V) -> Self {
+        self.var(StyleVarValue::LegendSpacing(value.into()))
+    }
+
+    /// Set the padding between plot edge and interior info text.
+    pub fn mouse_pos_padding<V: Into<ImVec2>>(self, value: V) -> Self {
+        self.var(StyleVarValue::MousePosPadding(value.into()))
+    }
+
+    /// Set the text padding around annotation labels.
+    pub fn annotation_padding<V: Into<ImVec2>>(self, value: V) -> Self {
+        self.var(StyleVarValue::AnnotationPadding(value.into()))
+    }
+
+    /// Set the additional fit padding as a fraction of the fit extents (e.g. 0.1 = 10%).
+    pub fn fit_padding<V: Into<ImVec2>>(self, value: V) -> Self {
+        self.var(StyleVarValue::FitPadding(value.into()))
+    }
+
+    /// Set the default size used when the plot size is not given.
+    pub fn plot_default_size<V: Into<ImVec2>>(self, value: V) -> Self {
+        self.var(StyleVarValue::PlotDefaultSize(value.into()))
+    }
+
+    /// Set the minimum size the plot frame can be when shrunk.
+    pub fn plot_min_size<V: Into<ImVec2>>(self, value: V) -> Self {
+        self.var(StyleVarValue::PlotMinSize(value.into()))
+    }
+
+    /// Set the style color of the given element.
+    pub fn color<C: Into<ImVec4>>(mut self, element: PlotColorElement, color: C) -> Self {
+        self.colors.push((element, color.into()));
+        self
+    }
+
+    /// Set whether lines are anti-aliased in all plots.
+    pub fn anti_aliased_lines(mut self, anti_aliased_lines: bool) -> Self {
+        self.anti_aliased_lines = Some(anti_aliased_lines);
+        self
+    }
+
+    /// Set whether time axes use the local timezone.
+    pub fn use_local_time(mut self, use_local_time: bool) -> Self {
+        self.use_local_time = Some(use_local_time);
+        self
+    }
+
+    /// Set whether dates are formatted according to ISO 8601.
+    pub fn use_iso_8601(mut self, use_iso_8601: bool) -> Self {
+        self.use_iso_8601 = Some(use_iso_8601);
+        self
+    }
+
+    /// Set whether times use a 24 hour clock.
+    pub fn use_24_hour_clock(mut self, use_24_hour_clock: bool) -> Self {
+        self.use_24_hour_clock = Some(use_24_hour_clock);
+        self
+    }
+
+    /// Set the colormap. The colormap is not part of the style in ImPlot, so this is applied by
+    /// [`StyleBuilder::apply`] only, and not by [`StyleBuilder::apply_to_style`].
+    pub fn colormap<C: Into<ColormapChoice>>(mut self, colormap: C) -> Self {
+        self.colormap = Some(colormap.into());
+        self
+    }
+
+    /// Check that all configured values are in the range ImPlot can work with: sizes, weights
+    /// and paddings must not be negative, and alpha values must be between 0 and 1. Not-a-number
+    /// values are out of range as well. Returns the first value that is out of range.
+    pub fn validate(&self) -> Result<(), StyleValueError> {
+        let non_negative = |value: f32| value >= 0.0;
+        for value in &self.vars {
+            let in_range = match *value {
+                StyleVarValue::Marker(_) => true,
+                StyleVarValue::LineWeight(value)
+                | StyleVarValue::MarkerSize(value)
+                | StyleVarValue::MarkerWeight(value)
+                | StyleVarValue::ErrorBarSize(value)
+                | StyleVarValue::ErrorBarWeight(value)
+                | StyleVarValue::DigitalBitHeight(value)
+                | StyleVarValue::DigitalBitGap(value)
+                | StyleVarValue::PlotBorderSize(value) => non_negative(value),
+                StyleVarValue::FillAlpha(value) | StyleVarValue::MinorAlpha(value) => {
+                    (0.0..=1.0).contains(&value)
+                }
+                StyleVarValue::MajorTickLen(value)
+                | StyleVarValue::MinorTickLen(value)
+                | StyleVarValue::MajorTickSize(value)
+                | StyleVarValue::MinorTickSize(value)
+                | StyleVarValue::MajorGridSize(value)
+                | StyleVarValue::MinorGridSize(value)
+                | StyleVarValue::PlotPadding(value)
+                | StyleVarValue::LabelPadding(value)
+                | StyleVarValue::LegendPadding(value)
+                | StyleVarValue::LegendInnerPadding(value)
+                | StyleVarValue::LegendSpacing(value)
+                | StyleVarValue::MousePosPadding(value)
+                | StyleVarValue::AnnotationPadding(value)
+                | StyleVarValue::FitPadding(value)
+                | StyleVarValue::PlotDefaultSize(value)
+                | StyleVarValue::PlotMinSize(value) => {
+                    non_negative(value.x) && non_negative(value.y)
+                }
+            };
+            if !in_range {
+                return Err(StyleValueError { value: *value });
+            }
+        }
+        Ok(())
+    }
+
+    /// Write all the configured settings, except for the colormap, to the given style. Nothing is
+    /// changed if any of the values is out of range, see [`StyleBuilder::validate`].
+    pub fn apply_to_style(&self, style: &mut Style) -> Result<(), StyleValueError> {
+        self.validate()?;
+        for value in &self.vars {
+            style.set_var(*value);
+        }
+        for (element, color) in &self.colors {
+            style[*element] = *color;
+        }
+        let flags = [
+            (self.anti_aliased_lines, &mut style.anti_aliased_lines),
+            (self.use_local_time, &mut style.use_local_time),
+            (self.use_iso_8601, &mut style.use_iso_8601),
+            (self.use_24_hour_clock, &mut style.use_24_hour_clock),
+        ];
+        for (configured, field) in flags {
+            if let Some(configured) = configured {
+                *field = configured;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write all the configured settings to the style of the given context, and set the
+    /// colormap if one was configured. The colors are set with
+    /// [`Context::set_style_color`](struct.Context.html#method.set_style_color), so they are
+    /// kept by [`sync_style_with_imgui`](fn.sync_style_with_imgui.html). Nothing is changed if
+    /// any of the values is out of range, see [`StyleBuilder::validate`].
+    pub fn apply<C: ContextMut + ?Sized>(&self, context: &mut C) -> Result<(), StyleValueError> {
+        let context = context_mut(context);
+        let mut style = context.style();
+        self.apply_to_style(&mut style)?;
+        context.set_style(&style);
+        // Remembered as overrides, so they survive sync_style_with_imgui
+        for (element, color) in &self.colors {
+            context.set_style_color(*element, *color);
+        }
+        if let Some(colormap) = self.colormap {
+            context.set_default_colormap(colormap);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the lines of the Rust code for `style` that differ from those for the default
+    /// style, which is one line per changed setting.
+    fn changed_lines(style: &Style) -> Vec<String> {
+        let default_code = Style::default().to_rust_code();
+        let default_lines = default_code.lines().collect::<Vec<_>>();
+        style
+            .to_rust_code()
+            .lines()
+            .filter(|line| !default_lines.contains(line))
+            .map(|line| line.to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_apply_changes_only_configured_settings() {
+        let mut style = Style::default();
+        StyleBuilder::new()
+            .line_weight(2.0)
+            .marker_size(3.0)
+            .plot_padding([8.0, 8.0])
+            .marker(Marker::Circle)
+            .color(PlotColorElement::PlotBg, [0.5, 0.25, 0.0, 1.0])
+            .use_24_hour_clock(true)
+            .apply_to_style(&mut style)
+            .unwrap();
+        let mut changed = changed_lines(&style);
+        changed.sort();
+        let mut expected = vec![
+            "style.line_weight = 2.0;".to_owned(),
+            "style.marker_size = 3.0;".to_owned(),
+            "style.plot_padding = ImVec2 { x: 8.0, y: 8.0 };".to_owned(),
+            "style.set_marker(Marker::Circle);".to_owned(),
+            "style[PlotColorElement::PlotBg] = ImVec4 { x: 0.5, y: 0.25, z: 0.0, w: 1.0 };"
+                .to_owned(),
+            "style.use_24_hour_clock = true;".to_owned(),
+        ];
+        expected.sort();
+        assert_eq!(changed, expected);
+    }
+
+    #[test]
+    fn test_out_of_range_values_change_nothing() {
+        for builder in &[
+            StyleBuilder::new().line_weight(2.0).marker_size(-1.0),
+            StyleBuilder::new().fill_alpha(1.5),
+            StyleBuilder::new().minor_alpha(f32::NAN),
+            StyleBuilder::new().plot_padding([8.0, -8.0]),
+        ] {
+            let mut style = Style::default();
+            assert!(builder.apply_to_style(&mut style).is_err());
+            assert!(changed_lines(&style).is_empty());
+        }
+    }
+}
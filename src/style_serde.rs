@@ -0,0 +1,350 @@
+//! # Style serialization module
+//!
+//! This module contains [`SerializableStyle`], a form of [`Style`] that can be saved and loaded
+//! with serde, for example to persist a theme the user put together with the style editor. It is
+//! only available with the `serde` feature enabled.
+use crate::style::COLOR_ELEMENTS;
+use crate::{ColormapChoice, Context, ImVec2, ImVec4, Marker, Style};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Version of the serialized format written by this version of the crate.
+const FORMAT_VERSION: u32 = 1;
+
+/// A [`Style`] in a form that is suitable for serialization, created with
+/// [`Style::to_serializable`] and turned back into a style with [`Style::from_serializable`].
+///
+/// The format is meant to be stable across versions of this crate and of ImPlot: unknown fields
+/// are ignored when loading, and fields that are missing are set to ImPlot's default values
+/// (which is also what [`SerializableStyle::default`] contains). Markers and colors are stored
+/// by name, and colors that are missing stay automatic. [`Context::serializable_style`] also
+/// stores the colormap of the context:
+/// ```no_run
+/// # use implot::{Context, SerializableStyle, Style};
+/// # fn example(context: &mut Context) -> Result<(), serde_json::Error> {
+/// let saved_json = serde_json::to_string(&context.serializable_style())?;
+/// // Later on, for example after restarting the application
+/// let saved: SerializableStyle = serde_json::from_str(&saved_json)?;
+/// context.set_style(&Style::from_serializable(&saved));
+/// if let Some(colormap) = saved.colormap_choice() {
+///     context.set_default_colormap(colormap);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SerializableStyle {
+    /// Version of the format, for handling format changes in the future
+    pub version: u32,
+    /// See [`Style::line_weight`]
+    pub line_weight: f32,
+    /// Name of the marker, such as `"Circle"`, see [`Style::marker`]
+    pub marker: String,
+    /// See [`Style::marker_size`]
+    pub marker_size: f32,
+    /// See [`Style::marker_weight`]
+    pub marker_weight: f32,
+    /// See [`Style::fill_alpha`]
+    pub fill_alpha: f32,
+    /// See [`Style::error_bar_size`]
+    pub error_bar_size: f32,
+    /// See [`Style::error_bar_weight`]
+    pub error_bar_weight: f32,
+    /// See [`Style::digital_bit_height`]
+    pub digital_bit_height: f32,
+    /// See [`Style::digital_bit_gap`]
+    pub digital_bit_gap: f32,
+    /// See [`Style::plot_border_size`]
+    pub plot_border_size: f32,
+    /// See [`Style::minor_alpha`]
+    pub minor_alpha: f32,
+    /// See [`Style::major_tick_len`]
+    pub major_tick_len: [f32; 2],
+    /// See [`Style::minor_tick_len`]
+    pub minor_tick_len: [f32; 2],
+    /// See [`Style::major_tick_size`]
+    pub major_tick_size: [f32; 2],
+    /// See [`Style::minor_tick_size`]
+    pub minor_tick_size: [f32; 2],
+    /// See [`Style::major_grid_size`]
+    pub major_grid_size: [f32; 2],
+    /// See [`Style::minor_grid_size`]
+    pub minor_grid_size: [f32; 2],
+    /// See [`Style::plot_padding`]
+    pub plot_padding: [f32; 2],
+    /// See [`Style::label_padding`]
+    pub label_padding: [f32; 2],
+    /// See [`Style::legend_padding`]
+    pub legend_padding: [f32; 2],
+    /// See [`Style::legend_inner_padding`]
+    pub legend_inner_padding: [f32; 2],
+    /// See [`Style::legend_spacing`]
+    pub legend_spacing: [f32; 2],
+    /// See [`Style::mouse_pos_padding`]
+    pub mouse_pos_padding: [f32; 2],
+    /// See [`Style::annotation_padding`]
+    pub annotation_padding: [f32; 2],
+    /// See [`Style::fit_padding`]
+    pub fit_padding: [f32; 2],
+    /// See [`Style::plot_default_size`]
+    pub plot_default_size: [f32; 2],
+    /// See [`Style::plot_min_size`]
+    pub plot_min_size: [f32; 2],
+    /// Colors by the name of their [`PlotColorElement`](enum.PlotColorElement.html), such as
+    /// `"PlotBg"`. Automatic colors have an alpha of -1.
+    pub colors: BTreeMap<String, [f32; 4]>,
+    /// Name of the colormap to use with the style, if any. The colormap is not part of the style
+    /// in ImPlot, so this is set by [`Context::serializable_style`] but not by
+    /// [`Style::to_serializable`]. Apply it with [`SerializableStyle::colormap_choice`] and
+    /// [`Context::set_default_colormap`](struct.Context.html#method.set_default_colormap).
+    pub colormap: Option<String>,
+    /// See [`Style::anti_aliased_lines`]
+    pub anti_aliased_lines: bool,
+    /// See [`Style::use_local_time`]
+    pub use_local_time: bool,
+    /// See [`Style::use_iso_8601`]
+    pub use_iso_8601: bool,
+    /// See [`Style::use_24_hour_clock`]
+    pub use_24_hour_clock: bool,
+}
+
+impl SerializableStyle {
+    /// Returns the colormap with the name stored in [`SerializableStyle::colormap`], or `None`
+    /// if no name is stored or there is no preset or registered colormap with that name.
+    pub fn colormap_choice(&self) -> Option<ColormapChoice> {
+        ColormapChoice::from_name(self.colormap.as_deref()?)
+    }
+}
+
+impl Default for SerializableStyle {
+    /// Returns ImPlot's default style, see [`Style::default`].
+    fn default() -> Self {
+        Style::default().to_serializable()
+    }
+}
+
+/// Internal helpers for converting between the vector types and arrays.
+fn vec2_to_array(value: ImVec2) -> [f32; 2] {
+    [value.x, value.y]
+}
+
+fn array_to_vec2(value: [f32; 2]) -> ImVec2 {
+    ImVec2 {
+        x: value[0],
+        y: value[1],
+    }
+}
+
+impl Context {
+    /// Returns the style of this context in a form that can be serialized with serde, along with
+    /// the name of the colormap set with [`Context::set_default_colormap`], see
+    /// [`SerializableStyle`].
+    pub fn serializable_style(&self) -> SerializableStyle {
+        SerializableStyle {
+            colormap: self
+                .default_colormap()
+                .map(|colormap| colormap.name().to_owned()),
+            ..self.style().to_serializable()
+        }
+    }
+}
+
+impl Style {
+    /// Convert the style into a form that can be serialized with serde, see
+    /// [`SerializableStyle`]. This leaves out the colormap, which is not part of the style,
+    /// see [`Context::serializable_style`] for including it.
+    pub fn to_serializable(&self) -> SerializableStyle {
+        SerializableStyle {
+            version: FORMAT_VERSION,
+            line_weight: self.line_weight,
+            marker: self.marker().unwrap_or(Marker::None).name().to_owned(),
+            marker_size: self.marker_size,
+            marker_weight: self.marker_weight,
+            fill_alpha: self.fill_alpha,
+            error_bar_size: self.error_bar_size,
+            error_bar_weight: self.error_bar_weight,
+            digital_bit_height: self.digital_bit_height,
+            digital_bit_gap: self.digital_bit_gap,
+            plot_border_size: self.plot_border_size,
+            minor_alpha: self.minor_alpha,
+            major_tick_len: vec2_to_array(self.major_tick_len),
+            minor_tick_len: vec2_to_array(self.minor_tick_len),
+            major_tick_size: vec2_to_array(self.major_tick_size),
+            minor_tick_size: vec2_to_array(self.minor_tick_size),
+            major_grid_size: vec2_to_array(self.major_grid_size),
+            minor_grid_size: vec2_to_array(self.minor_grid_size),
+            plot_padding: vec2_to_array(self.plot_padding),
+            label_padding: vec2_to_array(self.label_padding),
+            legend_padding: vec2_to_array(self.legend_padding),
+            legend_inner_padding: vec2_to_array(self.legend_inner_padding),
+            legend_spacing: vec2_to_array(self.legend_spacing),
+            mouse_pos_padding: vec2_to_array(self.mouse_pos_padding),
+            annotation_padding: vec2_to_array(self.annotation_padding),
+            fit_padding: vec2_to_array(self.fit_padding),
+            plot_default_size: vec2_to_array(self.plot_default_size),
+            plot_min_size: vec2_to_array(self.plot_min_size),
+            colors: COLOR_ELEMENTS
+                .iter()
+                .map(|element| {
+                    let color = self[*element];
+                    (
+                        format!("{:?}", element),
+                        [color.x, color.y, color.z, color.w],
+                    )
+                })
+                .collect(),
+            colormap: None,
+            anti_aliased_lines: self.anti_aliased_lines,
+            use_local_time: self.use_local_time,
+            use_iso_8601: self.use_iso_8601,
+            use_24_hour_clock: self.use_24_hour_clock,
+        }
+    }
+
+    /// Create a style from its serializable form, see [`SerializableStyle`]. A marker name that
+    /// is not known results in no marker, and colors that are missing are automatic.
+    pub fn from_serializable(serialized: &SerializableStyle) -> Style {
+        let marker = Marker::from_name(&serialized.marker).unwrap_or(Marker::None);
+        let mut colors = Style::default().colors;
+        for (color, element) in colors.iter_mut().zip(COLOR_ELEMENTS.iter()) {
+            if let Some(value) = serialized.colors.get(&format!("{:?}", element)) {
+                *color = ImVec4 {
+                    x: value[0],
+                    y: value[1],
+                    z: value[2],
+                    w: value[3],
+                };
+            }
+        }
+
+        Style {
+            line_weight: serialized.line_weight,
+            marker: marker as i32,
+            marker_size: serialized.marker_size,
+            marker_weight: serialized.marker_weight,
+            fill_alpha: serialized.fill_alpha,
+            error_bar_size: serialized.error_bar_size,
+            error_bar_weight: serialized.error_bar_weight,
+            digital_bit_height: serialized.digital_bit_height,
+            digital_bit_gap: serialized.digital_bit_gap,
+            plot_border_size: serialized.plot_border_size,
+            minor_alpha: serialized.minor_alpha,
+            major_tick_len: array_to_vec2(serialized.major_tick_len),
+            minor_tick_len: array_to_vec2(serialized.minor_tick_len),
+            major_tick_size: array_to_vec2(serialized.major_tick_size),
+            minor_tick_size: array_to_vec2(serialized.minor_tick_size),
+            major_grid_size: array_to_vec2(serialized.major_grid_size),
+            minor_grid_size: array_to_vec2(serialized.minor_grid_size),
+            plot_padding: array_to_vec2(serialized.plot_padding),
+            label_padding: array_to_vec2(serialized.label_padding),
+            legend_padding: array_to_vec2(serialized.legend_padding),
+            legend_inner_padding: array_to_vec2(serialized.legend_inner_padding),
+            legend_spacing: array_to_vec2(serialized.legend_spacing),
+            mouse_pos_padding: array_to_vec2(serialized.mouse_pos_padding),
+            annotation_padding: array_to_vec2(serialized.annotation_padding),
+            fit_padding: array_to_vec2(serialized.fit_padding),
+            plot_default_size: array_to_vec2(serialized.plot_default_size),
+            plot_min_size: array_to_vec2(serialized.plot_min_size),
+            colors,
+            anti_aliased_lines: serialized.anti_aliased_lines,
+            use_local_time: serialized.use_local_time,
+            use_iso_8601: serialized.use_iso_8601,
+            use_24_hour_clock: serialized.use_24_hour_clock,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "imgui-rs")]
+    use crate::test_support::with_test_frame;
+
+    /// JSON of the default style, which locks down the serialized format
+    const DEFAULT_STYLE_JSON: &str = include_str!("../tests/fixtures/default_style.json");
+
+    #[test]
+    fn test_default_style_matches_fixture() {
+        let fixture: serde_json::Value = serde_json::from_str(DEFAULT_STYLE_JSON).unwrap();
+        assert_eq!(
+            serde_json::to_value(&SerializableStyle::default()).unwrap(),
+            fixture
+        );
+        let loaded: SerializableStyle = serde_json::from_str(DEFAULT_STYLE_JSON).unwrap();
+        assert_eq!(loaded, SerializableStyle::default());
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_live_style_matches_fixture() {
+        // Compares with the style of a real ImPlot context, so the fixture can't drift from
+        // what ImPlot starts out with
+        let fixture: serde_json::Value = serde_json::from_str(DEFAULT_STYLE_JSON).unwrap();
+        with_test_frame(|_, context| {
+            let live = serde_json::to_value(&context.style().to_serializable()).unwrap();
+            assert_eq!(live, fixture);
+        });
+    }
+
+    #[cfg(feature = "imgui-rs")]
+    #[test]
+    fn test_serializable_style_has_the_colormap() {
+        let _guard = crate::test_support::TEST_MUTEX.lock();
+        let imgui = imgui::Context::create();
+        let mut context = Context::create_for(&imgui);
+        assert_eq!(context.serializable_style().colormap, None);
+        context.set_default_colormap(crate::Colormap::Viridis);
+        let serialized = context.serializable_style();
+        assert_eq!(serialized.colormap.as_deref(), Some("Viridis"));
+        assert_eq!(
+            serialized.colormap_choice().map(|colormap| colormap.name()),
+            Some("Viridis")
+        );
+        assert_eq!(context.style().to_serializable().colormap, None);
+    }
+
+    #[test]
+    fn test_style_round_trip() {
+        let mut style = Style {
+            line_weight: 2.5,
+            plot_padding: ImVec2 { x: 3.0, y: 4.0 },
+            use_24_hour_clock: true,
+            ..Style::default()
+        };
+        style.set_marker(Marker::Diamond);
+        style[crate::PlotColorElement::PlotBg] = ImVec4 {
+            x: 0.1,
+            y: 0.2,
+            z: 0.3,
+            w: 0.4,
+        };
+
+        let json = serde_json::to_string(&style.to_serializable()).unwrap();
+        let loaded: SerializableStyle = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, style.to_serializable());
+        assert_eq!(
+            Style::from_serializable(&loaded).to_serializable(),
+            style.to_serializable()
+        );
+    }
+
+    #[test]
+    fn test_unknown_fields_ignored_and_missing_fields_defaulted() {
+        let loaded: SerializableStyle = serde_json::from_str(
+            r#"{
+                "version": 2,
+                "line_weight": 3.0,
+                "some_future_setting": [1, 2, 3],
+                "colors": { "PlotBg": [0.0, 0.0, 0.0, 1.0], "FutureElement": [1.0, 1.0, 1.0, 1.0] }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(loaded.line_weight, 3.0);
+        assert_eq!(loaded.marker_size, SerializableStyle::default().marker_size);
+
+        let style = Style::from_serializable(&loaded);
+        assert_eq!(style[crate::PlotColorElement::PlotBg].w, 1.0);
+        assert_eq!(style[crate::PlotColorElement::Line].w, -1.0);
+    }
+}
@@ -0,0 +1,249 @@
+//! # Serialization module for the re-exported sys types
+//!
+//! This module contains serializable counterparts of [`ImPlotPoint`], [`ImPlotRange`],
+//! [`ImPlotLimits`], [`ImVec2`] and [`ImVec4`], which are defined in the sys crates and can't
+//! implement the serde traits themselves. They convert from and to the sys types with `From`.
+//! Points, ranges and limits are stored with lowercase field names, while vectors are stored as
+//! arrays, the same way as in [`SerializableStyle`](crate::SerializableStyle). It is only
+//! available with the `serde` feature enabled.
+//! [`PlotPoint`](crate::PlotPoint), [`PlotRange`](crate::PlotRange) and
+//! [`PlotLimits`](crate::PlotLimits) implement the serde traits directly and serialize the same
+//! way, so these are only needed for code that works with the sys types.
+//! ```no_run
+//! # use implot::{get_plot_limits_raw, SerializableLimits};
+//! # fn example() -> Result<(), serde_json::Error> {
+//! let saved_json = serde_json::to_string(&SerializableLimits::from(get_plot_limits_raw(None)))?;
+//! // {"x":{"min":0.0,"max":1.0},"y":{"min":0.0,"max":1.0}}
+//! # Ok(())
+//! # }
+//! ```
+use crate::{ImPlotLimits, ImPlotPoint, ImPlotRange, ImVec2, ImVec4};
+use serde::{Deserialize, Serialize};
+
+/// Serializable form of [`ImPlotPoint`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SerializablePoint {
+    /// See `ImPlotPoint::x`
+    pub x: f64,
+    /// See `ImPlotPoint::y`
+    pub y: f64,
+}
+
+impl From<ImPlotPoint> for SerializablePoint {
+    fn from(point: ImPlotPoint) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+        }
+    }
+}
+
+impl From<SerializablePoint> for ImPlotPoint {
+    fn from(point: SerializablePoint) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+        }
+    }
+}
+
+/// Serializable form of [`ImPlotRange`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SerializableRange {
+    /// See `ImPlotRange::Min`
+    pub min: f64,
+    /// See `ImPlotRange::Max`
+    pub max: f64,
+}
+
+impl From<ImPlotRange> for SerializableRange {
+    fn from(range: ImPlotRange) -> Self {
+        Self {
+            min: range.Min,
+            max: range.Max,
+        }
+    }
+}
+
+impl From<SerializableRange> for ImPlotRange {
+    fn from(range: SerializableRange) -> Self {
+        Self {
+            Min: range.min,
+            Max: range.max,
+        }
+    }
+}
+
+/// Serializable form of [`ImPlotLimits`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SerializableLimits {
+    /// See `ImPlotLimits::X`
+    pub x: SerializableRange,
+    /// See `ImPlotLimits::Y`
+    pub y: SerializableRange,
+}
+
+impl From<ImPlotLimits> for SerializableLimits {
+    fn from(limits: ImPlotLimits) -> Self {
+        Self {
+            x: limits.X.into(),
+            y: limits.Y.into(),
+        }
+    }
+}
+
+impl From<SerializableLimits> for ImPlotLimits {
+    fn from(limits: SerializableLimits) -> Self {
+        Self {
+            X: limits.x.into(),
+            Y: limits.y.into(),
+        }
+    }
+}
+
+/// Serializable form of [`ImVec2`], stored as an `[x, y]` array.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "[f32; 2]", into = "[f32; 2]")]
+pub struct SerializableVec2 {
+    /// See `ImVec2::x`
+    pub x: f32,
+    /// See `ImVec2::y`
+    pub y: f32,
+}
+
+impl From<ImVec2> for SerializableVec2 {
+    fn from(vec: ImVec2) -> Self {
+        Self { x: vec.x, y: vec.y }
+    }
+}
+
+impl From<SerializableVec2> for ImVec2 {
+    fn from(vec: SerializableVec2) -> Self {
+        Self { x: vec.x, y: vec.y }
+    }
+}
+
+impl From<[f32; 2]> for SerializableVec2 {
+    fn from([x, y]: [f32; 2]) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<SerializableVec2> for [f32; 2] {
+    fn from(vec: SerializableVec2) -> Self {
+        [vec.x, vec.y]
+    }
+}
+
+/// Serializable form of [`ImVec4`], which is mostly used for colors, stored as an
+/// `[x, y, z, w]` array. For colors, the components are red, green, blue and alpha.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "[f32; 4]", into = "[f32; 4]")]
+pub struct SerializableVec4 {
+    /// See `ImVec4::x`
+    pub x: f32,
+    /// See `ImVec4::y`
+    pub y: f32,
+    /// See `ImVec4::z`
+    pub z: f32,
+    /// See `ImVec4::w`
+    pub w: f32,
+}
+
+impl From<ImVec4> for SerializableVec4 {
+    fn from(vec: ImVec4) -> Self {
+        Self {
+            x: vec.x,
+            y: vec.y,
+            z: vec.z,
+            w: vec.w,
+        }
+    }
+}
+
+impl From<SerializableVec4> for ImVec4 {
+    fn from(vec: SerializableVec4) -> Self {
+        Self {
+            x: vec.x,
+            y: vec.y,
+            z: vec.z,
+            w: vec.w,
+        }
+    }
+}
+
+impl From<[f32; 4]> for SerializableVec4 {
+    fn from([x, y, z, w]: [f32; 4]) -> Self {
+        Self { x, y, z, w }
+    }
+}
+
+impl From<SerializableVec4> for [f32; 4] {
+    fn from(vec: SerializableVec4) -> Self {
+        [vec.x, vec.y, vec.z, vec.w]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_shape() {
+        let point = SerializablePoint::from(ImPlotPoint { x: 1.5, y: -2.0 });
+        assert_eq!(
+            serde_json::to_string(&point).unwrap(),
+            r#"{"x":1.5,"y":-2.0}"#
+        );
+
+        let limits = SerializableLimits::from(ImPlotLimits {
+            X: ImPlotRange { Min: 0.0, Max: 1.0 },
+            Y: ImPlotRange {
+                Min: -5.0,
+                Max: 5.0,
+            },
+        });
+        assert_eq!(
+            serde_json::to_string(&limits).unwrap(),
+            r#"{"x":{"min":0.0,"max":1.0},"y":{"min":-5.0,"max":5.0}}"#
+        );
+
+        let vec2 = SerializableVec2::from(ImVec2 { x: 3.0, y: 4.0 });
+        assert_eq!(serde_json::to_string(&vec2).unwrap(), r#"[3.0,4.0]"#);
+
+        let vec4 = SerializableVec4::from(ImVec4 {
+            x: 0.25,
+            y: 0.5,
+            z: 0.75,
+            w: 1.0,
+        });
+        assert_eq!(
+            serde_json::to_string(&vec4).unwrap(),
+            r#"[0.25,0.5,0.75,1.0]"#
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let json = r#"{"x":{"min":-1.0,"max":2.0},"y":{"min":3.0,"max":4.5}}"#;
+        let limits: ImPlotLimits = serde_json::from_str::<SerializableLimits>(json)
+            .unwrap()
+            .into();
+        assert_eq!((limits.X.Min, limits.X.Max), (-1.0, 2.0));
+        assert_eq!((limits.Y.Min, limits.Y.Max), (3.0, 4.5));
+        assert_eq!(
+            serde_json::to_string(&SerializableLimits::from(limits)).unwrap(),
+            json
+        );
+
+        // Vectors are read from arrays, like the ones in a serialized style
+        let vec2: ImVec2 = serde_json::from_str::<SerializableVec2>("[3.0,4.0]")
+            .unwrap()
+            .into();
+        assert_eq!((vec2.x, vec2.y), (3.0, 4.0));
+        let vec4: ImVec4 = serde_json::from_str::<SerializableVec4>("[0.25,0.5,0.75,1.0]")
+            .unwrap()
+            .into();
+        assert_eq!((vec4.x, vec4.y, vec4.z, vec4.w), (0.25, 0.5, 0.75, 1.0));
+    }
+}
@@ -0,0 +1,71 @@
+//! # Test support module
+//!
+//! This module contains helpers for the tests that need an imgui and an ImPlot context, such as
+//! the tests of the plot elements. The current contexts are global state, so these tests take
+//! [`TEST_MUTEX`] to not run in parallel.
+use crate::{Context, FrameStats, Plot, PlotUi};
+
+/// Lock for the tests that use the current imgui and ImPlot contexts.
+pub(crate) static TEST_MUTEX: parking_lot::Mutex<()> = parking_lot::const_mutex(());
+
+/// Create an imgui and an ImPlot context, and call `f` in each of `frames` frames with the
+/// `PlotUi` of the frame and the ImPlot context.
+pub(crate) fn with_test_frames<F: FnMut(&PlotUi, &Context)>(frames: usize, mut f: F) {
+    with_test_ui_frames(frames, |_, plot_ui, context| f(plot_ui, context));
+}
+
+/// Like [`with_test_frames`], but also passes the imgui `Ui` of the frame, for code that shows
+/// imgui widgets besides plots.
+pub(crate) fn with_test_ui_frames<F: FnMut(&imgui::Ui, &PlotUi, &Context)>(
+    frames: usize,
+    mut f: F,
+) {
+    let _guard = TEST_MUTEX.lock();
+    let mut imgui = imgui::Context::create();
+    imgui.io_mut().display_size = [800.0, 600.0];
+    imgui.fonts().build_rgba32_texture();
+    let context = Context::create_for(&imgui);
+
+    for _ in 0..frames {
+        let ui = imgui.new_frame();
+        let plot_ui = context.get_plot_ui(ui);
+        f(ui, &plot_ui, &context);
+        imgui.render();
+    }
+    drop(context);
+}
+
+/// Like [`with_test_frames`], with a single frame.
+pub(crate) fn with_test_frame<F: FnOnce(&PlotUi, &Context)>(f: F) {
+    let mut f = Some(f);
+    with_test_frames(1, |plot_ui, context| {
+        if let Some(f) = f.take() {
+            f(plot_ui, context);
+        }
+    });
+}
+
+/// Build a plot with `f` in its closure, with frame stats turned on, and return the stats of
+/// what was plotted in it.
+pub(crate) fn stats_of_test_plot<F: FnOnce()>(f: F) -> FrameStats {
+    let mut stats = FrameStats::default();
+    with_test_frame(|plot_ui, context| {
+        context.set_frame_stats_enabled(true);
+        Plot::new("test plot").build(plot_ui, f);
+        stats = context.current_frame_stats();
+        context.set_frame_stats_enabled(false);
+    });
+    stats
+}
+
+/// Build a plot with `f` in its closure, which is expected to panic, and return the panic
+/// message.
+pub(crate) fn panic_message_of_test_plot<F: FnOnce()>(plot_ui: &PlotUi, f: F) -> String {
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        Plot::new("panicking test plot").build(plot_ui, f);
+    }));
+    *result
+        .expect_err("The plot was expected to panic")
+        .downcast::<String>()
+        .expect("The panic message was expected to be a String")
+}
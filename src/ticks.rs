@@ -0,0 +1,515 @@
+//! # Ticks module
+//!
+//! This module contains nice tick placement as plain Rust functions, for code that needs ticks
+//! that fit ImPlot's grid, such as custom labels or overlays. The functions are ports of the
+//! tick placement in implot.cpp of the bundled ImPlot version, but they are only tested against
+//! values worked out from that code by hand, not against the ticks ImPlot draws, so the two can
+//! differ. Even where the algorithms agree, ImPlot adds up the tick interval step by step, while
+//! these functions multiply it, which gives "rounder" values.
+//!
+//! For time axes, [`compute_time_ticks`] places labeled ticks at calendar boundaries, for use
+//! with [`Plot::x_ticks_with_labels`](../struct.Plot.html#method.x_ticks_with_labels) when
+//! ImPlot's own time axis formatting does not fit.
+use crate::ImPlotRange;
+
+/// Pixels per major tick that ImPlot aims for on X axes with a linear scale, see
+/// [`compute_ticks`].
+pub const X_TICK_SPACING: f32 = 400.0;
+
+/// Pixels per major tick that ImPlot aims for on Y axes with a linear scale, see
+/// [`compute_ticks`].
+pub const Y_TICK_SPACING: f32 = 300.0;
+
+/// Returns the major tick positions for a linear axis that shows `range` over `pixels` pixels,
+/// aiming for one major tick per `target_spacing_px` pixels, with at least two. The ticks are
+/// "nice" numbers: multiples of 1, 2 or 5 times a power of ten. Inverted ranges give the same
+/// ticks as normalized ones, and empty or non-finite ranges give no ticks.
+///
+/// Use [`X_TICK_SPACING`] or [`Y_TICK_SPACING`] to follow the spacing ImPlot uses. ImPlot also
+/// draws minor ticks, which divide the interval between major ticks into ten parts.
+/// ```
+/// # use implot::{compute_ticks, ImPlotRange, Y_TICK_SPACING};
+/// let range = ImPlotRange { Min: 0.0, Max: 10.0 };
+/// assert_eq!(compute_ticks(&range, 900.0, Y_TICK_SPACING), [0.0, 5.0, 10.0]);
+/// ```
+pub fn compute_ticks(range: &ImPlotRange, pixels: f32, target_spacing_px: f32) -> Vec<f64> {
+    let range = range.normalized();
+    let size = range.size();
+    if !(size > 0.0 && size.is_finite()) {
+        return Vec::new();
+    }
+    let major_count = major_tick_count(pixels, target_spacing_px);
+    let nice_range = nice_number(size * 0.99, false);
+    let interval = nice_number(nice_range / (major_count - 1) as f64, true);
+    let first = (range.Min / interval).floor() as i64;
+    let last = (range.Max / interval).ceil() as i64;
+    (first..=last)
+        .map(|index| index as f64 * interval)
+        .filter(|tick| range.contains(*tick))
+        .collect()
+}
+
+/// Returns the major tick positions for a logarithmic axis that shows `range` over `pixels`
+/// pixels, aiming for one major tick per `target_spacing_px` pixels, with at least two. The
+/// ticks are powers of ten; if there are too many of them, only every third, sixth, ninth and
+/// so on power is used. Ranges that are not completely positive give no ticks.
+///
+/// ImPlot aims for a major tick per 100 pixels on X axes, and per 50 pixels on Y axes, with a
+/// logarithmic scale. Between the major ticks, it draws minor ticks at the multiples of each
+/// power of ten.
+/// ```
+/// # use implot::{compute_log_ticks, ImPlotRange};
+/// let range = ImPlotRange { Min: 1.0, Max: 1e6 };
+/// assert_eq!(compute_log_ticks(&range, 200.0, 100.0), [1.0, 1e3, 1e6]);
+/// ```
+pub fn compute_log_ticks(range: &ImPlotRange, pixels: f32, target_spacing_px: f32) -> Vec<f64> {
+    let range = range.normalized();
+    if !(range.Min > 0.0 && range.Max.is_finite()) {
+        return Vec::new();
+    }
+    let major_count = major_tick_count(pixels, target_spacing_px) as i32;
+    let (log_min, log_max) = (range.Min.log10(), range.Max.log10());
+    // The casts truncate towards zero, like the integer conversions in ImPlot
+    let mut exponent_step = ((log_max - log_min) as i32 / major_count).max(1);
+    let mut exponent_min = log_min as i32;
+    let exponent_max = log_max as i32;
+    if exponent_step != 1 {
+        // Use multiples of three, starting at a multiple of the step
+        while exponent_step % 3 != 0 {
+            exponent_step += 1;
+        }
+        while exponent_min % exponent_step != 0 {
+            exponent_min -= 1;
+        }
+    }
+
+    let mut ticks = Vec::new();
+    let mut exponent = exponent_min - exponent_step;
+    while exponent < exponent_max + exponent_step {
+        let tick = 10f64.powi(exponent);
+        if tick >= range.Min - f64::EPSILON && tick <= range.Max + f64::EPSILON {
+            ticks.push(tick);
+        }
+        exponent += exponent_step;
+    }
+    ticks
+}
+
+/// Pixels per labeled tick that [`compute_time_ticks`] aims for at most.
+const TIME_TICK_SPACING: f32 = 100.0;
+
+/// Largest timestamp magnitude [`compute_time_ticks`] handles, about 31 million years.
+const MAX_TIMESTAMP: f64 = 1e15;
+
+/// Calendar units that time ticks are placed at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TimeUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl TimeUnit {
+    /// Length of the unit in seconds, on average for months and years.
+    fn seconds(self) -> f64 {
+        match self {
+            TimeUnit::Second => 1.0,
+            TimeUnit::Minute => 60.0,
+            TimeUnit::Hour => 3600.0,
+            TimeUnit::Day => 86400.0,
+            TimeUnit::Week => 604_800.0,
+            TimeUnit::Month => 2_629_746.0,
+            TimeUnit::Year => 31_556_952.0,
+        }
+    }
+}
+
+/// Tick intervals for time axes, from the finest to the coarsest. Years continue with nice
+/// numbers of years after these.
+const TIME_STEPS: &[(TimeUnit, i64)] = &[
+    (TimeUnit::Second, 1),
+    (TimeUnit::Second, 5),
+    (TimeUnit::Second, 10),
+    (TimeUnit::Second, 15),
+    (TimeUnit::Second, 30),
+    (TimeUnit::Minute, 1),
+    (TimeUnit::Minute, 5),
+    (TimeUnit::Minute, 10),
+    (TimeUnit::Minute, 15),
+    (TimeUnit::Minute, 30),
+    (TimeUnit::Hour, 1),
+    (TimeUnit::Hour, 3),
+    (TimeUnit::Hour, 6),
+    (TimeUnit::Hour, 12),
+    (TimeUnit::Day, 1),
+    (TimeUnit::Day, 2),
+    (TimeUnit::Week, 1),
+    (TimeUnit::Month, 1),
+    (TimeUnit::Month, 3),
+    (TimeUnit::Month, 6),
+    (TimeUnit::Year, 1),
+];
+
+/// Returns labeled ticks for a time axis that shows `range_seconds`, given as UNIX timestamps
+/// in seconds, over `pixels` pixels. The interval between the ticks is picked from seconds,
+/// minutes, hours, days, weeks, months and years so that there is at most about one tick per
+/// 100 pixels, and the ticks are placed at the boundaries of that unit in UTC, for example at
+/// the first of every third month. Weeks start on Monday, and ticks at midnight within a day
+/// are labeled with their date.
+///
+/// The labels depend on the interval: `12:30:15` for seconds, `12:30` for minutes and hours,
+/// `2021-03-14` for days and weeks, `2021-03` for months and `2021` for years. Ranges that are
+/// empty, not finite or beyond a few million years give no ticks, and ticks are at least a
+/// second apart.
+/// ```no_run
+/// # use implot::{compute_time_ticks, ImPlotRange, Plot, PlotUi};
+/// # fn example(plot_ui: &PlotUi, range: ImPlotRange) {
+/// let ticks = compute_time_ticks(&range, 800.0);
+/// Plot::new("Measurements")
+///     .x_ticks_with_labels(&ticks, false)
+///     .build(plot_ui, || {
+///         // Plot elements
+///     });
+/// # }
+/// ```
+pub fn compute_time_ticks(range_seconds: &ImPlotRange, pixels: f32) -> Vec<(f64, String)> {
+    let range = range_seconds.normalized();
+    let size = range.size();
+    if !(size > 0.0 && range.Min >= -MAX_TIMESTAMP && range.Max <= MAX_TIMESTAMP) {
+        return Vec::new();
+    }
+    let max_ticks = f64::from((pixels / TIME_TICK_SPACING).floor().max(2.0));
+    let (unit, step) = TIME_STEPS
+        .iter()
+        .copied()
+        .find(|(unit, step)| size / (unit.seconds() * *step as f64) <= max_ticks)
+        .unwrap_or_else(|| {
+            let years = size / TimeUnit::Year.seconds();
+            (
+                TimeUnit::Year,
+                nice_number(years / max_ticks, false).ceil() as i64,
+            )
+        });
+
+    let positions = match unit {
+        TimeUnit::Month => calendar_ticks(&range, step, |days| {
+            let (year, month, _) = civil_from_days(days);
+            year * 12 + month - 1
+        }),
+        TimeUnit::Year => calendar_ticks(&range, step * 12, |days| civil_from_days(days).0 * 12),
+        _ => {
+            // 1970-01-05 is the first Monday after the epoch, which weeks are aligned to
+            let offset = if unit == TimeUnit::Week {
+                4.0 * TimeUnit::Day.seconds()
+            } else {
+                0.0
+            };
+            let interval = unit.seconds() * step as f64;
+            let first = ((range.Min - offset) / interval).ceil() as i64;
+            let last = ((range.Max - offset) / interval).floor() as i64;
+            (first..=last)
+                .map(|index| offset + index as f64 * interval)
+                .collect()
+        }
+    };
+    positions
+        .into_iter()
+        .map(|position| (position, format_time_tick(position as i64, unit)))
+        .collect()
+}
+
+/// Internal helper returning the starts of the months within the range whose index, counted
+/// in months since year 0, is a multiple of `step_months`. `month_index` returns the index of
+/// the month that contains the given day.
+fn calendar_ticks<F: Fn(i64) -> i64>(
+    range: &ImPlotRange,
+    step_months: i64,
+    month_index: F,
+) -> Vec<f64> {
+    let first_day = (range.Min / TimeUnit::Day.seconds()).floor() as i64;
+    let first_month = month_index(first_day);
+    let mut index = first_month - first_month.rem_euclid(step_months);
+    let mut ticks = Vec::new();
+    loop {
+        let days = days_from_civil(index.div_euclid(12), index.rem_euclid(12) + 1, 1);
+        let position = days as f64 * TimeUnit::Day.seconds();
+        if position > range.Max {
+            return ticks;
+        }
+        if position >= range.Min {
+            ticks.push(position);
+        }
+        index += step_months;
+    }
+}
+
+/// Internal helper formatting the label of a time tick at the given UNIX timestamp.
+fn format_time_tick(timestamp: i64, unit: TimeUnit) -> String {
+    let days = timestamp.div_euclid(86400);
+    let seconds = timestamp.rem_euclid(86400);
+    let (year, month, day) = civil_from_days(days);
+    let (hour, minute, second) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    match unit {
+        TimeUnit::Second if seconds != 0 => format!("{:02}:{:02}:{:02}", hour, minute, second),
+        TimeUnit::Minute | TimeUnit::Hour if seconds != 0 => format!("{:02}:{:02}", hour, minute),
+        TimeUnit::Month => format!("{:04}-{:02}", year, month),
+        TimeUnit::Year => format!("{:04}", year),
+        _ => format!("{:04}-{:02}-{:02}", year, month, day),
+    }
+}
+
+/// Internal helper returning the number of days since 1970-01-01 of the given date in the
+/// proleptic Gregorian calendar, with months and days counted from 1. This is
+/// `days_from_civil` from Howard Hinnant's "chrono-Compatible Low-Level Date Algorithms".
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_from_march = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * month_from_march + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Internal helper returning the year, month and day of the given number of days since
+/// 1970-01-01, the inverse of [`days_from_civil`].
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Internal helper returning the number of major ticks ImPlot aims for.
+fn major_tick_count(pixels: f32, target_spacing_px: f32) -> usize {
+    let count = (pixels / target_spacing_px).round();
+    if count.is_finite() && count > 2.0 {
+        count as usize
+    } else {
+        2
+    }
+}
+
+/// Internal helper returning a "nice" number close to `x`, which is 1, 2, 5 or 10 times a
+/// power of ten. With `round`, this is the nearest nice number, otherwise the next larger one.
+/// This is `NiceNum` from implot.cpp.
+fn nice_number(x: f64, round: bool) -> f64 {
+    let exponent = x.log10().floor();
+    let fraction = x / 10f64.powf(exponent);
+    let nice_fraction = if round {
+        if fraction < 1.5 {
+            1.0
+        } else if fraction < 3.0 {
+            2.0
+        } else if fraction < 7.0 {
+            5.0
+        } else {
+            10.0
+        }
+    } else if fraction <= 1.0 {
+        1.0
+    } else if fraction <= 2.0 {
+        2.0
+    } else if fraction <= 5.0 {
+        5.0
+    } else {
+        10.0
+    };
+    nice_fraction * 10f64.powf(exponent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(min: f64, max: f64) -> ImPlotRange {
+        ImPlotRange { Min: min, Max: max }
+    }
+
+    fn assert_ticks_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(
+            actual.len(),
+            expected.len(),
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+        for (actual, expected) in actual.iter().zip(expected) {
+            assert!(
+                (actual - expected).abs() <= 1e-9 * expected.abs().max(1.0),
+                "{} != {}",
+                actual,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_nice_number() {
+        assert_eq!(nice_number(0.99, false), 1.0);
+        assert_eq!(nice_number(2.97, false), 5.0);
+        assert_eq!(nice_number(1.4, true), 1.0);
+        assert_eq!(nice_number(1.667, true), 2.0);
+        assert_eq!(nice_number(6.9, true), 5.0);
+        assert_eq!(nice_number(7.0, true), 10.0);
+        assert!((nice_number(0.034, true) - 0.05).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_linear_ticks() {
+        // Reference values worked through ImPlot's AddTicksDefault by hand
+        assert_ticks_close(&compute_ticks(&range(0.0, 1.0), 400.0, 400.0), &[0.0, 1.0]);
+        assert_ticks_close(
+            &compute_ticks(&range(0.0, 10.0), 900.0, 300.0),
+            &[0.0, 5.0, 10.0],
+        );
+        assert_ticks_close(
+            &compute_ticks(&range(-0.3, 2.7), 1200.0, 300.0),
+            &[0.0, 2.0],
+        );
+        assert_ticks_close(
+            &compute_ticks(&range(0.1, 0.4), 1800.0, 300.0),
+            &[0.1, 0.2, 0.3, 0.4],
+        );
+        // Small plots still get two ticks
+        assert_ticks_close(&compute_ticks(&range(0.0, 1.0), 10.0, 400.0), &[0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_linear_ticks_edge_cases() {
+        assert_eq!(
+            compute_ticks(&range(10.0, 0.0), 900.0, 300.0),
+            compute_ticks(&range(0.0, 10.0), 900.0, 300.0)
+        );
+        assert!(compute_ticks(&range(1.0, 1.0), 900.0, 300.0).is_empty());
+        assert!(compute_ticks(&range(0.0, f64::INFINITY), 900.0, 300.0).is_empty());
+        assert!(compute_ticks(&range(0.0, f64::NAN), 900.0, 300.0).is_empty());
+    }
+
+    #[test]
+    fn test_log_ticks() {
+        assert_ticks_close(
+            &compute_log_ticks(&range(1.0, 1e6), 200.0, 100.0),
+            &[1.0, 1e3, 1e6],
+        );
+        assert_ticks_close(
+            &compute_log_ticks(&range(0.5, 50.0), 300.0, 100.0),
+            &[1.0, 10.0],
+        );
+        assert_ticks_close(
+            &compute_log_ticks(&range(1e-3, 1e3), 600.0, 100.0),
+            &[1e-3, 1e-2, 1e-1, 1.0, 10.0, 100.0, 1e3],
+        );
+        assert!(compute_log_ticks(&range(0.0, 10.0), 300.0, 100.0).is_empty());
+        assert!(compute_log_ticks(&range(-1.0, 10.0), 300.0, 100.0).is_empty());
+    }
+
+    fn time_ticks(min: f64, max: f64, pixels: f32) -> Vec<(f64, String)> {
+        compute_time_ticks(&range(min, max), pixels)
+    }
+
+    fn labeled(ticks: &[(f64, &str)]) -> Vec<(f64, String)> {
+        ticks
+            .iter()
+            .map(|(position, label)| (*position, label.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_civil_dates() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+        assert_eq!(days_from_civil(2020, 9, 13), 18518);
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(civil_from_days(11016), (2000, 2, 29));
+        for days in (-800_000..800_000).step_by(997) {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+
+    #[test]
+    fn test_time_ticks_seconds_and_hours() {
+        // 2020-09-13 12:26:40 UTC
+        assert_eq!(
+            time_ticks(1_600_000_000.0, 1_600_000_030.0, 300.0),
+            labeled(&[
+                (1_600_000_000.0, "12:26:40"),
+                (1_600_000_010.0, "12:26:50"),
+                (1_600_000_020.0, "12:27:00"),
+                (1_600_000_030.0, "12:27:10"),
+            ])
+        );
+        // From 20:00 to 04:00 the next day, with midnight labeled by its date
+        assert_eq!(
+            time_ticks(1_600_027_200.0, 1_600_056_000.0, 500.0),
+            labeled(&[
+                (1_600_030_800.0, "21:00"),
+                (1_600_041_600.0, "2020-09-14"),
+                (1_600_052_400.0, "03:00"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_time_ticks_weeks_months_and_years() {
+        // January 2021, with weeks starting on Monday the 4th
+        assert_eq!(
+            time_ticks(1_609_459_200.0, 1_612_051_200.0, 500.0),
+            labeled(&[
+                (1_609_718_400.0, "2021-01-04"),
+                (1_610_323_200.0, "2021-01-11"),
+                (1_610_928_000.0, "2021-01-18"),
+                (1_611_532_800.0, "2021-01-25"),
+            ])
+        );
+        // Mid January to mid June 2021, with months of different lengths
+        assert_eq!(
+            time_ticks(1_610_668_800.0, 1_623_715_200.0, 600.0),
+            labeled(&[
+                (1_612_137_600.0, "2021-02"),
+                (1_614_556_800.0, "2021-03"),
+                (1_617_235_200.0, "2021-04"),
+                (1_619_827_200.0, "2021-05"),
+                (1_622_505_600.0, "2021-06"),
+            ])
+        );
+        // 1970 to 2020 in decades
+        let decades = time_ticks(0.0, 1_577_836_800.0, 500.0);
+        let labels: Vec<&str> = decades.iter().map(|(_, label)| label.as_str()).collect();
+        assert_eq!(labels, ["1970", "1980", "1990", "2000", "2010", "2020"]);
+        assert_eq!(decades[3].0, 946_684_800.0);
+    }
+
+    #[test]
+    fn test_time_ticks_edge_cases() {
+        assert!(time_ticks(5.0, 5.0, 500.0).is_empty());
+        assert!(time_ticks(0.0, f64::INFINITY, 500.0).is_empty());
+        assert!(time_ticks(0.0, f64::NAN, 500.0).is_empty());
+        // Before the epoch
+        assert_eq!(
+            time_ticks(-86400.0 * 3.5, 0.0, 400.0),
+            labeled(&[
+                (-259_200.0, "1969-12-29"),
+                (-172_800.0, "1969-12-30"),
+                (-86_400.0, "1969-12-31"),
+                (0.0, "1970-01-01"),
+            ])
+        );
+    }
+}